@@ -3,6 +3,7 @@
 use clap::value_parser;
 use clap::Arg;
 use clap::ArgAction;
+use clap::ArgGroup;
 use clap::ArgMatches;
 use clap::ColorChoice;
 use clap::Command;
@@ -38,7 +39,11 @@ pub struct BenchFlags {
   pub files: FileFlags,
   pub filter: Option<String>,
   pub json: bool,
+  pub memory: bool,
   pub no_run: bool,
+  pub save_baseline: Option<PathBuf>,
+  pub compare_baseline: Option<PathBuf>,
+  pub max_regression: Option<u32>,
   pub watch: Option<WatchFlags>,
 }
 
@@ -57,6 +62,15 @@ pub struct CacheFlags {
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct CheckFlags {
   pub files: Vec<String>,
+  pub reporter: CheckReporterConfig,
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum CheckReporterConfig {
+  #[default]
+  Pretty,
+  Sarif,
+  Github,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -83,13 +97,26 @@ pub struct CompletionsFlags {
   pub buf: Box<[u8]>,
 }
 
+pub const DEFAULT_COVERAGE_INCLUDE: &str = r"^file:";
+pub const DEFAULT_COVERAGE_EXCLUDE: &str = r"test\.(js|mjs|ts|jsx|tsx)$";
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub enum CoverageType {
+  #[default]
+  Pretty,
+  Lcov,
+  Html,
+  Cobertura,
+  JsonSummary,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct CoverageFlags {
   pub files: FileFlags,
   pub output: Option<PathBuf>,
   pub include: Vec<String>,
   pub exclude: Vec<String>,
-  pub lcov: bool,
+  pub r#type: CoverageType,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -129,6 +156,9 @@ pub struct FmtFlags {
   pub prose_wrap: Option<String>,
   pub no_semicolons: Option<bool>,
   pub watch: Option<WatchFlags>,
+  pub unstable_css: bool,
+  pub unstable_html: bool,
+  pub unstable_yaml: bool,
 }
 
 impl FmtFlags {
@@ -164,6 +194,23 @@ pub struct UninstallFlags {
   pub root: Option<PathBuf>,
 }
 
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum KvSubcommand {
+  Export {
+    database: String,
+    output: Option<PathBuf>,
+  },
+  Import {
+    database: String,
+    input: Option<PathBuf>,
+  },
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct KvFlags {
+  pub subcommand: KvSubcommand,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct LintFlags {
   pub files: FileFlags,
@@ -173,6 +220,8 @@ pub struct LintFlags {
   pub maybe_rules_exclude: Option<Vec<String>>,
   pub json: bool,
   pub compact: bool,
+  pub reporter: Option<String>,
+  pub fix: bool,
   pub watch: Option<WatchFlags>,
 }
 
@@ -226,6 +275,23 @@ pub enum TestReporterConfig {
   Dot,
   Junit,
   Tap,
+  Json,
+}
+
+/// Selects one of `total` deterministic partitions of the collected test
+/// modules, `index` being one based.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct TestShard {
+  pub index: usize,
+  pub total: usize,
+}
+
+impl TestShard {
+  /// Whether the module at `position`, in the sorted list of all collected
+  /// modules, belongs to this shard.
+  pub fn includes(&self, position: usize) -> bool {
+    position % self.total == self.index - 1
+  }
 }
 
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
@@ -240,6 +306,8 @@ pub struct TestFlags {
   pub shuffle: Option<u64>,
   pub concurrent_jobs: Option<NonZeroUsize>,
   pub trace_ops: bool,
+  pub retry: usize,
+  pub shard: Option<TestShard>,
   pub watch: Option<WatchFlags>,
   pub reporter: TestReporterConfig,
   pub junit_path: Option<String>,
@@ -277,6 +345,7 @@ pub enum DenoSubcommand {
   Info(InfoFlags),
   Install(InstallFlags),
   Uninstall(UninstallFlags),
+  Kv(KvFlags),
   Lsp,
   Lint(LintFlags),
   Repl(ReplFlags),
@@ -335,6 +404,27 @@ impl Default for TypeCheckMode {
   }
 }
 
+/// Backend used by `BroadcastChannel`, see `--broadcast-channel-backend`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum BroadcastChannelBackend {
+  /// Only reaches the workers of the same process.
+  #[default]
+  Memory,
+  /// Also reaches other processes through a database in the Deno directory.
+  Sqlite,
+}
+
+/// Backend used by the Cache API, see `--cache-storage-backend`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum CacheStorageBackend {
+  /// Persists caches in a directory per origin.
+  #[default]
+  Sqlite,
+  /// Keeps caches in memory for as long as the worker that opened them is
+  /// alive.
+  Memory,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum CaData {
   /// The string is a file path
@@ -352,6 +442,12 @@ pub struct Flags {
   pub subcommand: DenoSubcommand,
 
   pub allow_all: bool,
+  pub audit_permissions: Option<PathBuf>,
+  pub broadcast_channel_backend: BroadcastChannelBackend,
+  pub cache_storage_backend: CacheStorageBackend,
+  pub cache_quota: Option<u64>,
+  pub cache_storage_quota: Option<u64>,
+  pub local_storage_quota: Option<u64>,
   pub allow_env: Option<Vec<String>>,
   pub deny_env: Option<Vec<String>>,
   pub allow_hrtime: bool,
@@ -678,7 +774,8 @@ impl Flags {
         std::env::current_dir().ok()
       }
       Bundle(_) | Completions(_) | Doc(_) | Fmt(_) | Init(_) | Install(_)
-      | Uninstall(_) | Lsp | Lint(_) | Types | Upgrade(_) | Vendor(_) => None,
+      | Uninstall(_) | Kv(_) | Lsp | Lint(_) | Types | Upgrade(_)
+      | Vendor(_) => None,
     }
   }
 
@@ -817,6 +914,7 @@ pub fn flags_from_vec(args: Vec<String>) -> clap::error::Result<Flags> {
       "init" => init_parse(&mut flags, &mut m),
       "info" => info_parse(&mut flags, &mut m),
       "install" => install_parse(&mut flags, &mut m),
+      "kv" => kv_parse(&mut flags, &mut m),
       "lint" => lint_parse(&mut flags, &mut m),
       "lsp" => lsp_parse(&mut flags, &mut m),
       "repl" => repl_parse(&mut flags, &mut m),
@@ -919,6 +1017,7 @@ fn clap_root() -> Command {
         .subcommand(info_subcommand())
         .subcommand(install_subcommand())
         .subcommand(uninstall_subcommand())
+        .subcommand(kv_subcommand())
         .subcommand(lsp_subcommand())
         .subcommand(lint_subcommand())
         .subcommand(repl_subcommand())
@@ -987,6 +1086,38 @@ glob {*_,*.,}bench.{js,mjs,ts,mts,jsx,tsx}:
             .help("Cache bench modules, but don't run benchmarks")
             .action(ArgAction::SetTrue),
         )
+        .arg(
+          Arg::new("memory")
+            .long("memory")
+            .action(ArgAction::SetTrue)
+            .help("Collect V8 heap usage statistics of every bench"),
+        )
+        .arg(
+          Arg::new("save-baseline")
+            .long("save-baseline")
+            .value_name("FILE")
+            .require_equals(true)
+            .help("Save the results to FILE, to compare later runs against")
+            .conflicts_with("watch")
+            .value_parser(value_parser!(PathBuf)),
+        )
+        .arg(
+          Arg::new("compare-baseline")
+            .long("compare-baseline")
+            .value_name("FILE")
+            .require_equals(true)
+            .help("Compare the results against a baseline saved with --save-baseline")
+            .value_parser(value_parser!(PathBuf)),
+        )
+        .arg(
+          Arg::new("max-regression")
+            .long("max-regression")
+            .value_name("PERCENT")
+            .require_equals(true)
+            .requires("compare-baseline")
+            .help("Fail when a benchmark is significantly slower than the baseline by more than PERCENT")
+            .value_parser(value_parser!(u32)),
+        )
         .arg(watch_arg(false))
         .arg(no_clear_screen_arg())
         .arg(script_arg().last(true))
@@ -1075,6 +1206,13 @@ Unless --reload is specified, this command will not re-download already cached d
           .conflicts_with("no-remote")
           .hide(true)
       )
+      .arg(
+        Arg::new("reporter")
+          .long("reporter")
+          .help("Select reporter to use for type errors. Default to 'pretty'.")
+          .value_parser(["pretty", "sarif", "github"])
+          .require_equals(true)
+      )
       .arg(
         Arg::new("file")
           .num_args(1..)
@@ -1205,9 +1343,15 @@ Write a report using the lcov format:
 
   deno coverage --lcov --output=cov.lcov cov_profile/
 
-Generate html reports from lcov:
+Write a browsable html report to the html_cov directory:
 
-  genhtml -o html_cov cov.lcov
+  deno coverage --html --output=html_cov cov_profile/
+
+Write a report in the Cobertura xml format, or a json summary of the totals
+and per file percentages:
+
+  deno coverage --cobertura --output=cobertura.xml cov_profile/
+  deno coverage --json --output=coverage.json cov_profile/
 ",
     )
     .defer(|cmd| {
@@ -1228,7 +1372,7 @@ Generate html reports from lcov:
             .action(ArgAction::Append)
             .value_name("regex")
             .require_equals(true)
-            .default_value(r"^file:")
+            .default_value(DEFAULT_COVERAGE_INCLUDE)
             .help("Include source files in the report"),
         )
         .arg(
@@ -1238,7 +1382,7 @@ Generate html reports from lcov:
             .action(ArgAction::Append)
             .value_name("regex")
             .require_equals(true)
-            .default_value(r"test\.(js|mjs|ts|jsx|tsx)$")
+            .default_value(DEFAULT_COVERAGE_EXCLUDE)
             .help("Exclude source files from the report"),
         )
         .arg(
@@ -1247,19 +1391,45 @@ Generate html reports from lcov:
             .help("Output coverage report in lcov format")
             .action(ArgAction::SetTrue),
         )
+        .arg(
+          Arg::new("html")
+            .long("html")
+            .help("Output coverage report in html format")
+            .action(ArgAction::SetTrue),
+        )
+        .arg(
+          Arg::new("cobertura")
+            .long("cobertura")
+            .help("Output coverage report in Cobertura xml format")
+            .action(ArgAction::SetTrue),
+        )
+        .arg(
+          Arg::new("json")
+            .long("json")
+            .help("Output a coverage summary in json format")
+            .action(ArgAction::SetTrue),
+        )
+        .group(ArgGroup::new("format").args([
+          "lcov",
+          "html",
+          "cobertura",
+          "json",
+        ]))
         .arg(
           Arg::new("output")
-            .requires("lcov")
+            .requires("format")
             .long("output")
             .value_parser(value_parser!(PathBuf))
-            .help("Output file (defaults to stdout) for lcov")
+            .help("Output file, or output directory for html")
             .long_help(
-              "Exports the coverage report in lcov format to the given file.
+              "Exports the coverage report to the given file.
     Filename should be passed along with '=' For example '--output=foo.lcov'
-    If no --output arg is specified then the report is written to stdout.",
+    If no --output arg is specified then the report is written to stdout.
+    With --html, the report is written to the given directory instead,
+    which defaults to 'html_cov'.",
             )
             .require_equals(true)
-            .value_hint(ValueHint::FilePath),
+            .value_hint(ValueHint::AnyPath),
         )
         .arg(
           Arg::new("files")
@@ -1390,6 +1560,13 @@ fn fmt_subcommand() -> Command {
   deno fmt myfile1.ts myfile2.ts
   deno fmt --check
 
+Formatting CSS (including SCSS, Sass and Less), HTML and YAML files, and code
+blocks in those languages inside Markdown files, is unstable and needs to be
+enabled with --unstable-css, --unstable-html and --unstable-yaml, or with
+\"fmt-css\", \"fmt-html\" and \"fmt-yaml\" in the \"unstable\" array of the
+config file. They use the same options as the other languages (line width,
+indent width, tabs and quotes); semicolons and prose wrap don't apply to them.
+
 Format stdin and write to stdout:
 
   cat file.ts | deno fmt -
@@ -1418,7 +1595,10 @@ Ignore formatting a file by adding an ignore comment at the top of the file:
             .help("Set content type of the supplied file")
             // prefer using ts for formatting instead of js because ts works in more scenarios
             .default_value("ts")
-            .value_parser(["ts", "tsx", "js", "jsx", "md", "json", "jsonc"]),
+            .value_parser([
+              "ts", "tsx", "js", "jsx", "md", "json", "jsonc", "css", "scss",
+              "sass", "less", "html", "yaml", "yml",
+            ]),
         )
         .arg(
           Arg::new("ignore")
@@ -1493,6 +1673,24 @@ Ignore formatting a file by adding an ignore comment at the top of the file:
             .require_equals(true)
             .help("Don't use semicolons except where necessary."),
         )
+        .arg(
+          Arg::new("unstable-css")
+            .long("unstable-css")
+            .help("Enable formatting CSS, SCSS, Sass and Less files.")
+            .action(ArgAction::SetTrue),
+        )
+        .arg(
+          Arg::new("unstable-html")
+            .long("unstable-html")
+            .help("Enable formatting HTML files.")
+            .action(ArgAction::SetTrue),
+        )
+        .arg(
+          Arg::new("unstable-yaml")
+            .long("unstable-yaml")
+            .help("Enable formatting YAML files.")
+            .action(ArgAction::SetTrue),
+        )
     })
 }
 
@@ -1650,6 +1848,63 @@ https://deno.land/manual@v",
   "/getting_started/setup_your_environment#editors-and-ides",
 );
 
+fn kv_subcommand() -> Command {
+  Command::new("kv")
+    .about("UNSTABLE: Export and import Deno KV databases")
+    .long_about(
+      "UNSTABLE: Export and import Deno KV databases.
+
+The contents of a database are written as newline-delimited JSON, one entry
+per line, including keys, values, versionstamps and expiration times.
+Databases are specified either as the path to a local SQLite database, or as
+the URL of a remote database. Only local databases can be exported; an export
+is a consistent snapshot of the database, even while it is being written to.
+
+  deno kv export ./db.sqlite3 > backup.ndjson
+  deno kv import ./db.sqlite3 backup.ndjson
+
+Entries can be copied between databases by piping one command into the other:
+
+  deno kv export ./db.sqlite3 | deno kv import https://example.com/my-db",
+    )
+    .subcommand_required(true)
+    .defer(|cmd| {
+      cmd
+        .subcommand(
+          Command::new("export")
+            .about("Write all entries of a database to a file or stdout")
+            .arg(
+              Arg::new("database")
+                .help("The path of the database to export")
+                .required(true),
+            )
+            .arg(
+              Arg::new("output")
+                .long("output")
+                .short('o')
+                .help("The file to write to. Defaults to stdout")
+                .value_parser(value_parser!(PathBuf))
+                .value_hint(ValueHint::FilePath),
+            ),
+        )
+        .subcommand(
+          Command::new("import")
+            .about("Write all entries from a file or stdin into a database")
+            .arg(
+              Arg::new("database")
+                .help("The path or URL of the database to import into")
+                .required(true),
+            )
+            .arg(
+              Arg::new("input")
+                .help("The file to read from. Defaults to stdin")
+                .value_parser(value_parser!(PathBuf))
+                .value_hint(ValueHint::FilePath),
+            ),
+        )
+    })
+}
+
 fn lsp_subcommand() -> Command {
   Command::new("lsp")
     .about("Start the language server")
@@ -1674,6 +1929,15 @@ Read from stdin:
   cat file.ts | deno lint -
   cat file.ts | deno lint --json -
 
+Print result as a SARIF log or as GitHub Actions annotations:
+
+  deno lint --reporter=sarif
+  deno lint --reporter=github
+
+Fix problems that have a safe automatic fix:
+
+  deno lint --fix
+
 List available rules:
 
   deno lint --rules
@@ -1693,6 +1957,12 @@ Ignore linting a file by adding an ignore comment at the top of the file:
     )
     .defer(|cmd| {
       cmd
+        .arg(
+          Arg::new("fix")
+            .long("fix")
+            .help("Fix any linting errors for rules that support it")
+            .action(ArgAction::SetTrue),
+        )
         .arg(
           Arg::new("rules")
             .long("rules")
@@ -1751,6 +2021,14 @@ Ignore linting a file by adding an ignore comment at the top of the file:
             .action(ArgAction::SetTrue)
             .conflicts_with("json"),
         )
+        .arg(
+          Arg::new("reporter")
+            .long("reporter")
+            .help("Select reporter to use. Default to 'pretty'.")
+            .value_parser(["pretty", "compact", "json", "sarif", "github"])
+            .require_equals(true)
+            .conflicts_with_all(["json", "compact"]),
+        )
         .arg(
           Arg::new("files")
             .value_parser(value_parser!(PathBuf))
@@ -1929,6 +2207,29 @@ Directory arguments are expanded to all contained files matching the glob
         .require_equals(true)
         .value_parser(value_parser!(u64)),
     )
+    .arg(
+      Arg::new("retry")
+        .long("retry")
+        .value_name("N")
+        .help("Re-run failed tests up to N times, reporting tests that pass on a retry as flaky")
+        .require_equals(true)
+        .value_parser(value_parser!(usize)),
+    )
+    .arg(
+      Arg::new("shard")
+        .long("shard")
+        .value_name("INDEX/TOTAL")
+        .help("Only run the INDEX-th of TOTAL deterministic partitions of the test modules")
+        .long_help(
+          "Split the test modules into TOTAL partitions and only run the INDEX-th of them, \
+counting from 1. Modules are assigned by their sorted position, so every machine \
+running the same set of modules gets the same partitions. Useful for splitting \
+a test suite across parallel CI jobs.",
+        )
+        .require_equals(true)
+        .conflicts_with("watch")
+        .value_parser(test_shard_parse),
+    )
     .arg(
       Arg::new("coverage")
         .long("coverage")
@@ -1981,7 +2282,7 @@ Directory arguments are expanded to all contained files matching the glob
       Arg::new("reporter")
         .long("reporter")
         .help("Select reporter to use. Default to 'pretty'.")
-        .value_parser(["pretty", "dot", "junit", "tap"])
+        .value_parser(["pretty", "dot", "junit", "tap", "json"])
     )
   )
 }
@@ -2162,17 +2463,18 @@ static DENY_WRITE_HELP: &str = concat!(
 );
 
 static ALLOW_NET_HELP: &str = concat!(
-  "Allow network access. Optionally specify allowed IP addresses and host names, with ports as necessary.\n",
+  "Allow network access. Optionally specify allowed IP addresses, CIDR ranges and host names (optionally \"*.\" wildcards), with ports or port ranges as necessary.\n",
   "Docs: https://deno.land/manual@v",
   env!("CARGO_PKG_VERSION"),
   "/basics/permissions\n",
   "Examples:\n",
   "  --allow-net\n",
-  "  --allow-net=\"localhost:8080,deno.land\""
+  "  --allow-net=\"localhost:8080,deno.land\"\n",
+  "  --allow-net=\"10.0.0.0/8,*.example.com,localhost:8000-8100\""
 );
 
 static DENY_NET_HELP: &str = concat!(
-  "Deny network access. Optionally specify denied IP addresses and host names, with ports as necessary.\n",
+  "Deny network access. Optionally specify denied IP addresses, CIDR ranges and host names (optionally \"*.\" wildcards), with ports or port ranges as necessary.\n",
   "Docs: https://deno.land/manual@v",
   env!("CARGO_PKG_VERSION"),
   "/basics/permissions\n",
@@ -2483,6 +2785,15 @@ fn permission_args(app: Command) -> Command {
         .action(ArgAction::SetTrue)
         .help("Always throw if required permission wasn't passed"),
     )
+    .arg(
+      Arg::new("audit-permissions")
+        .long("audit-permissions")
+        .require_equals(true)
+        .value_name("FILE")
+        .help("Record every permission check to a file as JSON lines")
+        .value_parser(value_parser!(PathBuf))
+        .value_hint(ValueHint::FilePath),
+    )
 }
 
 fn runtime_args(
@@ -2506,6 +2817,11 @@ fn runtime_args(
     .arg(location_arg())
     .arg(v8_flags_arg())
     .arg(seed_arg())
+    .arg(broadcast_channel_backend_arg())
+    .arg(cache_storage_backend_arg())
+    .arg(cache_quota_arg())
+    .arg(cache_storage_quota_arg())
+    .arg(local_storage_quota_arg())
     .arg(enable_testing_features_arg())
 }
 
@@ -2636,6 +2952,69 @@ fn location_arg() -> Arg {
     .value_hint(ValueHint::Url)
 }
 
+fn broadcast_channel_backend_arg() -> Arg {
+  Arg::new("broadcast-channel-backend")
+    .long("broadcast-channel-backend")
+    .value_name("BACKEND")
+    .require_equals(true)
+    .value_parser(["memory", "sqlite"])
+    .help("Backend used by 'BroadcastChannel'")
+    .long_help(
+      "Backend used by 'BroadcastChannel'. 'memory' (the default) only reaches \
+workers of the same process, 'sqlite' also reaches other processes on this \
+machine with the same origin through a database in the Deno directory.",
+    )
+}
+
+fn cache_storage_backend_arg() -> Arg {
+  Arg::new("cache-storage-backend")
+    .long("cache-storage-backend")
+    .value_name("BACKEND")
+    .require_equals(true)
+    .value_parser(["sqlite", "memory"])
+    .help("Backend used by the Cache API")
+    .long_help(
+      "Backend used by the Cache API. 'sqlite' (the default) persists caches \
+in a directory per origin, 'memory' keeps them in memory for as long as the \
+worker that opened them is alive.",
+    )
+}
+
+fn cache_quota_arg() -> Arg {
+  Arg::new("cache-quota")
+    .long("cache-quota")
+    .value_name("MEGABYTES")
+    .require_equals(true)
+    .value_parser(value_parser!(u64).range(1..))
+    .help("Maximum size in megabytes of each cache of the Cache API")
+    .long_help(
+      "Maximum size in megabytes of each cache of the Cache API. Once a cache \
+grows larger, its least recently used responses are evicted.",
+    )
+}
+
+fn cache_storage_quota_arg() -> Arg {
+  Arg::new("cache-storage-quota")
+    .long("cache-storage-quota")
+    .value_name("MEGABYTES")
+    .require_equals(true)
+    .value_parser(value_parser!(u64).range(1..))
+    .help("Maximum size in megabytes of all caches of the Cache API together")
+    .long_help(
+      "Maximum size in megabytes of all caches of the Cache API together. Once \
+they grow larger, the least recently used responses of any cache are evicted.",
+    )
+}
+
+fn local_storage_quota_arg() -> Arg {
+  Arg::new("local-storage-quota")
+    .long("local-storage-quota")
+    .value_name("MEGABYTES")
+    .require_equals(true)
+    .value_parser(value_parser!(u64).range(1..))
+    .help("Maximum size in megabytes of 'localStorage' and 'sessionStorage' per origin, defaults to 10")
+}
+
 fn enable_testing_features_arg() -> Arg {
   Arg::new("enable-testing-features-do-not-use")
     .long("enable-testing-features-do-not-use")
@@ -2887,12 +3266,20 @@ fn bench_parse(flags: &mut Flags, matches: &mut ArgMatches) {
   };
 
   let no_run = matches.get_flag("no-run");
+  let memory = matches.get_flag("memory");
+  let save_baseline = matches.remove_one::<PathBuf>("save-baseline");
+  let compare_baseline = matches.remove_one::<PathBuf>("compare-baseline");
+  let max_regression = matches.remove_one::<u32>("max-regression");
 
   flags.subcommand = DenoSubcommand::Bench(BenchFlags {
     files: FileFlags { include, ignore },
     filter,
     json,
+    memory,
     no_run,
+    save_baseline,
+    compare_baseline,
+    max_regression,
     watch: watch_arg_parse(matches),
   });
 }
@@ -2934,7 +3321,12 @@ fn check_parse(flags: &mut Flags, matches: &mut ArgMatches) {
   if matches.get_flag("all") || matches.get_flag("remote") {
     flags.type_check_mode = TypeCheckMode::All;
   }
-  flags.subcommand = DenoSubcommand::Check(CheckFlags { files });
+  let reporter = match matches.remove_one::<String>("reporter").as_deref() {
+    Some("sarif") => CheckReporterConfig::Sarif,
+    Some("github") => CheckReporterConfig::Github,
+    _ => CheckReporterConfig::Pretty,
+  };
+  flags.subcommand = DenoSubcommand::Check(CheckFlags { files, reporter });
 }
 
 fn compile_parse(flags: &mut Flags, matches: &mut ArgMatches) {
@@ -3009,7 +3401,17 @@ fn coverage_parse(flags: &mut Flags, matches: &mut ArgMatches) {
     Some(f) => f.collect(),
     None => vec![],
   };
-  let lcov = matches.get_flag("lcov");
+  let r#type = if matches.get_flag("lcov") {
+    CoverageType::Lcov
+  } else if matches.get_flag("html") {
+    CoverageType::Html
+  } else if matches.get_flag("cobertura") {
+    CoverageType::Cobertura
+  } else if matches.get_flag("json") {
+    CoverageType::JsonSummary
+  } else {
+    CoverageType::Pretty
+  };
   let output = matches.remove_one::<PathBuf>("output");
   flags.subcommand = DenoSubcommand::Coverage(CoverageFlags {
     files: FileFlags {
@@ -3019,7 +3421,7 @@ fn coverage_parse(flags: &mut Flags, matches: &mut ArgMatches) {
     output,
     include,
     exclude,
-    lcov,
+    r#type,
   });
 }
 
@@ -3117,6 +3519,9 @@ fn fmt_parse(flags: &mut Flags, matches: &mut ArgMatches) {
     prose_wrap,
     no_semicolons,
     watch: watch_arg_parse(matches),
+    unstable_css: matches.get_flag("unstable-css"),
+    unstable_html: matches.get_flag("unstable-html"),
+    unstable_yaml: matches.get_flag("unstable-yaml"),
   });
 }
 
@@ -3172,6 +3577,21 @@ fn uninstall_parse(flags: &mut Flags, matches: &mut ArgMatches) {
   flags.subcommand = DenoSubcommand::Uninstall(UninstallFlags { name, root });
 }
 
+fn kv_parse(flags: &mut Flags, matches: &mut ArgMatches) {
+  let subcommand = match matches.remove_subcommand() {
+    Some((name, mut m)) if name == "export" => KvSubcommand::Export {
+      database: m.remove_one::<String>("database").unwrap(),
+      output: m.remove_one::<PathBuf>("output"),
+    },
+    Some((name, mut m)) if name == "import" => KvSubcommand::Import {
+      database: m.remove_one::<String>("database").unwrap(),
+      input: m.remove_one::<PathBuf>("input"),
+    },
+    _ => unreachable!(),
+  };
+  flags.subcommand = DenoSubcommand::Kv(KvFlags { subcommand });
+}
+
 fn lsp_parse(flags: &mut Flags, _matches: &mut ArgMatches) {
   flags.subcommand = DenoSubcommand::Lsp;
 }
@@ -3201,6 +3621,8 @@ fn lint_parse(flags: &mut Flags, matches: &mut ArgMatches) {
 
   let json = matches.get_flag("json");
   let compact = matches.get_flag("compact");
+  let reporter = matches.remove_one::<String>("reporter");
+  let fix = matches.get_flag("fix");
   flags.subcommand = DenoSubcommand::Lint(LintFlags {
     files: FileFlags {
       include: files,
@@ -3212,6 +3634,8 @@ fn lint_parse(flags: &mut Flags, matches: &mut ArgMatches) {
     maybe_rules_exclude,
     json,
     compact,
+    reporter,
+    fix,
     watch: watch_arg_parse(matches),
   });
 }
@@ -3328,6 +3752,9 @@ fn test_parse(flags: &mut Flags, matches: &mut ArgMatches) {
     None
   };
 
+  let retry = matches.remove_one::<usize>("retry").unwrap_or(0);
+  let shard = matches.remove_one::<TestShard>("shard");
+
   if let Some(script_arg) = matches.remove_many::<String>("script_arg") {
     flags.argv.extend(script_arg);
   }
@@ -3371,13 +3798,19 @@ fn test_parse(flags: &mut Flags, matches: &mut ArgMatches) {
         "junit" => TestReporterConfig::Junit,
         "dot" => TestReporterConfig::Dot,
         "tap" => TestReporterConfig::Tap,
+        "json" => TestReporterConfig::Json,
         _ => unreachable!(),
       }
     } else {
       TestReporterConfig::Pretty
     };
 
-  if matches!(reporter, TestReporterConfig::Dot | TestReporterConfig::Tap) {
+  if matches!(
+    reporter,
+    TestReporterConfig::Dot
+      | TestReporterConfig::Tap
+      | TestReporterConfig::Json
+  ) {
     flags.log_level = Some(Level::Error);
   }
 
@@ -3392,6 +3825,8 @@ fn test_parse(flags: &mut Flags, matches: &mut ArgMatches) {
     allow_none,
     concurrent_jobs,
     trace_ops,
+    retry,
+    shard,
     watch: watch_arg_parse(matches),
     reporter,
     junit_path,
@@ -3548,6 +3983,8 @@ fn permission_args_parse(flags: &mut Flags, matches: &mut ArgMatches) {
   if matches.get_flag("no-prompt") {
     flags.no_prompt = true;
   }
+
+  flags.audit_permissions = matches.remove_one::<PathBuf>("audit-permissions");
 }
 fn unsafely_ignore_certificate_errors_parse(
   flags: &mut Flags,
@@ -3578,6 +4015,11 @@ fn runtime_args_parse(
   location_arg_parse(flags, matches);
   v8_flags_arg_parse(flags, matches);
   seed_arg_parse(flags, matches);
+  broadcast_channel_backend_arg_parse(flags, matches);
+  cache_storage_backend_arg_parse(flags, matches);
+  cache_quota_arg_parse(flags, matches);
+  cache_storage_quota_arg_parse(flags, matches);
+  local_storage_quota_arg_parse(flags, matches);
   enable_testing_features_arg_parse(flags, matches);
 }
 
@@ -3656,6 +4098,46 @@ fn location_arg_parse(flags: &mut Flags, matches: &mut ArgMatches) {
   flags.location = matches.remove_one::<Url>("location");
 }
 
+fn broadcast_channel_backend_arg_parse(
+  flags: &mut Flags,
+  matches: &mut ArgMatches,
+) {
+  if let Some(backend) =
+    matches.remove_one::<String>("broadcast-channel-backend")
+  {
+    flags.broadcast_channel_backend = match backend.as_str() {
+      "memory" => BroadcastChannelBackend::Memory,
+      "sqlite" => BroadcastChannelBackend::Sqlite,
+      _ => unreachable!(),
+    };
+  }
+}
+
+fn cache_storage_backend_arg_parse(
+  flags: &mut Flags,
+  matches: &mut ArgMatches,
+) {
+  if let Some(backend) = matches.remove_one::<String>("cache-storage-backend") {
+    flags.cache_storage_backend = match backend.as_str() {
+      "sqlite" => CacheStorageBackend::Sqlite,
+      "memory" => CacheStorageBackend::Memory,
+      _ => unreachable!(),
+    };
+  }
+}
+
+fn cache_quota_arg_parse(flags: &mut Flags, matches: &mut ArgMatches) {
+  flags.cache_quota = matches.remove_one::<u64>("cache-quota");
+}
+
+fn cache_storage_quota_arg_parse(flags: &mut Flags, matches: &mut ArgMatches) {
+  flags.cache_storage_quota = matches.remove_one::<u64>("cache-storage-quota");
+}
+
+fn local_storage_quota_arg_parse(flags: &mut Flags, matches: &mut ArgMatches) {
+  flags.local_storage_quota = matches.remove_one::<u64>("local-storage-quota");
+}
+
 fn v8_flags_arg_parse(flags: &mut Flags, matches: &mut ArgMatches) {
   if let Some(v8_flags) = matches.remove_many::<String>("v8-flags") {
     flags.v8_flags = v8_flags.collect();
@@ -3761,6 +4243,19 @@ fn reload_arg_validate(urlstr: &str) -> Result<String, String> {
   }
 }
 
+fn test_shard_parse(value: &str) -> Result<TestShard, String> {
+  let error = || {
+    format!("Invalid shard '{value}'. Expected INDEX/TOTAL, with 1 <= INDEX <= TOTAL.")
+  };
+  let (index, total) = value.split_once('/').ok_or_else(error)?;
+  let index = index.parse::<usize>().map_err(|_| error())?;
+  let total = total.parse::<usize>().map_err(|_| error())?;
+  if index == 0 || index > total {
+    return Err(error());
+  }
+  Ok(TestShard { index, total })
+}
+
 fn watch_arg_parse(matches: &mut ArgMatches) -> Option<WatchFlags> {
   if matches.get_flag("watch") {
     Some(WatchFlags {
@@ -4113,50 +4608,63 @@ mod tests {
   }
 
   #[test]
-  fn allow_hrtime() {
-    let r = flags_from_vec(svec!["deno", "run", "--allow-hrtime", "gist.ts"]);
+  fn cache_storage_backend() {
+    let r =
+      flags_from_vec(svec!["deno", "test", "--cache-storage-backend=memory",]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Run(RunFlags {
-          script: "gist.ts".to_string(),
-          watch: Default::default(),
-        }),
-        allow_hrtime: true,
+        subcommand: DenoSubcommand::Test(TestFlags::default()),
+        cache_storage_backend: CacheStorageBackend::Memory,
+        type_check_mode: TypeCheckMode::Local,
+        no_prompt: true,
         ..Flags::default()
       }
     );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--cache-storage-backend=redis",
+      "script.ts"
+    ]);
+    assert!(r.is_err());
   }
 
   #[test]
-  fn deny_hrtime() {
-    let r = flags_from_vec(svec!["deno", "run", "--deny-hrtime", "gist.ts"]);
+  fn cache_quota() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--cache-quota=50",
+      "--cache-storage-quota=200",
+      "script.ts"
+    ]);
     assert_eq!(
       r.unwrap(),
       Flags {
         subcommand: DenoSubcommand::Run(RunFlags {
-          watch: None,
-          script: "gist.ts".to_string(),
+          script: "script.ts".to_string(),
+          watch: Default::default(),
         }),
-        deny_hrtime: true,
+        cache_quota: Some(50),
+        cache_storage_quota: Some(200),
         ..Flags::default()
       }
     );
+
+    let r =
+      flags_from_vec(svec!["deno", "run", "--cache-quota=0", "script.ts"]);
+    assert!(r.is_err());
   }
 
   #[test]
-  fn double_hyphen() {
-    // notice that flags passed after double dash will not
-    // be parsed to Flags but instead forwarded to
-    // script args as Deno.args
+  fn local_storage_quota() {
     let r = flags_from_vec(svec![
       "deno",
       "run",
-      "--allow-write",
-      "script.ts",
-      "--",
-      "-D",
-      "--allow-net"
+      "--local-storage-quota=50",
+      "script.ts"
     ]);
     assert_eq!(
       r.unwrap(),
@@ -4165,50 +4673,144 @@ mod tests {
           script: "script.ts".to_string(),
           watch: Default::default(),
         }),
-        argv: svec!["--", "-D", "--allow-net"],
-        allow_write: Some(vec![]),
+        local_storage_quota: Some(50),
         ..Flags::default()
       }
     );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--local-storage-quota=0",
+      "script.ts"
+    ]);
+    assert!(r.is_err());
   }
 
   #[test]
-  fn fmt() {
-    let r = flags_from_vec(svec!["deno", "fmt", "script_1.ts", "script_2.ts"]);
+  fn broadcast_channel_backend() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--broadcast-channel-backend=sqlite",
+      "script.ts"
+    ]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Fmt(FmtFlags {
-          check: false,
-          files: FileFlags {
-            include: vec![
-              PathBuf::from("script_1.ts"),
-              PathBuf::from("script_2.ts")
-            ],
-            ignore: vec![],
-          },
-          use_tabs: None,
-          line_width: None,
-          indent_width: None,
-          single_quote: None,
-          prose_wrap: None,
-          no_semicolons: None,
+        subcommand: DenoSubcommand::Run(RunFlags {
+          script: "script.ts".to_string(),
           watch: Default::default(),
         }),
-        ext: Some("ts".to_string()),
+        broadcast_channel_backend: BroadcastChannelBackend::Sqlite,
         ..Flags::default()
       }
     );
 
-    let r = flags_from_vec(svec!["deno", "fmt", "--check"]);
-    assert_eq!(
-      r.unwrap(),
-      Flags {
-        subcommand: DenoSubcommand::Fmt(FmtFlags {
-          check: true,
-          files: FileFlags {
-            include: vec![],
-            ignore: vec![],
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--broadcast-channel-backend=redis",
+      "script.ts"
+    ]);
+    assert!(r.is_err());
+  }
+
+  #[test]
+  fn audit_permissions() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--audit-permissions=audit.jsonl",
+      "gist.ts"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Run(RunFlags {
+          script: "gist.ts".to_string(),
+          watch: Default::default(),
+        }),
+        audit_permissions: Some(PathBuf::from("audit.jsonl")),
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn allow_hrtime() {
+    let r = flags_from_vec(svec!["deno", "run", "--allow-hrtime", "gist.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Run(RunFlags {
+          script: "gist.ts".to_string(),
+          watch: Default::default(),
+        }),
+        allow_hrtime: true,
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn deny_hrtime() {
+    let r = flags_from_vec(svec!["deno", "run", "--deny-hrtime", "gist.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Run(RunFlags {
+          watch: None,
+          script: "gist.ts".to_string(),
+        }),
+        deny_hrtime: true,
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn double_hyphen() {
+    // notice that flags passed after double dash will not
+    // be parsed to Flags but instead forwarded to
+    // script args as Deno.args
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--allow-write",
+      "script.ts",
+      "--",
+      "-D",
+      "--allow-net"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Run(RunFlags {
+          script: "script.ts".to_string(),
+          watch: Default::default(),
+        }),
+        argv: svec!["--", "-D", "--allow-net"],
+        allow_write: Some(vec![]),
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn fmt() {
+    let r = flags_from_vec(svec!["deno", "fmt", "script_1.ts", "script_2.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Fmt(FmtFlags {
+          check: false,
+          files: FileFlags {
+            include: vec![
+              PathBuf::from("script_1.ts"),
+              PathBuf::from("script_2.ts")
+            ],
+            ignore: vec![],
           },
           use_tabs: None,
           line_width: None,
@@ -4217,6 +4819,35 @@ mod tests {
           prose_wrap: None,
           no_semicolons: None,
           watch: Default::default(),
+          unstable_css: false,
+          unstable_html: false,
+          unstable_yaml: false,
+        }),
+        ext: Some("ts".to_string()),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "fmt", "--check"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Fmt(FmtFlags {
+          check: true,
+          files: FileFlags {
+            include: vec![],
+            ignore: vec![],
+          },
+          use_tabs: None,
+          line_width: None,
+          indent_width: None,
+          single_quote: None,
+          prose_wrap: None,
+          no_semicolons: None,
+          watch: Default::default(),
+          unstable_css: false,
+          unstable_html: false,
+          unstable_yaml: false,
         }),
         ext: Some("ts".to_string()),
         ..Flags::default()
@@ -4240,6 +4871,9 @@ mod tests {
           prose_wrap: None,
           no_semicolons: None,
           watch: Default::default(),
+          unstable_css: false,
+          unstable_html: false,
+          unstable_yaml: false,
         }),
         ext: Some("ts".to_string()),
         ..Flags::default()
@@ -4265,6 +4899,9 @@ mod tests {
           watch: Some(WatchFlags {
             no_clear_screen: false,
           })
+          unstable_css: false,
+          unstable_html: false,
+          unstable_yaml: false,
         }),
         ext: Some("ts".to_string()),
         ..Flags::default()
@@ -4291,6 +4928,9 @@ mod tests {
           watch: Some(WatchFlags {
             no_clear_screen: true,
           })
+          unstable_css: false,
+          unstable_html: false,
+          unstable_yaml: false,
         }),
         ext: Some("ts".to_string()),
         ..Flags::default()
@@ -4323,6 +4963,9 @@ mod tests {
           watch: Some(WatchFlags {
             no_clear_screen: false,
           })
+          unstable_css: false,
+          unstable_html: false,
+          unstable_yaml: false,
         }),
         ext: Some("ts".to_string()),
         ..Flags::default()
@@ -4346,6 +4989,9 @@ mod tests {
           prose_wrap: None,
           no_semicolons: None,
           watch: Default::default(),
+          unstable_css: false,
+          unstable_html: false,
+          unstable_yaml: false,
         }),
         ext: Some("ts".to_string()),
         config_flag: ConfigFlag::Path("deno.jsonc".to_string()),
@@ -4379,6 +5025,9 @@ mod tests {
           watch: Some(WatchFlags {
             no_clear_screen: false,
           })
+          unstable_css: false,
+          unstable_html: false,
+          unstable_yaml: false,
         }),
         config_flag: ConfigFlag::Path("deno.jsonc".to_string()),
         ext: Some("ts".to_string()),
@@ -4415,6 +5064,9 @@ mod tests {
           prose_wrap: Some("never".to_string()),
           no_semicolons: Some(true),
           watch: Default::default(),
+          unstable_css: false,
+          unstable_html: false,
+          unstable_yaml: false,
         }),
         ext: Some("ts".to_string()),
         ..Flags::default()
@@ -4445,6 +5097,41 @@ mod tests {
           prose_wrap: None,
           no_semicolons: Some(false),
           watch: Default::default(),
+          unstable_css: false,
+          unstable_html: false,
+          unstable_yaml: false,
+        }),
+        ext: Some("ts".to_string()),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "fmt",
+      "--unstable-css",
+      "--unstable-html",
+      "--unstable-yaml",
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Fmt(FmtFlags {
+          check: false,
+          files: FileFlags {
+            include: vec![],
+            ignore: vec![],
+          },
+          use_tabs: None,
+          line_width: None,
+          indent_width: None,
+          single_quote: None,
+          prose_wrap: None,
+          no_semicolons: None,
+          watch: Default::default(),
+          unstable_css: true,
+          unstable_html: true,
+          unstable_yaml: true,
         }),
         ext: Some("ts".to_string()),
         ..Flags::default()
@@ -4472,6 +5159,8 @@ mod tests {
           maybe_rules_exclude: None,
           json: false,
           compact: false,
+          reporter: None,
+          fix: false,
           watch: Default::default(),
         }),
         ..Flags::default()
@@ -4502,6 +5191,8 @@ mod tests {
           maybe_rules_exclude: None,
           json: false,
           compact: false,
+          reporter: None,
+          fix: false,
           watch: Some(WatchFlags {
             no_clear_screen: false,
           })
@@ -4535,6 +5226,8 @@ mod tests {
           maybe_rules_exclude: None,
           json: false,
           compact: false,
+          reporter: None,
+          fix: false,
           watch: Some(WatchFlags {
             no_clear_screen: true,
           })
@@ -4562,6 +5255,8 @@ mod tests {
           maybe_rules_exclude: None,
           json: false,
           compact: false,
+          reporter: None,
+          fix: false,
           watch: Default::default(),
         }),
         ..Flags::default()
@@ -4583,6 +5278,8 @@ mod tests {
           maybe_rules_exclude: None,
           json: false,
           compact: false,
+          reporter: None,
+          fix: false,
           watch: Default::default(),
         }),
         ..Flags::default()
@@ -4609,6 +5306,8 @@ mod tests {
           maybe_rules_exclude: None,
           json: false,
           compact: false,
+          reporter: None,
+          fix: false,
           watch: Default::default(),
         }),
         ..Flags::default()
@@ -4636,6 +5335,8 @@ mod tests {
           maybe_rules_exclude: Some(svec!["no-const-assign"]),
           json: false,
           compact: false,
+          reporter: None,
+          fix: false,
           watch: Default::default(),
         }),
         ..Flags::default()
@@ -4657,6 +5358,8 @@ mod tests {
           maybe_rules_exclude: None,
           json: true,
           compact: false,
+          reporter: None,
+          fix: false,
           watch: Default::default(),
         }),
         ..Flags::default()
@@ -4685,6 +5388,8 @@ mod tests {
           maybe_rules_exclude: None,
           json: true,
           compact: false,
+          reporter: None,
+          fix: false,
           watch: Default::default(),
         }),
         config_flag: ConfigFlag::Path("Deno.jsonc".to_string()),
@@ -4714,12 +5419,73 @@ mod tests {
           maybe_rules_exclude: None,
           json: false,
           compact: true,
+          reporter: None,
+          fix: false,
           watch: Default::default(),
         }),
         config_flag: ConfigFlag::Path("Deno.jsonc".to_string()),
         ..Flags::default()
       }
     );
+
+    let r = flags_from_vec(svec!["deno", "lint", "--fix", "script_1.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Lint(LintFlags {
+          files: FileFlags {
+            include: vec![PathBuf::from("script_1.ts")],
+            ignore: vec![],
+          },
+          rules: false,
+          maybe_rules_tags: None,
+          maybe_rules_include: None,
+          maybe_rules_exclude: None,
+          json: false,
+          compact: false,
+          reporter: None,
+          fix: true,
+          watch: Default::default(),
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r =
+      flags_from_vec(svec!["deno", "lint", "--reporter=sarif", "script_1.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Lint(LintFlags {
+          files: FileFlags {
+            include: vec![PathBuf::from("script_1.ts")],
+            ignore: vec![],
+          },
+          rules: false,
+          maybe_rules_tags: None,
+          maybe_rules_include: None,
+          maybe_rules_exclude: None,
+          json: false,
+          compact: false,
+          reporter: Some("sarif".to_string()),
+          fix: false,
+          watch: Default::default(),
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "lint",
+      "--reporter=sarif",
+      "--json",
+      "script_1.ts"
+    ]);
+    assert_eq!(
+      r.unwrap_err().kind(),
+      clap::error::ErrorKind::ArgumentConflict
+    );
   }
 
   #[test]
@@ -4756,6 +5522,7 @@ mod tests {
       Flags {
         subcommand: DenoSubcommand::Check(CheckFlags {
           files: svec!["script.ts"],
+          reporter: CheckReporterConfig::Pretty,
         }),
         type_check_mode: TypeCheckMode::Local,
         ..Flags::default()
@@ -4769,6 +5536,7 @@ mod tests {
         Flags {
           subcommand: DenoSubcommand::Check(CheckFlags {
             files: svec!["script.ts"],
+            reporter: CheckReporterConfig::Pretty,
           }),
           type_check_mode: TypeCheckMode::All,
           ..Flags::default()
@@ -4787,6 +5555,20 @@ mod tests {
         clap::error::ErrorKind::ArgumentConflict
       );
     }
+
+    let r =
+      flags_from_vec(svec!["deno", "check", "--reporter=github", "script.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Check(CheckFlags {
+          files: svec!["script.ts"],
+          reporter: CheckReporterConfig::Github,
+        }),
+        type_check_mode: TypeCheckMode::Local,
+        ..Flags::default()
+      }
+    );
   }
 
   #[test]
@@ -5991,6 +6773,67 @@ mod tests {
     assert_eq!(r.err().unwrap().kind(), clap::error::ErrorKind::DisplayHelp);
   }
 
+  #[test]
+  fn kv() {
+    let r = flags_from_vec(svec!["deno", "kv", "export", "db.sqlite3"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Kv(KvFlags {
+          subcommand: KvSubcommand::Export {
+            database: "db.sqlite3".to_string(),
+            output: None,
+          },
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "kv",
+      "export",
+      "db.sqlite3",
+      "--output",
+      "backup.ndjson"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Kv(KvFlags {
+          subcommand: KvSubcommand::Export {
+            database: "db.sqlite3".to_string(),
+            output: Some(PathBuf::from("backup.ndjson")),
+          },
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "kv",
+      "import",
+      "http://localhost:4545/kv",
+      "backup.ndjson"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Kv(KvFlags {
+          subcommand: KvSubcommand::Import {
+            database: "http://localhost:4545/kv".to_string(),
+            input: Some(PathBuf::from("backup.ndjson")),
+          },
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "kv"]);
+    assert!(r.is_err());
+  }
+
   #[test]
   fn log_level() {
     let r =
@@ -6631,6 +7474,8 @@ mod tests {
           shuffle: None,
           concurrent_jobs: None,
           trace_ops: true,
+          retry: 0,
+          shard: None,
           coverage_dir: Some("cov".to_string()),
           watch: Default::default(),
           reporter: Default::default(),
@@ -6712,6 +7557,8 @@ mod tests {
           },
           concurrent_jobs: Some(NonZeroUsize::new(4).unwrap()),
           trace_ops: false,
+          retry: 0,
+          shard: None,
           coverage_dir: None,
           watch: Default::default(),
           junit_path: None,
@@ -6745,6 +7592,8 @@ mod tests {
           },
           concurrent_jobs: None,
           trace_ops: false,
+          retry: 0,
+          shard: None,
           coverage_dir: None,
           watch: Default::default(),
           reporter: Default::default(),
@@ -6783,6 +7632,8 @@ mod tests {
           },
           concurrent_jobs: None,
           trace_ops: false,
+          retry: 0,
+          shard: None,
           coverage_dir: None,
           watch: Default::default(),
           reporter: Default::default(),
@@ -6856,6 +7707,21 @@ mod tests {
       }
     );
 
+    let r = flags_from_vec(svec!["deno", "test", "--reporter=json"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Test(TestFlags {
+          reporter: TestReporterConfig::Json,
+          ..Default::default()
+        }),
+        no_prompt: true,
+        type_check_mode: TypeCheckMode::Local,
+        log_level: Some(Level::Error),
+        ..Flags::default()
+      }
+    );
+
     let r = flags_from_vec(svec![
       "deno",
       "test",
@@ -6900,6 +7766,8 @@ mod tests {
           },
           concurrent_jobs: None,
           trace_ops: false,
+          retry: 0,
+          shard: None,
           coverage_dir: None,
           watch: Default::default(),
           reporter: Default::default(),
@@ -6912,6 +7780,55 @@ mod tests {
     );
   }
 
+  #[test]
+  fn test_retry() {
+    let r = flags_from_vec(svec!["deno", "test", "--retry=2"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Test(TestFlags {
+          retry: 2,
+          ..Default::default()
+        }),
+        no_prompt: true,
+        type_check_mode: TypeCheckMode::Local,
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "test", "--retry=-1"]);
+    assert!(r.is_err());
+  }
+
+  #[test]
+  fn test_shard() {
+    let r = flags_from_vec(svec!["deno", "test", "--shard=2/3"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Test(TestFlags {
+          shard: Some(TestShard { index: 2, total: 3 }),
+          ..Default::default()
+        }),
+        no_prompt: true,
+        type_check_mode: TypeCheckMode::Local,
+        ..Flags::default()
+      }
+    );
+
+    for invalid in ["0/3", "4/3", "1", "a/b", "1/0"] {
+      let r =
+        flags_from_vec(svec!["deno", "test", format!("--shard={invalid}")]);
+      assert!(r.is_err(), "{invalid}");
+    }
+    let r = flags_from_vec(svec!["deno", "test", "--shard=1/2", "--watch"]);
+    assert!(r.is_err());
+
+    let shard = TestShard { index: 2, total: 3 };
+    let positions = (0..7).filter(|p| shard.includes(*p)).collect::<Vec<_>>();
+    assert_eq!(positions, vec![1, 4]);
+  }
+
   #[test]
   fn test_watch() {
     let r = flags_from_vec(svec!["deno", "test", "--watch"]);
@@ -6931,6 +7848,8 @@ mod tests {
           },
           concurrent_jobs: None,
           trace_ops: false,
+          retry: 0,
+          shard: None,
           coverage_dir: None,
           watch: Some(WatchFlags {
             no_clear_screen: false,
@@ -6963,6 +7882,8 @@ mod tests {
           },
           concurrent_jobs: None,
           trace_ops: false,
+          retry: 0,
+          shard: None,
           coverage_dir: None,
           watch: Some(WatchFlags {
             no_clear_screen: false,
@@ -6997,6 +7918,8 @@ mod tests {
           },
           concurrent_jobs: None,
           trace_ops: false,
+          retry: 0,
+          shard: None,
           coverage_dir: None,
           watch: Some(WatchFlags {
             no_clear_screen: true,
@@ -7308,7 +8231,7 @@ mod tests {
           output: None,
           include: vec![r"^file:".to_string()],
           exclude: vec![r"test\.(js|mjs|ts|jsx|tsx)$".to_string()],
-          lcov: false,
+          r#type: CoverageType::Pretty,
         }),
         ..Flags::default()
       }
@@ -7334,13 +8257,82 @@ mod tests {
           },
           include: vec![r"^file:".to_string()],
           exclude: vec![r"test\.(js|mjs|ts|jsx|tsx)$".to_string()],
-          lcov: true,
+          r#type: CoverageType::Lcov,
           output: Some(PathBuf::from("foo.lcov")),
         }),
         ..Flags::default()
       }
     );
   }
+
+  #[test]
+  fn coverage_with_html_and_out_dir() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "coverage",
+      "--html",
+      "--output=html_cov",
+      "foo.json"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Coverage(CoverageFlags {
+          files: FileFlags {
+            include: vec![PathBuf::from("foo.json")],
+            ignore: vec![],
+          },
+          include: vec![r"^file:".to_string()],
+          exclude: vec![r"test\.(js|mjs|ts|jsx|tsx)$".to_string()],
+          r#type: CoverageType::Html,
+          output: Some(PathBuf::from("html_cov")),
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r =
+      flags_from_vec(svec!["deno", "coverage", "--html", "--lcov", "foo.json"]);
+    assert!(r.is_err());
+
+    let r = flags_from_vec(svec!["deno", "coverage", "--output=x", "foo.json"]);
+    assert!(r.is_err());
+  }
+
+  #[test]
+  fn coverage_with_cobertura_and_json() {
+    for (flag, r#type) in [
+      ("--cobertura", CoverageType::Cobertura),
+      ("--json", CoverageType::JsonSummary),
+    ] {
+      let r = flags_from_vec(svec!["deno", "coverage", flag, "foo.json"]);
+      assert_eq!(
+        r.unwrap(),
+        Flags {
+          subcommand: DenoSubcommand::Coverage(CoverageFlags {
+            files: FileFlags {
+              include: vec![PathBuf::from("foo.json")],
+              ignore: vec![],
+            },
+            include: vec![r"^file:".to_string()],
+            exclude: vec![r"test\.(js|mjs|ts|jsx|tsx)$".to_string()],
+            r#type,
+            output: None,
+          }),
+          ..Flags::default()
+        }
+      );
+    }
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "coverage",
+      "--cobertura",
+      "--json",
+      "foo.json"
+    ]);
+    assert!(r.is_err());
+  }
   #[test]
   fn location_with_bad_scheme() {
     #[rustfmt::skip]
@@ -7674,11 +8666,15 @@ mod tests {
         subcommand: DenoSubcommand::Bench(BenchFlags {
           filter: Some("- foo".to_string()),
           json: true,
+          memory: false,
           no_run: true,
           files: FileFlags {
             include: vec![PathBuf::from("dir1/"), PathBuf::from("dir2/")],
             ignore: vec![],
           },
+          save_baseline: None,
+          compare_baseline: None,
+          max_regression: None,
           watch: Default::default(),
         }),
         unstable: true,
@@ -7703,11 +8699,15 @@ mod tests {
         subcommand: DenoSubcommand::Bench(BenchFlags {
           filter: None,
           json: false,
+          memory: false,
           no_run: false,
           files: FileFlags {
             include: vec![],
             ignore: vec![],
           },
+          save_baseline: None,
+          compare_baseline: None,
+          max_regression: None,
           watch: Some(WatchFlags {
             no_clear_screen: false,
           }),
@@ -7719,6 +8719,51 @@ mod tests {
     );
   }
 
+  #[test]
+  fn bench_baseline() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "bench",
+      "--save-baseline=new.json",
+      "--compare-baseline=old.json",
+      "--max-regression=10",
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Bench(BenchFlags {
+          save_baseline: Some(PathBuf::from("new.json")),
+          compare_baseline: Some(PathBuf::from("old.json")),
+          max_regression: Some(10),
+          ..Default::default()
+        }),
+        no_prompt: true,
+        type_check_mode: TypeCheckMode::Local,
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "bench", "--max-regression=10"]);
+    assert!(r.is_err());
+  }
+
+  #[test]
+  fn bench_memory() {
+    let r = flags_from_vec(svec!["deno", "bench", "--memory"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Bench(BenchFlags {
+          memory: true,
+          ..Default::default()
+        }),
+        no_prompt: true,
+        type_check_mode: TypeCheckMode::Local,
+        ..Flags::default()
+      }
+    );
+  }
+
   #[test]
   fn run_with_check() {
     let r = flags_from_vec(svec!["deno", "run", "--check", "script.ts",]);
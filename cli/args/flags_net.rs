@@ -1,6 +1,7 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
 
 use deno_core::url::Url;
+use deno_runtime::permissions::NetDescriptor;
 use std::net::IpAddr;
 use std::str::FromStr;
 
@@ -26,10 +27,44 @@ impl FromStr for BarePort {
   }
 }
 
-pub fn validator(host_and_port: &str) -> Result<String, String> {
-  if Url::parse(&format!("internal://{host_and_port}")).is_ok()
+/// A "bare port" range like ":8000-8100".
+#[derive(Debug, PartialEq, Eq)]
+pub struct BarePortRange(u16, u16);
+
+impl FromStr for BarePortRange {
+  type Err = ParsePortError;
+  fn from_str(s: &str) -> Result<BarePortRange, ParsePortError> {
+    let range = s.strip_prefix(':').ok_or_else(|| {
+      ParsePortError("Bare Port doesn't start with ':'".to_string())
+    })?;
+    let (start, end) = range
+      .split_once('-')
+      .ok_or_else(|| ParsePortError("Port range is missing '-'".to_string()))?;
+    let start = start
+      .parse::<u16>()
+      .map_err(|e| ParsePortError(e.to_string()))?;
+    let end = end
+      .parse::<u16>()
+      .map_err(|e| ParsePortError(e.to_string()))?;
+    if start > end {
+      return Err(ParsePortError(format!("Invalid port range: {range}")));
+    }
+    Ok(BarePortRange(start, end))
+  }
+}
+
+/// Whether the value is a host (or host pattern) with an optional port (or
+/// port range) that can be passed through as is.
+fn is_host_and_port(host_and_port: &str) -> bool {
+  Url::parse(&format!("internal://{host_and_port}")).is_ok()
     || host_and_port.parse::<IpAddr>().is_ok()
+    || host_and_port.parse::<NetDescriptor>().is_ok()
+}
+
+pub fn validator(host_and_port: &str) -> Result<String, String> {
+  if is_host_and_port(host_and_port)
     || host_and_port.parse::<BarePort>().is_ok()
+    || host_and_port.parse::<BarePortRange>().is_ok()
   {
     Ok(host_and_port.to_string())
   } else {
@@ -37,21 +72,23 @@ pub fn validator(host_and_port: &str) -> Result<String, String> {
   }
 }
 
-/// Expands "bare port" paths (eg. ":8080") into full paths with hosts. It
-/// expands to such paths into 3 paths with following hosts: `0.0.0.0:port`,
-/// `127.0.0.1:port` and `localhost:port`.
+/// Expands "bare port" paths (eg. ":8080" or ":8000-8100") into full paths
+/// with hosts. It expands to such paths into 3 paths with following hosts:
+/// `0.0.0.0:port`, `127.0.0.1:port` and `localhost:port`.
 pub fn parse(paths: Vec<String>) -> clap::error::Result<Vec<String>> {
   let mut out: Vec<String> = vec![];
   for host_and_port in paths.iter() {
-    if Url::parse(&format!("internal://{host_and_port}")).is_ok()
-      || host_and_port.parse::<IpAddr>().is_ok()
-    {
+    if is_host_and_port(host_and_port) {
       out.push(host_and_port.to_owned())
     } else if let Ok(port) = host_and_port.parse::<BarePort>() {
       // we got bare port, let's add default hosts
       for host in ["0.0.0.0", "127.0.0.1", "localhost"].iter() {
         out.push(format!("{}:{}", host, port.0));
       }
+    } else if let Ok(range) = host_and_port.parse::<BarePortRange>() {
+      for host in ["0.0.0.0", "127.0.0.1", "localhost"].iter() {
+        out.push(format!("{}:{}-{}", host, range.0, range.1));
+      }
     } else {
       return Err(clap::Error::raw(
         clap::error::ErrorKind::InvalidValue,
@@ -172,6 +209,37 @@ mod tests {
     assert_eq!(actual, expected);
   }
 
+  #[test]
+  fn parse_net_args_patterns() {
+    let entries = svec![
+      "10.0.0.0/8",
+      "*.internal.example.com",
+      "*.example.com:443",
+      "deno.land:8000-8100",
+      "[fd00::/8]:443"
+    ];
+    let actual = parse(entries.clone()).unwrap();
+    assert_eq!(actual, entries);
+  }
+
+  #[test]
+  fn parse_net_args_port_range_expansion() {
+    let entries = svec![":8000-8100"];
+    let expected = svec![
+      "0.0.0.0:8000-8100",
+      "127.0.0.1:8000-8100",
+      "localhost:8000-8100"
+    ];
+    let actual = parse(entries).unwrap();
+    assert_eq!(actual, expected);
+  }
+
+  #[test]
+  fn parse_net_args_port_range_error() {
+    assert!(parse(svec![":8100-8000"]).is_err());
+    assert!(parse(svec!["deno.land:8100-8000"]).is_err());
+  }
+
   #[test]
   fn parse_net_args_ipv6() {
     let entries =
@@ -45,6 +45,7 @@ use deno_core::parking_lot::Mutex;
 use deno_core::serde_json;
 use deno_core::url::Url;
 use deno_runtime::colors;
+use deno_runtime::deno_cache::CacheLimits;
 use deno_runtime::deno_node::PackageJson;
 use deno_runtime::deno_tls::rustls;
 use deno_runtime::deno_tls::rustls::RootCertStore;
@@ -55,6 +56,8 @@ use deno_runtime::inspector_server::InspectorServer;
 use deno_runtime::permissions::PermissionsOptions;
 use once_cell::sync::Lazy;
 use once_cell::sync::OnceCell;
+use serde::Deserialize;
+use serde::Serialize;
 use std::collections::HashMap;
 use std::env;
 use std::io::BufReader;
@@ -71,6 +74,8 @@ use crate::npm::CliNpmRegistryApi;
 use crate::npm::NpmProcessState;
 use crate::util::fs::canonicalize_path_maybe_not_exists;
 use crate::util::glob::expand_globs;
+use crate::util::glob::GlobPattern;
+use crate::util::path::specifier_to_file_path;
 use crate::version;
 
 use deno_config::FmtConfig;
@@ -157,7 +162,11 @@ pub struct BenchOptions {
   pub files: FilesConfig,
   pub filter: Option<String>,
   pub json: bool,
+  pub memory: bool,
   pub no_run: bool,
+  pub save_baseline: Option<PathBuf>,
+  pub compare_baseline: Option<PathBuf>,
+  pub max_regression: Option<u32>,
 }
 
 impl BenchOptions {
@@ -173,22 +182,225 @@ impl BenchOptions {
       )?,
       filter: bench_flags.filter,
       json: bench_flags.json,
+      memory: bench_flags.memory,
       no_run: bench_flags.no_run,
+      save_baseline: bench_flags.save_baseline,
+      compare_baseline: bench_flags.compare_baseline,
+      max_regression: bench_flags.max_regression,
     })
   }
 }
 
+/// Minimum coverage percentages. Metrics that are not set are not enforced.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct CoverageThresholds {
+  pub lines: Option<f64>,
+  pub branches: Option<f64>,
+  pub functions: Option<f64>,
+}
+
+impl CoverageThresholds {
+  pub fn is_empty(&self) -> bool {
+    self.lines.is_none() && self.branches.is_none() && self.functions.is_none()
+  }
+
+  /// Returns these thresholds, with the metrics that are set in `other`
+  /// replaced.
+  fn merge(self, other: &CoverageThresholds) -> Self {
+    Self {
+      lines: other.lines.or(self.lines),
+      branches: other.branches.or(self.branches),
+      functions: other.functions.or(self.functions),
+    }
+  }
+
+  fn validate(&self) -> Result<(), AnyError> {
+    for value in [self.lines, self.branches, self.functions]
+      .into_iter()
+      .flatten()
+    {
+      if !(0.0..=100.0).contains(&value) {
+        bail!("Coverage thresholds must be between 0 and 100, got {value}");
+      }
+    }
+    Ok(())
+  }
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct SerializedCoverageConfig {
+  thresholds: CoverageThresholds,
+  overrides: Vec<SerializedCoverageOverride>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SerializedCoverageOverride {
+  include: Vec<String>,
+  thresholds: CoverageThresholds,
+}
+
+enum CoveragePathMatcher {
+  Glob(GlobPattern),
+  Path(PathBuf),
+}
+
+impl CoveragePathMatcher {
+  fn matches(&self, path: &Path) -> bool {
+    match self {
+      CoveragePathMatcher::Glob(pattern) => pattern.matches_path(path),
+      CoveragePathMatcher::Path(base) => path.starts_with(base),
+    }
+  }
+}
+
+/// A task in the `"tasks"` of the config file. It is either a command, or an
+/// object with the command and the tasks that have to run before it.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct TaskDefinition {
+  pub command: Option<String>,
+  pub description: Option<String>,
+  pub dependencies: Vec<String>,
+  /// Files, directories or globs the task reads, relative to the config file.
+  /// When they are given, the task is skipped if they haven't changed since
+  /// its last successful run.
+  pub inputs: Vec<String>,
+  /// Files or directories the task writes, relative to the config file. The
+  /// task is not skipped when one of them is missing.
+  pub outputs: Vec<String>,
+}
+
+impl TaskDefinition {
+  pub fn from_command(command: String) -> Self {
+    Self {
+      command: Some(command),
+      ..Default::default()
+    }
+  }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum SerializedTaskDefinition {
+  Command(String),
+  Definition(SerializedTaskObject),
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SerializedTaskObject {
+  command: Option<String>,
+  description: Option<String>,
+  #[serde(default)]
+  dependencies: Vec<String>,
+  #[serde(default)]
+  inputs: Vec<String>,
+  #[serde(default)]
+  outputs: Vec<String>,
+}
+
+impl From<SerializedTaskDefinition> for TaskDefinition {
+  fn from(task: SerializedTaskDefinition) -> Self {
+    match task {
+      SerializedTaskDefinition::Command(command) => Self::from_command(command),
+      SerializedTaskDefinition::Definition(task) => Self {
+        command: task.command,
+        description: task.description,
+        dependencies: task.dependencies,
+        inputs: task.inputs,
+        outputs: task.outputs,
+      },
+    }
+  }
+}
+
+/// The `"coverage"` settings of the config file.
+#[derive(Default)]
+pub struct CoverageOptions {
+  pub thresholds: CoverageThresholds,
+  overrides: Vec<(Vec<CoveragePathMatcher>, CoverageThresholds)>,
+}
+
+impl CoverageOptions {
+  fn resolve(
+    config: SerializedCoverageConfig,
+    base_dir: &Path,
+  ) -> Result<Self, AnyError> {
+    config.thresholds.validate()?;
+    let mut overrides = Vec::with_capacity(config.overrides.len());
+    for config_override in config.overrides {
+      config_override.thresholds.validate()?;
+      let mut matchers = Vec::with_capacity(config_override.include.len());
+      for include in config_override.include {
+        let path = base_dir.join(&include);
+        let path_str = path.to_string_lossy();
+        matchers.push(match GlobPattern::new_if_pattern(&path_str)? {
+          Some(pattern) => CoveragePathMatcher::Glob(pattern),
+          None => CoveragePathMatcher::Path(normalize_path(&path)),
+        });
+      }
+      overrides.push((matchers, config_override.thresholds));
+    }
+    Ok(Self {
+      thresholds: config.thresholds,
+      overrides,
+    })
+  }
+
+  pub fn has_thresholds(&self) -> bool {
+    !self.thresholds.is_empty()
+      || self.overrides.iter().any(|(_, t)| !t.is_empty())
+  }
+
+  /// Returns the thresholds for the file at `path` if an override matches
+  /// it. Matching overrides take precedence over the global thresholds, and
+  /// later overrides take precedence over earlier ones.
+  ///
+  /// Files that no override matches are not checked one by one: the global
+  /// thresholds apply to their aggregated coverage instead.
+  pub fn override_thresholds_for(
+    &self,
+    path: &Path,
+  ) -> Option<CoverageThresholds> {
+    let mut thresholds = None;
+    for (matchers, override_thresholds) in &self.overrides {
+      if matchers.iter().any(|m| m.matches(path)) {
+        thresholds = Some(
+          thresholds
+            .unwrap_or(self.thresholds)
+            .merge(override_thresholds),
+        );
+      }
+    }
+    thresholds
+  }
+}
+
 #[derive(Clone, Debug, Default)]
 pub struct FmtOptions {
   pub check: bool,
   pub options: FmtOptionsConfig,
+  pub unstable: UnstableFmtOptions,
   pub files: FilesConfig,
 }
 
+/// The languages whose formatting is unstable. `deno fmt` only formats files
+/// in these languages, and code blocks in them in Markdown files, when they
+/// are enabled with a flag or in the `unstable` option of the config file.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize)]
+pub struct UnstableFmtOptions {
+  pub css: bool,
+  pub html: bool,
+  pub yaml: bool,
+}
+
 impl FmtOptions {
   pub fn resolve(
     maybe_fmt_config: Option<FmtConfig>,
     maybe_fmt_flags: Option<FmtFlags>,
+    unstable_config: UnstableFmtOptions,
   ) -> Result<Self, AnyError> {
     let (maybe_config_options, maybe_config_files) =
       maybe_fmt_config.map(|c| (c.options, c.files)).unzip();
@@ -199,6 +411,14 @@ impl FmtOptions {
         maybe_fmt_flags.as_ref(),
         maybe_config_options,
       ),
+      unstable: UnstableFmtOptions {
+        css: unstable_config.css
+          || maybe_fmt_flags.as_ref().is_some_and(|f| f.unstable_css),
+        html: unstable_config.html
+          || maybe_fmt_flags.as_ref().is_some_and(|f| f.unstable_html),
+        yaml: unstable_config.yaml
+          || maybe_fmt_flags.as_ref().is_some_and(|f| f.unstable_yaml),
+      },
       files: resolve_files(
         maybe_config_files,
         maybe_fmt_flags.map(|f| f.files),
@@ -259,6 +479,8 @@ pub struct TestOptions {
   pub shuffle: Option<u64>,
   pub concurrent_jobs: NonZeroUsize,
   pub trace_ops: bool,
+  pub retry: usize,
+  pub shard: Option<TestShard>,
   pub reporter: TestReporterConfig,
   pub junit_path: Option<String>,
 }
@@ -285,6 +507,8 @@ impl TestOptions {
       no_run: test_flags.no_run,
       shuffle: test_flags.shuffle,
       trace_ops: test_flags.trace_ops,
+      retry: test_flags.retry,
+      shard: test_flags.shard,
       reporter: test_flags.reporter,
       junit_path: test_flags.junit_path,
     })
@@ -297,6 +521,21 @@ pub enum LintReporterKind {
   Pretty,
   Json,
   Compact,
+  Sarif,
+  Github,
+}
+
+impl LintReporterKind {
+  fn from_name(name: &str) -> Option<Self> {
+    match name {
+      "pretty" => Some(Self::Pretty),
+      "json" => Some(Self::Json),
+      "compact" => Some(Self::Compact),
+      "sarif" => Some(Self::Sarif),
+      "github" => Some(Self::Github),
+      _ => None,
+    }
+  }
 }
 
 #[derive(Clone, Debug, Default)]
@@ -304,6 +543,10 @@ pub struct LintOptions {
   pub rules: LintRulesConfig,
   pub files: FilesConfig,
   pub reporter_kind: LintReporterKind,
+  pub fix: bool,
+  /// Plugin modules with additional lint rules, from the `lintPlugins` option
+  /// of the config file.
+  pub plugins: Vec<ModuleSpecifier>,
 }
 
 impl LintOptions {
@@ -311,32 +554,30 @@ impl LintOptions {
     maybe_lint_config: Option<LintConfig>,
     maybe_lint_flags: Option<LintFlags>,
   ) -> Result<Self, AnyError> {
-    let mut maybe_reporter_kind =
-      maybe_lint_flags.as_ref().and_then(|lint_flags| {
-        if lint_flags.json {
-          Some(LintReporterKind::Json)
-        } else if lint_flags.compact {
-          Some(LintReporterKind::Compact)
-        } else {
-          None
-        }
-      });
+    let mut maybe_reporter_kind = match maybe_lint_flags.as_ref() {
+      Some(lint_flags) if lint_flags.json => Some(LintReporterKind::Json),
+      Some(lint_flags) if lint_flags.compact => Some(LintReporterKind::Compact),
+      Some(lint_flags) => lint_flags
+        .reporter
+        .as_deref()
+        .and_then(LintReporterKind::from_name),
+      None => None,
+    };
 
     if maybe_reporter_kind.is_none() {
       // Flag not set, so try to get lint reporter from the config file.
       if let Some(lint_config) = &maybe_lint_config {
         maybe_reporter_kind = match lint_config.report.as_deref() {
-          Some("json") => Some(LintReporterKind::Json),
-          Some("compact") => Some(LintReporterKind::Compact),
-          Some("pretty") => Some(LintReporterKind::Pretty),
-          Some(_) => {
-            bail!("Invalid lint report type in config file")
-          }
+          Some(report) => match LintReporterKind::from_name(report) {
+            Some(reporter_kind) => Some(reporter_kind),
+            None => bail!("Invalid lint report type in config file"),
+          },
           None => None,
         }
       }
     }
 
+    let fix = maybe_lint_flags.as_ref().map(|f| f.fix).unwrap_or(false);
     let (
       maybe_file_flags,
       maybe_rules_tags,
@@ -364,8 +605,88 @@ impl LintOptions {
         maybe_rules_include,
         maybe_rules_exclude,
       ),
+      fix,
+      plugins: Vec::new(),
+    })
+  }
+}
+
+/// Resolves the plugin modules listed in the `lintPlugins` option of the
+/// config file, relative to the config file.
+pub fn resolve_lint_plugins(
+  config_file: &ConfigFile,
+  extras: &ConfigFileExtras,
+) -> Result<Vec<ModuleSpecifier>, AnyError> {
+  let Some(value) = extras.lint_plugins.clone() else {
+    return Ok(Vec::new());
+  };
+  let plugins: Vec<String> = serde_json::from_value(value)
+    .context("Failed to parse \"lintPlugins\" configuration")?;
+  plugins
+    .iter()
+    .map(|plugin| {
+      deno_core::resolve_import(plugin, config_file.specifier.as_str())
+        .with_context(|| format!("Failed to resolve lint plugin \"{plugin}\""))
     })
+    .collect()
+}
+
+/// Resolves the unstable formatter languages enabled by the `unstable` option
+/// of the config file (`"fmt-css"`, `"fmt-html"` and `"fmt-yaml"`).
+pub fn resolve_unstable_fmt_options(
+  extras: &ConfigFileExtras,
+) -> Result<UnstableFmtOptions, AnyError> {
+  let Some(value) = extras.unstable.clone() else {
+    return Ok(UnstableFmtOptions::default());
+  };
+  let features: Vec<String> = serde_json::from_value(value)
+    .context("Failed to parse \"unstable\" configuration")?;
+  let enabled = |feature: &str| features.iter().any(|f| f == feature);
+  Ok(UnstableFmtOptions {
+    css: enabled("fmt-css"),
+    html: enabled("fmt-html"),
+    yaml: enabled("fmt-yaml"),
+  })
+}
+
+/// Resolves the `"tasks"` of the config file. A task is either a command, or
+/// an object with the command and its description, dependencies, inputs and
+/// outputs.
+pub fn resolve_tasks_config(
+  config_file: &ConfigFile,
+) -> Result<IndexMap<String, TaskDefinition>, AnyError> {
+  let Some(value) = config_file.json.tasks.clone() else {
+    return Ok(Default::default());
+  };
+  let tasks: IndexMap<String, serde_json::Value> =
+    serde_json::from_value(value)
+      .context("Failed to parse \"tasks\" configuration")?;
+  for name in tasks.keys() {
+    validate_task_name(name)?;
+  }
+  tasks
+    .into_iter()
+    .map(|(name, value)| {
+      let task: SerializedTaskDefinition = serde_json::from_value(value)
+        .with_context(|| format!("Failed to parse task \"{name}\""))?;
+      Ok((name, task.into()))
+    })
+    .collect()
+}
+
+/// Applies the same checks on task names as `deno_config`.
+fn validate_task_name(name: &str) -> Result<(), AnyError> {
+  if name.is_empty() {
+    bail!("Configuration file task names cannot be empty");
+  } else if !name
+    .chars()
+    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | ':'))
+  {
+    bail!("Configuration file task names must only contain alpha-numeric characters, colons (:), underscores (_), or dashes (-). Task: {}", name);
+  } else if !name.chars().next().unwrap().is_ascii_alphabetic() {
+    bail!("Configuration file task names must start with an alphabetic character. Task: {}", name);
   }
+  Ok(())
 }
 
 fn resolve_lint_rules_options(
@@ -395,6 +716,40 @@ fn resolve_lint_rules_options(
   }
 }
 
+/// The top level properties of the config file that `deno_config` does not
+/// know about. They are read once, when the config file is loaded, and are
+/// only parsed by the subcommands that use them.
+#[derive(Clone, Debug, Default)]
+pub struct ConfigFileExtras {
+  lint_plugins: Option<serde_json::Value>,
+  coverage: Option<serde_json::Value>,
+  unstable: Option<serde_json::Value>,
+}
+
+impl ConfigFileExtras {
+  pub fn read(config_file: &ConfigFile) -> Result<Self, AnyError> {
+    let Ok(path) = config_file.specifier.to_file_path() else {
+      return Ok(Self::default());
+    };
+    let text = std::fs::read_to_string(&path)
+      .with_context(|| format!("Failed to read {}", path.display()))?;
+    Self::parse(&text)
+      .with_context(|| format!("Failed to parse {}", path.display()))
+  }
+
+  fn parse(text: &str) -> Result<Self, AnyError> {
+    let value = jsonc_parser::parse_to_serde_value(text, &Default::default())?;
+    let Some(serde_json::Value::Object(mut object)) = value else {
+      return Ok(Self::default());
+    };
+    Ok(Self {
+      lint_plugins: object.remove("lintPlugins"),
+      coverage: object.remove("coverage"),
+      unstable: object.remove("unstable"),
+    })
+  }
+}
+
 /// Discover `package.json` file. If `maybe_stop_at` is provided, we will stop
 /// crawling up the directory tree at that path.
 fn discover_package_json(
@@ -575,6 +930,7 @@ pub struct CliOptions {
   maybe_node_modules_folder: Option<PathBuf>,
   maybe_vendor_folder: Option<PathBuf>,
   maybe_config_file: Option<ConfigFile>,
+  config_file_extras: ConfigFileExtras,
   maybe_package_json: Option<PackageJson>,
   maybe_lockfile: Option<Arc<Mutex<Lockfile>>>,
   overrides: CliOptionOverrides,
@@ -611,11 +967,16 @@ impl CliOptions {
     .with_context(|| "Resolving node_modules folder.")?;
     let maybe_vendor_folder =
       resolve_vendor_folder(&initial_cwd, &flags, maybe_config_file.as_ref());
+    let config_file_extras = match &maybe_config_file {
+      Some(config_file) => ConfigFileExtras::read(config_file)?,
+      None => ConfigFileExtras::default(),
+    };
 
     Ok(Self {
       flags,
       initial_cwd,
       maybe_config_file,
+      config_file_extras,
       maybe_lockfile,
       maybe_package_json,
       maybe_node_modules_folder,
@@ -948,9 +1309,9 @@ impl CliOptions {
 
   pub fn resolve_tasks_config(
     &self,
-  ) -> Result<IndexMap<String, String>, AnyError> {
+  ) -> Result<IndexMap<String, TaskDefinition>, AnyError> {
     if let Some(config_file) = &self.maybe_config_file {
-      config_file.resolve_tasks_config()
+      resolve_tasks_config(config_file)
     } else if self.maybe_package_json.is_some() {
       Ok(Default::default())
     } else {
@@ -1020,7 +1381,8 @@ impl CliOptions {
     } else {
       None
     };
-    FmtOptions::resolve(maybe_fmt_config, Some(fmt_flags))
+    let unstable = resolve_unstable_fmt_options(&self.config_file_extras)?;
+    FmtOptions::resolve(maybe_fmt_config, Some(fmt_flags), unstable)
   }
 
   pub fn resolve_lint_options(
@@ -1032,7 +1394,13 @@ impl CliOptions {
     } else {
       None
     };
-    LintOptions::resolve(maybe_lint_config, Some(lint_flags))
+    let mut lint_options =
+      LintOptions::resolve(maybe_lint_config, Some(lint_flags))?;
+    if let Some(config_file) = &self.maybe_config_file {
+      lint_options.plugins =
+        resolve_lint_plugins(config_file, &self.config_file_extras)?;
+    }
+    Ok(lint_options)
   }
 
   pub fn resolve_test_options(
@@ -1047,6 +1415,20 @@ impl CliOptions {
     TestOptions::resolve(maybe_test_config, Some(test_flags))
   }
 
+  pub fn resolve_coverage_options(&self) -> Result<CoverageOptions, AnyError> {
+    let Some(config_file) = &self.maybe_config_file else {
+      return Ok(CoverageOptions::default());
+    };
+    let Some(value) = self.config_file_extras.coverage.clone() else {
+      return Ok(CoverageOptions::default());
+    };
+    let config: SerializedCoverageConfig = serde_json::from_value(value)
+      .context("Failed to parse \"coverage\" configuration")?;
+    let config_path = specifier_to_file_path(&config_file.specifier)?;
+    let base_dir = config_path.parent().unwrap_or(&config_path);
+    CoverageOptions::resolve(config, base_dir)
+  }
+
   pub fn resolve_bench_options(
     &self,
     bench_flags: BenchFlags,
@@ -1176,6 +1558,37 @@ impl CliOptions {
     self.flags.seed
   }
 
+  pub fn audit_permissions(&self) -> Option<&PathBuf> {
+    self.flags.audit_permissions.as_ref()
+  }
+
+  /// Maximum size in bytes of `localStorage` and `sessionStorage`.
+  pub fn local_storage_quota(&self) -> Option<usize> {
+    self
+      .flags
+      .local_storage_quota
+      .map(|megabytes| megabytes as usize * 1024 * 1024)
+  }
+
+  /// Size limits of the Cache API storage.
+  pub fn cache_storage_limits(&self) -> CacheLimits {
+    let megabytes_to_bytes = |megabytes: u64| megabytes * 1024 * 1024;
+    CacheLimits {
+      max_cache_bytes: self.flags.cache_quota.map(megabytes_to_bytes),
+      max_total_bytes: self.flags.cache_storage_quota.map(megabytes_to_bytes),
+    }
+  }
+
+  /// Whether the Cache API should keep its caches in memory instead of on disk.
+  pub fn in_memory_cache_storage(&self) -> bool {
+    self.flags.cache_storage_backend == CacheStorageBackend::Memory
+  }
+
+  /// Whether `BroadcastChannel` messages should also reach other processes.
+  pub fn shared_broadcast_channel(&self) -> bool {
+    self.flags.broadcast_channel_backend == BroadcastChannelBackend::Sqlite
+  }
+
   pub fn sub_command(&self) -> &DenoSubcommand {
     &self.flags.subcommand
   }
@@ -1434,6 +1847,7 @@ pub fn npm_pkg_req_ref_to_binary_command(
 #[cfg(test)]
 mod test {
   use super::*;
+  use deno_core::serde_json::json;
   use pretty_assertions::assert_eq;
 
   #[cfg(not(windows))]
@@ -1668,4 +2082,160 @@ mod test {
       ]
     )
   }
+
+  #[cfg(not(windows))]
+  #[test]
+  fn resolve_coverage_options() {
+    let config: SerializedCoverageConfig = serde_json::from_value(json!({
+      "thresholds": { "lines": 80, "branches": 70 },
+      "overrides": [
+        { "include": ["src/legacy/"], "thresholds": { "lines": 50 } },
+        { "include": ["src/**/*_gen.ts"], "thresholds": { "branches": 0 } }
+      ]
+    }))
+    .unwrap();
+    let options =
+      CoverageOptions::resolve(config, Path::new("/project")).unwrap();
+    assert!(options.has_thresholds());
+
+    assert_eq!(
+      options.thresholds,
+      CoverageThresholds {
+        lines: Some(80.0),
+        branches: Some(70.0),
+        functions: None,
+      }
+    );
+    assert_eq!(
+      options.override_thresholds_for(Path::new("/project/src/mod.ts")),
+      None
+    );
+    assert_eq!(
+      options.override_thresholds_for(Path::new("/project/src/legacy/a.ts")),
+      Some(CoverageThresholds {
+        lines: Some(50.0),
+        branches: Some(70.0),
+        functions: None,
+      })
+    );
+    assert_eq!(
+      options
+        .override_thresholds_for(Path::new("/project/src/legacy/a_gen.ts")),
+      Some(CoverageThresholds {
+        lines: Some(50.0),
+        branches: Some(0.0),
+        functions: None,
+      })
+    );
+
+    let config: SerializedCoverageConfig =
+      serde_json::from_value(json!({ "thresholds": { "lines": 101 } }))
+        .unwrap();
+    assert!(CoverageOptions::resolve(config, Path::new("/project")).is_err());
+  }
+
+  #[test]
+  fn parse_config_file_extras() {
+    let extras = ConfigFileExtras::parse(
+      r#"{
+        // comments are allowed
+        "lintPlugins": ["./plugin.ts"],
+        "tasks": { "build": "deno run build.ts" },
+        "fmt": { "lineWidth": 100 },
+        "unstable": ["fmt-css", "fmt-yaml"]
+      }"#,
+    )
+    .unwrap();
+    assert_eq!(extras.lint_plugins, Some(json!(["./plugin.ts"])));
+    assert_eq!(extras.coverage, None);
+    assert_eq!(
+      resolve_unstable_fmt_options(&extras).unwrap(),
+      UnstableFmtOptions {
+        css: true,
+        html: false,
+        yaml: true,
+      }
+    );
+  }
+
+  #[test]
+  fn deserialize_task_definitions() {
+    let config_text = r#"{
+      "tasks": {
+        "clean": "rm -rf dist",
+        "build": {
+          "command": "deno run -A build.ts",
+          "description": "Builds the site",
+          "dependencies": ["clean"],
+          "inputs": ["src/**/*.ts"],
+          "outputs": ["dist"]
+        },
+        "ci": { "dependencies": ["build"] }
+      }
+    }"#;
+    // remote config files support the object form as well
+    let config_specifier =
+      ModuleSpecifier::parse("https://deno.land/x/app/deno.json").unwrap();
+    let config_file = ConfigFile::new(config_text, config_specifier).unwrap();
+    let tasks = resolve_tasks_config(&config_file)
+      .unwrap()
+      .into_iter()
+      .collect::<Vec<(String, TaskDefinition)>>();
+    assert_eq!(
+      tasks,
+      vec![
+        (
+          "clean".to_string(),
+          TaskDefinition::from_command("rm -rf dist".to_string())
+        ),
+        (
+          "build".to_string(),
+          TaskDefinition {
+            command: Some("deno run -A build.ts".to_string()),
+            description: Some("Builds the site".to_string()),
+            dependencies: vec!["clean".to_string()],
+            inputs: vec!["src/**/*.ts".to_string()],
+            outputs: vec!["dist".to_string()],
+          }
+        ),
+        (
+          "ci".to_string(),
+          TaskDefinition {
+            dependencies: vec!["build".to_string()],
+            ..Default::default()
+          }
+        ),
+      ]
+    );
+
+    assert!(serde_json::from_value::<SerializedTaskDefinition>(
+      json!({ "command": "echo", "deps": [] })
+    )
+    .is_err());
+  }
+
+  #[test]
+  fn resolve_tasks_config_validates_names() {
+    let config_specifier =
+      ModuleSpecifier::parse("file:///deno/deno.json").unwrap();
+    for (config_text, message) in [
+      (
+        r#"{ "tasks": { "": { "command": "echo" } } }"#,
+        "Configuration file task names cannot be empty",
+      ),
+      (
+        r#"{ "tasks": { "build!": { "command": "echo" } } }"#,
+        "Configuration file task names must only contain alpha-numeric characters, colons (:), underscores (_), or dashes (-). Task: build!",
+      ),
+      (
+        r#"{ "tasks": { "1build": { "dependencies": [] } } }"#,
+        "Configuration file task names must start with an alphabetic character. Task: 1build",
+      ),
+    ] {
+      let config_file =
+        ConfigFile::new(config_text, config_specifier.clone()).unwrap();
+      let err = resolve_tasks_config(&config_file).unwrap_err();
+      assert_eq!(err.to_string(), message);
+    }
+  }
 }
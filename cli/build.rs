@@ -310,7 +310,7 @@ deno_core::extension!(
 #[must_use = "The files listed by create_cli_snapshot should be printed as 'cargo:rerun-if-changed' lines"]
 fn create_cli_snapshot(snapshot_path: PathBuf) -> CreateSnapshotOutput {
   use deno_core::Extension;
-  use deno_runtime::deno_cache::SqliteBackedCache;
+  use deno_runtime::deno_cache::CacheImpl;
   use deno_runtime::deno_http::DefaultHttpPropertyExtractor;
   use deno_runtime::deno_kv::sqlite::SqliteDbHandler;
   use deno_runtime::permissions::PermissionsContainer;
@@ -328,13 +328,13 @@ fn create_cli_snapshot(snapshot_path: PathBuf) -> CreateSnapshotOutput {
       Default::default(),
     ),
     deno_fetch::deno_fetch::init_ops::<PermissionsContainer>(Default::default()),
-    deno_cache::deno_cache::init_ops::<SqliteBackedCache>(None),
+    deno_cache::deno_cache::init_ops::<CacheImpl>(None),
     deno_websocket::deno_websocket::init_ops::<PermissionsContainer>(
       "".to_owned(),
       None,
       None,
     ),
-    deno_webstorage::deno_webstorage::init_ops(None),
+    deno_webstorage::deno_webstorage::init_ops(None, None),
     deno_crypto::deno_crypto::init_ops(None),
     deno_broadcast_channel::deno_broadcast_channel::init_ops(
       deno_broadcast_channel::InMemoryBroadcastChannel::default(),
@@ -110,6 +110,18 @@ impl DenoDir {
     self.root.join("check_cache_v1")
   }
 
+  /// Path for the cache used to skip tasks whose inputs didn't change.
+  pub fn task_cache_db_file_path(&self) -> PathBuf {
+    // bump this version name to invalidate the entire cache
+    self.root.join("task_cache_v1")
+  }
+
+  /// Path for the database relaying `BroadcastChannel` messages between
+  /// processes.
+  pub fn broadcast_channel_db_file_path(&self) -> PathBuf {
+    self.root.join("broadcast_channel_v1")
+  }
+
   /// Path to the registries cache, used for the lps.
   pub fn registries_folder_path(&self) -> PathBuf {
     self.root.join("registries")
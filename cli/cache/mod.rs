@@ -33,6 +33,7 @@ mod emit;
 mod incremental;
 mod node;
 mod parsed_source;
+mod task;
 
 pub use caches::Caches;
 pub use check::TypeCheckCache;
@@ -44,6 +45,7 @@ pub use emit::EmitCache;
 pub use incremental::IncrementalCache;
 pub use node::NodeAnalysisCache;
 pub use parsed_source::ParsedSourceCache;
+pub use task::TaskCache;
 
 /// Permissions used to save a file in the disk caches.
 pub const CACHE_PERM: u32 = 0o644;
@@ -0,0 +1,110 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+
+use deno_core::error::AnyError;
+use deno_runtime::deno_webstorage::rusqlite::params;
+
+use super::cache_db::CacheDB;
+use super::cache_db::CacheDBConfiguration;
+use super::cache_db::CacheFailure;
+
+pub static TASK_CACHE_DB: CacheDBConfiguration = CacheDBConfiguration {
+  table_initializer: "CREATE TABLE IF NOT EXISTS taskcache (
+      task_key TEXT PRIMARY KEY,
+      input_hash TEXT NOT NULL
+    );",
+  on_version_change: "DELETE FROM taskcache;",
+  preheat_queries: &[],
+  // If the cache fails, just ignore all caching attempts
+  on_failure: CacheFailure::Blackhole,
+};
+
+/// The cache used to skip running a task again when its inputs haven't
+/// changed since its last successful run.
+///
+/// This stores a hash of the inputs of the last successful run of each task,
+/// keyed by the config file and the task name.
+#[derive(Clone)]
+pub struct TaskCache(CacheDB);
+
+impl TaskCache {
+  pub fn new(db: CacheDB) -> Self {
+    Self(db)
+  }
+
+  pub fn get_input_hash(&self, task_key: &str) -> Option<String> {
+    match self.get_input_hash_result(task_key) {
+      Ok(hash) => hash,
+      Err(err) => {
+        if cfg!(debug_assertions) {
+          panic!("Error retrieving task input hash: {err}");
+        } else {
+          log::debug!("Error retrieving task input hash: {}", err);
+          // fail silently when not debugging
+          None
+        }
+      }
+    }
+  }
+
+  fn get_input_hash_result(
+    &self,
+    task_key: &str,
+  ) -> Result<Option<String>, AnyError> {
+    self.0.query_row(
+      "SELECT input_hash FROM taskcache WHERE task_key=?1 LIMIT 1",
+      params![task_key],
+      |row| Ok(row.get::<_, String>(0)?),
+    )
+  }
+
+  pub fn set_input_hash(&self, task_key: &str, input_hash: &str) {
+    if let Err(err) = self.set_input_hash_result(task_key, input_hash) {
+      if cfg!(debug_assertions) {
+        panic!("Error saving task input hash: {err}");
+      } else {
+        log::debug!("Error saving task input hash: {}", err);
+      }
+    }
+  }
+
+  fn set_input_hash_result(
+    &self,
+    task_key: &str,
+    input_hash: &str,
+  ) -> Result<(), AnyError> {
+    self.0.execute(
+      "INSERT OR REPLACE INTO taskcache (task_key, input_hash) VALUES (?1, ?2)",
+      params![task_key, input_hash],
+    )?;
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  pub fn task_cache_general_use() {
+    let conn = CacheDB::in_memory(&TASK_CACHE_DB, "1.0.0");
+    let cache = TaskCache::new(conn);
+
+    assert_eq!(cache.get_input_hash("deno.json#build"), None);
+    cache.set_input_hash("deno.json#build", "a");
+    assert_eq!(
+      cache.get_input_hash("deno.json#build").as_deref(),
+      Some("a")
+    );
+    cache.set_input_hash("deno.json#build", "b");
+    assert_eq!(
+      cache.get_input_hash("deno.json#build").as_deref(),
+      Some("b")
+    );
+    assert_eq!(cache.get_input_hash("deno.json#test"), None);
+
+    // changing the cli version clears the cache
+    let conn = cache.0.recreate_with_version("2.0.0");
+    let cache = TaskCache::new(conn);
+    assert_eq!(cache.get_input_hash("deno.json#build"), None);
+  }
+}
@@ -656,14 +656,22 @@ impl CliFactory {
   ) -> Result<CliMainWorkerOptions, AnyError> {
     Ok(CliMainWorkerOptions {
       argv: self.options.argv().clone(),
+      broadcast_channel_db_path: if self.options.shared_broadcast_channel() {
+        Some(self.deno_dir()?.broadcast_channel_db_file_path())
+      } else {
+        None
+      },
+      cache_storage_limits: self.options.cache_storage_limits(),
       log_level: self.options.log_level().unwrap_or(log::Level::Info).into(),
       coverage_dir: self.options.coverage_dir(),
       enable_testing_features: self.options.enable_testing_features(),
       has_node_modules_dir: self.options.has_node_modules_dir(),
+      in_memory_cache_storage: self.options.in_memory_cache_storage(),
       inspect_brk: self.options.inspect_brk().is_some(),
       inspect_wait: self.options.inspect_wait().is_some(),
       is_inspecting: self.options.is_inspecting(),
       is_npm_main: self.options.is_npm_main(),
+      local_storage_quota: self.options.local_storage_quota(),
       location: self.options.location_flag().clone(),
       maybe_binary_npm_command_name: {
         let mut maybe_binary_command_name = None;
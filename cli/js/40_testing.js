@@ -21,6 +21,8 @@ const {
   MapPrototypeHas,
   MapPrototypeSet,
   MathCeil,
+  MathSqrt,
+  NumberIsInteger,
   ObjectKeys,
   ObjectHasOwn,
   ObjectPrototypeIsPrototypeOf,
@@ -421,6 +423,11 @@ function assertExit(fn, isTest) {
 
 function wrapOuter(fn, desc) {
   return async function outerWrapped() {
+    // Tests can be run more than once with `--retry`, so start from a clean
+    // slate.
+    const state = MapPrototypeGet(testStates, desc.id);
+    state.children = [];
+    state.completed = false;
     try {
       if (desc.ignore) {
         return "ignored";
@@ -429,7 +436,6 @@ function wrapOuter(fn, desc) {
     } catch (error) {
       return { failed: { jsError: core.destructureError(error) } };
     } finally {
-      const state = MapPrototypeGet(testStates, desc.id);
       for (const childDesc of state.children) {
         stepReportResult(childDesc, { failed: "incomplete" }, 0);
       }
@@ -692,6 +698,13 @@ function testInner(
 
   testDesc = { ...testDesc, ...overrides };
 
+  if (
+    testDesc.retry != undefined &&
+    (!NumberIsInteger(testDesc.retry) || testDesc.retry < 0)
+  ) {
+    throw new TypeError("The 'retry' option must be a non-negative integer");
+  }
+
   // Delete this prop in case the user passed it. It's used to detect steps.
   delete testDesc.parent;
   const jsError = core.destructureError(new Error());
@@ -860,9 +873,10 @@ function bench(
   benchDesc.warmup = false;
   benchDesc.name = escapeName(benchDesc.name);
 
-  const { id, origin } = ops.op_register_bench(benchDesc);
+  const { id, origin, memory } = ops.op_register_bench(benchDesc);
   benchDesc.id = id;
   benchDesc.origin = origin;
+  benchDesc.memory = memory;
 }
 
 function compareMeasurements(a, b) {
@@ -873,6 +887,14 @@ function compareMeasurements(a, b) {
 }
 
 function benchStats(n, highPrecision, usedExplicitTimers, avg, min, max, all) {
+  // Sample standard deviation, used to tell real changes from noise when
+  // comparing against a saved baseline.
+  const mean = avg / n;
+  let squaredDeviations = 0;
+  for (let i = 0; i < all.length; i++) {
+    squaredDeviations += (all[i] - mean) ** 2;
+  }
+  const stddev = n > 1 ? MathSqrt(squaredDeviations / (n - 1)) : 0;
   return {
     n,
     min,
@@ -882,6 +904,7 @@ function benchStats(n, highPrecision, usedExplicitTimers, avg, min, max, all) {
     p995: all[MathCeil(n * (99.5 / 100)) - 1],
     p999: all[MathCeil(n * (99.9 / 100)) - 1],
     avg: !highPrecision ? (avg / n) : MathCeil(avg / n),
+    stddev,
     highPrecision,
     usedExplicitTimers,
   };
@@ -1087,6 +1110,52 @@ function createBenchContext(desc) {
   };
 }
 
+/**
+ * Runs the bench in a separate pass from the timing measurements, sampling the
+ * used heap around every iteration. Iterations after which the heap shrank are
+ * counted as heap shrinks and left out of the heap delta. This is only a hint
+ * that the garbage collector ran: collections that free less than the
+ * iteration allocates, or that run outside of the sampled iterations, go
+ * unnoticed.
+ */
+async function benchMemory(timeBudget, fn, async, context) {
+  let n = 0;
+  let heapShrinks = 0;
+  let heapGrowth = 0;
+  let heapGrowthSamples = 0;
+  let iterations = 10;
+  let budget = timeBudget * 1e6;
+
+  while (budget > 0 || iterations-- > 0) {
+    const before = ops.op_bench_heap_used();
+    const t1 = benchNow();
+    if (async) {
+      await fn(context);
+    } else {
+      fn(context);
+    }
+    const t2 = benchNow();
+    const after = ops.op_bench_heap_used();
+    currentBenchUserExplicitStart = null;
+    currentBenchUserExplicitEnd = null;
+
+    n++;
+    budget -= t2 - t1;
+    if (after < before) {
+      heapShrinks++;
+    } else {
+      heapGrowth += after - before;
+      heapGrowthSamples++;
+    }
+  }
+
+  return {
+    n,
+    heapDelta: heapGrowthSamples > 0 ? heapGrowth / heapGrowthSamples : 0,
+    heapShrinks,
+  };
+}
+
 /** Wrap a user benchmark function in one which returns a structured result. */
 function wrapBenchmark(desc) {
   const fn = desc.fn;
@@ -1116,6 +1185,15 @@ function wrapBenchmark(desc) {
       const benchTimeInMs = 500;
       const context = createBenchContext(desc);
       const stats = await benchMeasure(benchTimeInMs, fn, desc.async, context);
+      if (desc.memory) {
+        const memoryTimeInMs = 100;
+        stats.memory = await benchMemory(
+          memoryTimeInMs,
+          fn,
+          desc.async,
+          context,
+        );
+      }
 
       return { ok: stats };
     } catch (error) {
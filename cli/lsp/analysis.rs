@@ -9,6 +9,7 @@ use super::tsc;
 use crate::npm::CliNpmResolver;
 use crate::npm::NpmResolution;
 use crate::tools::lint::create_linter;
+use crate::tools::lint::LintPluginHost;
 
 use deno_ast::SourceRange;
 use deno_ast::SourceRangedForSpanned;
@@ -130,9 +131,11 @@ fn as_lsp_range(range: &deno_lint::diagnostic::Range) -> Range {
 pub fn get_lint_references(
   parsed_source: &deno_ast::ParsedSource,
   lint_rules: Vec<&'static dyn LintRule>,
+  lint_plugins: Option<LintPluginHost>,
 ) -> Result<Vec<Reference>, AnyError> {
-  let linter = create_linter(parsed_source.media_type(), lint_rules);
-  let lint_diagnostics = linter.lint_with_ast(parsed_source);
+  let linter =
+    create_linter(parsed_source.media_type(), lint_rules, lint_plugins);
+  let lint_diagnostics = linter.lint_with_ast(parsed_source)?;
 
   Ok(
     lint_diagnostics
@@ -110,8 +110,13 @@ pub fn server_capabilities(
       resolve_provider: Some(true),
     }),
     document_formatting_provider: Some(OneOf::Left(true)),
-    document_range_formatting_provider: None,
-    document_on_type_formatting_provider: None,
+    document_range_formatting_provider: Some(OneOf::Left(true)),
+    document_on_type_formatting_provider: Some(
+      DocumentOnTypeFormattingOptions {
+        first_trigger_character: ";".to_string(),
+        more_trigger_character: Some(vec!["}".to_string(), "\n".to_string()]),
+      },
+    ),
     selection_range_provider: Some(SelectionRangeProviderCapability::Simple(
       true,
     )),
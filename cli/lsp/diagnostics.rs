@@ -9,6 +9,7 @@ use super::documents::Document;
 use super::documents::DocumentsFilter;
 use super::language_server;
 use super::language_server::StateSnapshot;
+use super::logging::lsp_warn;
 use super::performance::Performance;
 use super::tsc;
 use super::tsc::TsServer;
@@ -20,6 +21,7 @@ use crate::graph_util;
 use crate::graph_util::enhanced_resolution_error_message;
 use crate::lsp::lsp_custom::DiagnosticBatchNotificationParams;
 use crate::tools::lint::get_configured_rules;
+use crate::tools::lint::LintPluginHost;
 
 use deno_ast::MediaType;
 use deno_core::anyhow::anyhow;
@@ -57,6 +59,7 @@ pub struct DiagnosticServerUpdateMessage {
   pub snapshot: Arc<StateSnapshot>,
   pub config: Arc<ConfigSnapshot>,
   pub lint_options: LintOptions,
+  pub lint_plugins: Option<LintPluginHost>,
   pub url_map: LspUrlMap,
 }
 
@@ -375,6 +378,7 @@ impl DiagnosticsServer {
                     snapshot,
                     config,
                     lint_options,
+                    lint_plugins,
                     url_map,
                   },
                 batch_index,
@@ -528,6 +532,7 @@ impl DiagnosticsServer {
                         &snapshot,
                         &config,
                         &lint_options,
+                        lint_plugins,
                         token,
                       )
                     }
@@ -703,6 +708,7 @@ fn generate_lint_diagnostics(
   snapshot: &language_server::StateSnapshot,
   config: &ConfigSnapshot,
   lint_options: &LintOptions,
+  lint_plugins: Option<LintPluginHost>,
   token: CancellationToken,
 ) -> DiagnosticVec {
   let documents = snapshot
@@ -734,6 +740,7 @@ fn generate_lint_diagnostics(
             config,
             lint_options,
             lint_rules.clone(),
+            lint_plugins.clone(),
             &document,
           ),
         },
@@ -747,6 +754,7 @@ fn generate_document_lint_diagnostics(
   config: &ConfigSnapshot,
   lint_options: &LintOptions,
   lint_rules: Vec<&'static dyn LintRule>,
+  lint_plugins: Option<LintPluginHost>,
   document: &Document,
 ) -> Vec<lsp::Diagnostic> {
   if !config.specifier_enabled(document.specifier()) {
@@ -757,15 +765,19 @@ fn generate_document_lint_diagnostics(
   }
   match document.maybe_parsed_source() {
     Some(Ok(parsed_source)) => {
-      if let Ok(references) =
-        analysis::get_lint_references(&parsed_source, lint_rules)
-      {
-        references
+      match analysis::get_lint_references(
+        &parsed_source,
+        lint_rules,
+        lint_plugins,
+      ) {
+        Ok(references) => references
           .into_iter()
           .map(|r| r.to_diagnostic())
-          .collect::<Vec<_>>()
-      } else {
-        Vec::new()
+          .collect::<Vec<_>>(),
+        Err(err) => {
+          lsp_warn!("Unable to lint {}: {:#}", document.specifier(), err);
+          Vec::new()
+        }
       }
     }
     Some(Err(_)) => Vec::new(),
@@ -1441,6 +1453,7 @@ let c: number = "a";
         &snapshot,
         &enabled_config,
         &Default::default(),
+        None,
         Default::default(),
       );
       assert_eq!(get_diagnostics_for_single(diagnostics).len(), 6);
@@ -1477,6 +1490,7 @@ let c: number = "a";
         &snapshot,
         &disabled_config,
         &Default::default(),
+        None,
         Default::default(),
       );
       assert_eq!(get_diagnostics_for_single(diagnostics).len(), 0);
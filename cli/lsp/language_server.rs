@@ -77,11 +77,15 @@ use super::urls::LspClientUrl;
 use crate::args::get_root_cert_store;
 use crate::args::package_json;
 use crate::args::resolve_import_map_from_specifier;
+use crate::args::resolve_lint_plugins;
+use crate::args::resolve_tasks_config;
+use crate::args::resolve_unstable_fmt_options;
 use crate::args::snapshot_from_lockfile;
 use crate::args::CaData;
 use crate::args::CacheSetting;
 use crate::args::CliOptions;
 use crate::args::ConfigFile;
+use crate::args::ConfigFileExtras;
 use crate::args::Flags;
 use crate::args::FmtOptions;
 use crate::args::LintOptions;
@@ -105,6 +109,7 @@ use crate::npm::NpmCacheDir;
 use crate::npm::NpmResolution;
 use crate::tools::fmt::format_file;
 use crate::tools::fmt::format_parsed_source;
+use crate::tools::lint::LintPluginHost;
 use crate::util::fs::remove_dir_all_if_exists;
 use crate::util::path::specifier_to_file_path;
 use crate::util::progress_bar::ProgressBar;
@@ -198,6 +203,8 @@ pub struct Inner {
   fmt_options: FmtOptions,
   /// An optional configuration for linter which has been taken from specified config file.
   lint_options: LintOptions,
+  /// The rules of the lint plugins listed in the config file.
+  lint_plugins: Option<LintPluginHost>,
   /// A lazily create "server" for handling test run requests.
   maybe_testing_server: Option<testing::TestServer>,
   /// Services used for dealing with npm related functionality.
@@ -611,6 +618,7 @@ impl Inner {
       maybe_package_json: None,
       fmt_options: Default::default(),
       lint_options: Default::default(),
+      lint_plugins: None,
       maybe_testing_server: None,
       module_registries,
       module_registries_location,
@@ -1152,6 +1160,7 @@ impl Inner {
     self.config.clear_config_file();
     self.fmt_options = Default::default();
     self.lint_options = Default::default();
+    self.lint_plugins = None;
     if let Some(config_file) = self.get_config_file()? {
       let lint_options = config_file
         .to_lint_config()
@@ -1161,17 +1170,42 @@ impl Inner {
         .map_err(|err| {
           anyhow!("Unable to update lint configuration: {:?}", err)
         })?;
+      let extras = ConfigFileExtras::read(&config_file);
       let fmt_options = config_file
         .to_fmt_config()
         .and_then(|maybe_fmt_config| {
-          FmtOptions::resolve(maybe_fmt_config, None)
+          let unstable = match &extras {
+            Ok(extras) => resolve_unstable_fmt_options(extras)?,
+            Err(_) => Default::default(),
+          };
+          FmtOptions::resolve(maybe_fmt_config, None, unstable)
         })
         .map_err(|err| {
           anyhow!("Unable to update formatter configuration: {:?}", err)
         })?;
+      let lint_plugins = match extras
+        .and_then(|extras| resolve_lint_plugins(&config_file, &extras))
+      {
+        Ok(plugins) if plugins.is_empty() => None,
+        Ok(plugins) => {
+          let load = move || LintPluginHost::new(plugins);
+          match deno_core::unsync::spawn_blocking(load).await? {
+            Ok(lint_plugins) => Some(lint_plugins),
+            Err(err) => {
+              lsp_warn!("Unable to load lint plugins: {:#}", err);
+              None
+            }
+          }
+        }
+        Err(err) => {
+          lsp_warn!("Unable to resolve lint plugins: {:#}", err);
+          None
+        }
+      };
 
       self.config.set_config_file(config_file);
       self.lint_options = lint_options;
+      self.lint_plugins = lint_plugins;
       self.fmt_options = fmt_options;
       self.recreate_http_client_and_dependents().await?;
     }
@@ -1714,14 +1748,66 @@ impl Inner {
     &self,
     params: DocumentFormattingParams,
   ) -> LspResult<Option<Vec<TextEdit>>> {
-    let specifier = self
-      .url_map
-      .normalize_url(&params.text_document.uri, LspUrlKind::File);
+    let mark = self.performance.mark("formatting", Some(&params));
+    let text_edits =
+      self.format_document(&params.text_document.uri, true).await;
+    self.performance.measure(mark);
+    text_edits
+  }
+
+  async fn range_formatting(
+    &self,
+    params: DocumentRangeFormattingParams,
+  ) -> LspResult<Option<Vec<TextEdit>>> {
+    let mark = self.performance.mark("range_formatting", Some(&params));
+    let text_edits = self
+      .format_document(&params.text_document.uri, true)
+      .await?
+      .map(|text_edits| text::get_edits_in_range(text_edits, &params.range))
+      .filter(|text_edits| !text_edits.is_empty());
+    self.performance.measure(mark);
+    Ok(text_edits)
+  }
+
+  async fn on_type_formatting(
+    &self,
+    params: DocumentOnTypeFormattingParams,
+  ) -> LspResult<Option<Vec<TextEdit>>> {
+    let mark = self.performance.mark("on_type_formatting", Some(&params));
+    let uri = &params.text_document_position.text_document.uri;
+    let specifier = self.url_map.normalize_url(uri, LspUrlKind::File);
+    let Some(document) = self.documents.get(&specifier) else {
+      return Ok(None);
+    };
+    let range = text::get_on_type_format_range(
+      &document.content(),
+      params.text_document_position.position,
+      &params.ch,
+      document.media_type(),
+    );
+    // the document is usually incomplete while typing, so failing to format
+    // it is not worth a message
+    let text_edits = self
+      .format_document(uri, false)
+      .await?
+      .map(|text_edits| text::get_edits_in_range(text_edits, &range))
+      .filter(|text_edits| !text_edits.is_empty());
+    self.performance.measure(mark);
+    Ok(text_edits)
+  }
+
+  /// Gets the edits formatting the whole document, or `None` if there are no
+  /// changes or the document can't be formatted.
+  async fn format_document(
+    &self,
+    uri: &Url,
+    show_errors: bool,
+  ) -> LspResult<Option<Vec<TextEdit>>> {
+    let specifier = self.url_map.normalize_url(uri, LspUrlKind::File);
     let document = match self.documents.get(&specifier) {
       Some(doc) if doc.is_open() => doc,
       _ => return Ok(None),
     };
-    let mark = self.performance.mark("formatting", Some(&params));
     let file_path = specifier_to_file_path(&specifier).map_err(|err| {
       error!("{}", err);
       LspError::invalid_request()
@@ -1735,6 +1821,7 @@ impl Inner {
     // spawn a blocking task to allow doing other work while this is occurring
     let format_result = deno_core::unsync::spawn_blocking({
       let fmt_options = self.fmt_options.options.clone();
+      let unstable_fmt_options = self.fmt_options.unstable;
       let document = document.clone();
       move || {
         match document.maybe_parsed_source() {
@@ -1752,7 +1839,12 @@ impl Inner {
               .map(|ext| file_path.with_extension(ext))
               .unwrap_or(file_path);
             // it's not a js/ts file, so attempt to format its contents
-            format_file(&file_path, &document.content(), &fmt_options)
+            format_file(
+              &file_path,
+              &document.content(),
+              &fmt_options,
+              &unstable_fmt_options,
+            )
           }
         }
       }
@@ -1769,12 +1861,13 @@ impl Inner {
       Ok(None) => Some(Vec::new()),
       Err(err) => {
         // TODO(lucacasonato): handle error properly
-        lsp_warn!("Format error: {:#}", err);
+        if show_errors {
+          lsp_warn!("Format error: {:#}", err);
+        }
         None
       }
     };
 
-    self.performance.measure(mark);
     if let Some(text_edits) = text_edits {
       if text_edits.is_empty() {
         Ok(None)
@@ -1782,7 +1875,9 @@ impl Inner {
         Ok(Some(text_edits))
       }
     } else {
-      self.client.show_message(MessageType::WARNING, format!("Unable to format \"{specifier}\". Likely due to unrecoverable syntax errors in the file."));
+      if show_errors {
+        self.client.show_message(MessageType::WARNING, format!("Unable to format \"{specifier}\". Likely due to unrecoverable syntax errors in the file."));
+      }
       Ok(None)
     }
   }
@@ -3028,6 +3123,7 @@ impl Inner {
       snapshot: self.snapshot(),
       config: self.config.snapshot(),
       lint_options: self.lint_options.clone(),
+      lint_plugins: self.lint_plugins.clone(),
       url_map: self.url_map.clone(),
     };
     if let Err(err) = self.diagnostics_server.update(snapshot) {
@@ -3332,6 +3428,20 @@ impl tower_lsp::LanguageServer for LanguageServer {
     self.0.read().await.formatting(params).await
   }
 
+  async fn range_formatting(
+    &self,
+    params: DocumentRangeFormattingParams,
+  ) -> LspResult<Option<Vec<TextEdit>>> {
+    self.0.read().await.range_formatting(params).await
+  }
+
+  async fn on_type_formatting(
+    &self,
+    params: DocumentOnTypeFormattingParams,
+  ) -> LspResult<Option<Vec<TextEdit>>> {
+    self.0.read().await.on_type_formatting(params).await
+  }
+
   async fn hover(&self, params: HoverParams) -> LspResult<Option<Hover>> {
     self.0.read().await.hover(params).await
   }
@@ -3570,12 +3680,34 @@ impl Inner {
   }
 
   fn get_tasks(&self) -> LspResult<Option<Value>> {
-    Ok(
-      self
-        .config
-        .maybe_config_file()
-        .and_then(|cf| cf.to_lsp_tasks()),
-    )
+    let Some(config_file) = self.config.maybe_config_file() else {
+      return Ok(None);
+    };
+    let tasks = match resolve_tasks_config(config_file) {
+      Ok(tasks) => tasks,
+      Err(err) => {
+        lsp_warn!("Unable to resolve tasks: {:#}", err);
+        return Ok(None);
+      }
+    };
+    Ok(Some(
+      tasks
+        .into_iter()
+        .map(|(name, task)| {
+          let detail = match (task.command, task.description) {
+            (Some(command), Some(description)) => {
+              format!("{command} - {description}")
+            }
+            (Some(detail), None) | (None, Some(detail)) => detail,
+            (None, None) => String::new(),
+          };
+          json!({
+            "name": name,
+            "detail": detail,
+          })
+        })
+        .collect(),
+    ))
   }
 
   async fn inlay_hint(
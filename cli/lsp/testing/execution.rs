@@ -298,6 +298,7 @@ impl TestRun {
               filter,
               shuffle: None,
               trace_ops: false,
+              retry: 0,
             },
           ))
         };
@@ -360,6 +361,10 @@ impl TestRun {
                 let description = tests.read().get(&id).unwrap().clone();
                 match &result {
                   test::TestResult::Ok => summary.passed += 1,
+                  test::TestResult::Flaky(failures) => {
+                    summary.passed += 1;
+                    summary.flaky.push((description.clone(), failures.len()));
+                  }
                   test::TestResult::Ignored => summary.ignored += 1,
                   test::TestResult::Failed(error) => {
                     summary.failed += 1;
@@ -628,7 +633,7 @@ impl LspTestReporter {
   ) {
     self.current_test = None;
     match result {
-      test::TestResult::Ok => {
+      test::TestResult::Ok | test::TestResult::Flaky(_) => {
         let desc = self.tests.get(&desc.id).unwrap();
         self.progress(lsp_custom::TestRunProgressMessage::Passed {
           test: desc.as_test_identifier(&self.tests),
@@ -1,5 +1,8 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
 
+use deno_ast::swc::parser::token::Token;
+use deno_ast::MediaType;
+use deno_ast::TokenOrComment;
 use deno_core::error::custom_error;
 use deno_core::error::AnyError;
 use dissimilar::diff;
@@ -257,6 +260,117 @@ pub fn get_edits(a: &str, b: &str, line_index: &LineIndex) -> Vec<TextEdit> {
   text_edits
 }
 
+/// Keeps the edits that only change the lines of the range. This is how a
+/// part of a document is formatted, from the edits formatting the whole
+/// document.
+pub fn get_edits_in_range(
+  text_edits: Vec<TextEdit>,
+  range: &lsp::Range,
+) -> Vec<TextEdit> {
+  let start_line = range.start.line;
+  // a selection ending at the start of a line doesn't include that line
+  let end_line = if range.end.character == 0 && range.end.line > start_line {
+    range.end.line - 1
+  } else {
+    range.end.line
+  };
+  text_edits
+    .into_iter()
+    .filter(|edit| {
+      edit.range.start.line >= start_line && edit.range.end.line <= end_line
+    })
+    .collect()
+}
+
+/// The range to format after `ch` was typed before `position`. That is the
+/// block closed by a `}`, the line ended by a new line, or otherwise the
+/// current line.
+pub fn get_on_type_format_range(
+  text: &str,
+  position: lsp::Position,
+  ch: &str,
+  media_type: MediaType,
+) -> lsp::Range {
+  let lines = text.split('\n').collect::<Vec<_>>();
+  let line = position.line as usize;
+  let (start_line, end_line) = match ch {
+    // the new line only holds the indentation the editor inserted, which
+    // formatting would remove
+    "\n" => (line.saturating_sub(1), line.saturating_sub(1)),
+    "}" => {
+      let start_line = utf16_position_to_offset(&lines, position)
+        .and_then(|offset| find_opening_brace(text, offset, media_type))
+        .map(|offset| text[..offset].matches('\n').count())
+        .unwrap_or(line);
+      (start_line, line)
+    }
+    _ => (line, line),
+  };
+  let end_character = lines
+    .get(end_line)
+    .map(|text| text.encode_utf16().count() as u32)
+    .unwrap_or(0);
+  lsp::Range {
+    start: lsp::Position {
+      line: start_line as u32,
+      character: 0,
+    },
+    end: lsp::Position {
+      line: end_line as u32,
+      character: end_character,
+    },
+  }
+}
+
+fn utf16_position_to_offset(
+  lines: &[&str],
+  position: lsp::Position,
+) -> Option<usize> {
+  let line_start = lines
+    .iter()
+    .take(position.line as usize)
+    .map(|line| line.len() + 1)
+    .sum::<usize>();
+  let line = lines.get(position.line as usize)?;
+  let mut utf16_offset = 0;
+  for (byte_offset, c) in line.char_indices() {
+    if utf16_offset >= position.character as usize {
+      return Some(line_start + byte_offset);
+    }
+    utf16_offset += c.len_utf16();
+  }
+  Some(line_start + line.len())
+}
+
+/// Finds the offset of the `{` (or `${`) matching the `}` that ends at
+/// `end_offset`. The text is lexed, so that braces in strings, template
+/// literals and comments are not counted.
+fn find_opening_brace(
+  text: &str,
+  end_offset: usize,
+  media_type: MediaType,
+) -> Option<usize> {
+  let mut opening_braces = Vec::new();
+  for item in deno_ast::lex(text, media_type) {
+    if item.range.start >= end_offset {
+      break;
+    }
+    match item.inner {
+      TokenOrComment::Token(Token::LBrace | Token::DollarLBrace) => {
+        opening_braces.push(item.range.start);
+      }
+      TokenOrComment::Token(Token::RBrace) => {
+        let opening_brace = opening_braces.pop();
+        if item.range.end == end_offset {
+          return opening_brace;
+        }
+      }
+      _ => {}
+    }
+  }
+  None
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -527,4 +641,100 @@ const C: char = \"メ メ\";
       ]
     )
   }
+
+  #[test]
+  fn test_get_edits_in_range() {
+    let a = "let a=1\nlet b=2\nlet c=3\n";
+    let b = "let a = 1;\nlet b = 2;\nlet c = 3;\n";
+    let edits = get_edits(a, b, &LineIndex::new(a));
+    let range = lsp::Range {
+      start: lsp::Position {
+        line: 1,
+        character: 3,
+      },
+      end: lsp::Position {
+        line: 2,
+        character: 0,
+      },
+    };
+    let actual = get_edits_in_range(edits, &range);
+    assert!(!actual.is_empty());
+    assert!(actual
+      .iter()
+      .all(|edit| edit.range.start.line == 1 && edit.range.end.line == 1));
+  }
+
+  #[test]
+  fn test_get_on_type_format_range() {
+    let text = "function f() {\n  if (a) {\n    b();\n  }\n}\nc();\n";
+    let range = |line, character, ch| {
+      let range = get_on_type_format_range(
+        text,
+        lsp::Position { line, character },
+        ch,
+        MediaType::TypeScript,
+      );
+      (range.start.line, range.end.line, range.end.character)
+    };
+    assert_eq!(range(4, 1, "}"), (0, 4, 1));
+    assert_eq!(range(3, 3, "}"), (1, 3, 3));
+    assert_eq!(range(5, 4, ";"), (5, 5, 4));
+  }
+
+  #[test]
+  fn test_get_on_type_format_range_new_line() {
+    // the indentation of the new line is left alone
+    let text = "if (a) {\n  \n}\n";
+    let range = get_on_type_format_range(
+      text,
+      lsp::Position {
+        line: 1,
+        character: 2,
+      },
+      "\n",
+      MediaType::TypeScript,
+    );
+    assert_eq!(
+      range,
+      lsp::Range {
+        start: lsp::Position {
+          line: 0,
+          character: 0,
+        },
+        end: lsp::Position {
+          line: 0,
+          character: 8,
+        },
+      }
+    );
+    let a = text;
+    let b = "if (a) {\n}\n";
+    let edits = get_edits_in_range(get_edits(a, b, &LineIndex::new(a)), &range);
+    assert!(edits.is_empty());
+  }
+
+  #[test]
+  fn test_get_on_type_format_range_skips_strings_and_comments() {
+    let text = concat!(
+      "function f() {\n",
+      "  const a = \"}\";\n",
+      "  // }\n",
+      "  if (b) {\n",
+      "    c(`${d}}`, '{');\n",
+      "    /* { */\n",
+      "  }\n",
+      "}\n",
+    );
+    let range = |line, character| {
+      let range = get_on_type_format_range(
+        text,
+        lsp::Position { line, character },
+        "}",
+        MediaType::TypeScript,
+      );
+      (range.start.line, range.end.line)
+    };
+    assert_eq!(range(6, 3), (3, 6));
+    assert_eq!(range(7, 1), (0, 7));
+  }
 }
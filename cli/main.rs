@@ -110,9 +110,10 @@ async fn run_subcommand(flags: Flags) -> Result<i32, AnyError> {
     DenoSubcommand::Check(check_flags) => spawn_subcommand(async move {
       let factory = CliFactory::from_flags(flags).await?;
       let module_load_preparer = factory.module_load_preparer().await?;
-      module_load_preparer
+      let result = module_load_preparer
         .load_and_type_check_files(&check_flags.files)
-        .await
+        .await;
+      tools::check::report_check_result(check_flags.reporter, result)
     }),
     DenoSubcommand::Compile(compile_flags) => spawn_subcommand(async {
       tools::compile::compile(flags, compile_flags).await
@@ -137,6 +138,9 @@ async fn run_subcommand(flags: Flags) -> Result<i32, AnyError> {
     DenoSubcommand::Uninstall(uninstall_flags) => spawn_subcommand(async {
       tools::installer::uninstall(uninstall_flags.name, uninstall_flags.root)
     }),
+    DenoSubcommand::Kv(kv_flags) => {
+      spawn_subcommand(async { tools::kv::kv_command(kv_flags).await })
+    }
     DenoSubcommand::Lsp => spawn_subcommand(async { lsp::start().await }),
     DenoSubcommand::Lint(lint_flags) => spawn_subcommand(async {
       if lint_flags.rules {
@@ -178,7 +182,12 @@ async fn run_subcommand(flags: Flags) -> Result<i32, AnyError> {
         if test_flags.watch.is_some() {
           tools::test::run_tests_with_watch(flags, test_flags).await
         } else {
-          tools::test::run_tests(flags, test_flags).await
+          let coverage_dir = test_flags.coverage_dir.clone();
+          tools::test::run_tests(flags.clone(), test_flags).await?;
+          if let Some(coverage_dir) = coverage_dir {
+            tools::coverage::check_test_coverage(flags, &coverage_dir).await?;
+          }
+          Ok(())
         }
       })
     }
@@ -299,6 +308,13 @@ pub fn main() {
 
     util::logger::init(flags.log_level);
 
+    // compiled binaries record their own permission checks instead
+    if let Some(audit_log_path) = &flags.audit_permissions {
+      if !matches!(flags.subcommand, DenoSubcommand::Compile(_)) {
+        deno_runtime::permissions::set_audit_log(audit_log_path)?;
+      }
+    }
+
     run_subcommand(flags).await
   };
 
@@ -34,16 +34,23 @@ deno_core::extension!(deno_bench,
     op_register_bench,
     op_dispatch_bench_event,
     op_bench_now,
+    op_bench_heap_used,
   ],
   options = {
     sender: UnboundedSender<BenchEvent>,
+    collect_memory: bool,
   },
   state = |state, options| {
     state.put(options.sender);
     state.put(BenchContainer::default());
+    state.put(CollectMemory(options.collect_memory));
   },
 );
 
+/// Whether `--memory` was passed, collecting memory statistics for every
+/// bench.
+struct CollectMemory(bool);
+
 #[derive(Clone)]
 struct PermissionsHolder(Uuid, PermissionsContainer);
 
@@ -103,6 +110,8 @@ struct BenchInfo<'s> {
   only: bool,
   #[serde(default)]
   warmup: bool,
+  #[serde(default)]
+  memory: bool,
 }
 
 #[derive(Debug, Serialize)]
@@ -110,6 +119,7 @@ struct BenchInfo<'s> {
 struct BenchRegisterResult {
   id: usize,
   origin: String,
+  memory: bool,
 }
 
 static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
@@ -122,6 +132,7 @@ fn op_register_bench<'a>(
 ) -> Result<BenchRegisterResult, AnyError> {
   let id = NEXT_ID.fetch_add(1, Ordering::SeqCst);
   let origin = state.borrow::<ModuleSpecifier>().to_string();
+  let memory = info.memory || state.borrow::<CollectMemory>().0;
   let description = BenchDescription {
     id,
     name: info.name,
@@ -140,7 +151,7 @@ fn op_register_bench<'a>(
     .push((description.clone(), function));
   let sender = state.borrow::<UnboundedSender<BenchEvent>>().clone();
   sender.send(BenchEvent::Register(description)).ok();
-  Ok(BenchRegisterResult { id, origin })
+  Ok(BenchRegisterResult { id, origin, memory })
 }
 
 #[op]
@@ -159,3 +170,13 @@ fn op_bench_now(state: &mut OpState) -> Result<u64, AnyError> {
   let ns_u64 = u64::try_from(ns)?;
   Ok(ns_u64)
 }
+
+/// The used V8 heap size in bytes. Cheaper than `op_runtime_memory_usage`,
+/// which also reads the RSS of the process, as it's called around every
+/// iteration of a bench that collects memory statistics.
+#[op(v8)]
+fn op_bench_heap_used(scope: &mut v8::HandleScope) -> usize {
+  let mut stats = v8::HeapStatistics::default();
+  scope.get_heap_statistics(&mut stats);
+  stats.used_heap_size()
+}
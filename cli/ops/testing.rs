@@ -28,6 +28,12 @@ pub(crate) struct TestContainer(
   pub Vec<(TestDescription, v8::Global<v8::Function>)>,
 );
 
+/// Step events of a test attempt that may still be retried. They are only
+/// sent once it is known that the attempt is the final one, so that the steps
+/// of discarded attempts are not reported.
+#[derive(Default)]
+pub(crate) struct TestStepEventBuffer(pub Option<Vec<TestEvent>>);
+
 deno_core::extension!(deno_test,
   ops = [
     op_pledge_test_permissions,
@@ -42,6 +48,7 @@ deno_core::extension!(deno_test,
   state = |state, options| {
     state.put(options.sender);
     state.put(TestContainer::default());
+    state.put(TestStepEventBuffer::default());
   },
 );
 
@@ -102,6 +109,8 @@ struct TestInfo<'s> {
   #[serde(default)]
   only: bool,
   location: TestLocation,
+  #[serde(default)]
+  retry: Option<usize>,
 }
 
 #[derive(Debug, Serialize)]
@@ -128,6 +137,7 @@ fn op_register_test<'a>(
     only: info.only,
     origin: origin.clone(),
     location: info.location,
+    retry: info.retry,
   };
   let function: v8::Local<v8::Function> = info.function.v8_value.try_into()?;
   let function = v8::Global::new(scope, function);
@@ -181,8 +191,7 @@ fn op_register_test_step(
     root_id: info.root_id,
     root_name: info.root_name,
   };
-  let mut sender = state.borrow::<TestEventSender>().clone();
-  sender.send(TestEvent::StepRegister(description)).ok();
+  send_step_event(state, TestEvent::StepRegister(description));
   Ok(TestRegisterResult { id, origin })
 }
 
@@ -195,7 +204,15 @@ fn op_dispatch_test_event(
     matches!(event, TestEvent::StepWait(_) | TestEvent::StepResult(..)),
     "Only step wait/result events are expected from JS."
   );
+  send_step_event(state, event);
+  Ok(())
+}
+
+fn send_step_event(state: &mut OpState, event: TestEvent) {
+  if let Some(buffer) = &mut state.borrow_mut::<TestStepEventBuffer>().0 {
+    buffer.push(event);
+    return;
+  }
   let mut sender = state.borrow::<TestEventSender>().clone();
   sender.send(event).ok();
-  Ok(())
 }
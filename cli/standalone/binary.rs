@@ -130,6 +130,8 @@ pub struct Metadata {
   pub unstable: bool,
   pub seed: Option<u64>,
   pub permissions: PermissionsOptions,
+  /// File that permission checks are recorded to, see `--audit-permissions`.
+  pub audit_permissions: Option<PathBuf>,
   pub location: Option<Url>,
   pub v8_flags: Vec<String>,
   pub log_level: Option<Level>,
@@ -518,6 +520,7 @@ impl<'a> DenoCompileBinaryWriter<'a> {
       seed: cli_options.seed(),
       location: cli_options.location_flag().clone(),
       permissions: cli_options.permissions_options(),
+      audit_permissions: cli_options.audit_permissions().cloned(),
       v8_flags: cli_options.v8_flags().clone(),
       unsafely_ignore_certificate_errors: cli_options
         .unsafely_ignore_certificate_errors()
@@ -287,6 +287,9 @@ pub async fn run(
   metadata: Metadata,
 ) -> Result<(), AnyError> {
   let main_module = &metadata.entrypoint;
+  if let Some(audit_log_path) = &metadata.audit_permissions {
+    deno_runtime::permissions::set_audit_log(audit_log_path)?;
+  }
   let current_exe_path = std::env::current_exe().unwrap();
   let current_exe_name =
     current_exe_path.file_name().unwrap().to_string_lossy();
@@ -439,14 +442,18 @@ pub async fn run(
     None,
     CliMainWorkerOptions {
       argv: metadata.argv,
+      broadcast_channel_db_path: None,
+      cache_storage_limits: Default::default(),
       log_level: WorkerLogLevel::Info,
       coverage_dir: None,
       enable_testing_features: false,
       has_node_modules_dir,
+      in_memory_cache_storage: false,
       inspect_brk: false,
       inspect_wait: false,
       is_inspecting: false,
       is_npm_main: main_module.scheme() == "npm",
+      local_storage_quota: None,
       location: metadata.location,
       maybe_binary_npm_command_name: NpmPackageReqReference::from_specifier(
         main_module,
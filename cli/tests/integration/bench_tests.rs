@@ -1,11 +1,14 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
 
+use deno_core::serde_json;
+use deno_core::serde_json::json;
 use deno_core::url::Url;
 use test_util as util;
 use util::assert_contains;
 use util::assert_not_contains;
 use util::env_vars_for_npm_tests;
 use util::TestContext;
+use util::TestContextBuilder;
 
 itest!(overloads {
   args: "bench bench/overloads.ts",
@@ -283,3 +286,100 @@ fn conditionally_loads_type_graph() {
     .run();
   assert_not_contains!(output.combined_output(), "type_reference.d.ts");
 }
+
+#[test]
+fn baseline_save_and_compare() {
+  let context = TestContextBuilder::new().use_temp_cwd().build();
+  let temp_dir = context.temp_dir();
+  temp_dir.write(
+    "sum_bench.ts",
+    r#"Deno.bench("sum", () => {
+  let sum = 0;
+  for (let i = 0; i < 1000; i++) sum += i;
+});
+"#,
+  );
+
+  let output = context
+    .new_command()
+    .args("bench --save-baseline=baseline.json sum_bench.ts")
+    .run();
+  output.assert_exit_code(0);
+  let baseline: serde_json::Value =
+    serde_json::from_str(&temp_dir.read_to_string("baseline.json")).unwrap();
+  assert_eq!(baseline["version"], 1);
+  assert_eq!(baseline["benches"][0]["origin"], "./sum_bench.ts");
+  assert_eq!(baseline["benches"][0]["name"], "sum");
+  assert!(baseline["benches"][0]["stats"]["stddev"].is_number());
+
+  let output = context
+    .new_command()
+    .args("bench --compare-baseline=baseline.json sum_bench.ts")
+    .split_output()
+    .run();
+  output.assert_exit_code(0);
+  assert_contains!(output.stderr(), "compared with baseline baseline.json");
+  assert_contains!(output.stderr(), "sum");
+
+  // A baseline that is impossibly fast makes the benchmark a regression.
+  let mut fast_baseline = baseline;
+  let stats = &mut fast_baseline["benches"][0]["stats"];
+  stats["avg"] = json!(0.001);
+  stats["stddev"] = json!(0.0);
+  temp_dir.write(
+    "fast_baseline.json",
+    serde_json::to_string(&fast_baseline).unwrap(),
+  );
+  let output = context
+    .new_command()
+    .args(
+      "bench --compare-baseline=fast_baseline.json --max-regression=10 sum_bench.ts",
+    )
+    .run();
+  output.assert_exit_code(1);
+  assert_contains!(
+    output.combined_output(),
+    "Bench failed because 1 benchmark regressed by more than 10%"
+  );
+}
+
+#[test]
+fn memory_stats() {
+  let context = TestContextBuilder::new().use_temp_cwd().build();
+  let temp_dir = context.temp_dir();
+  temp_dir.write(
+    "alloc_bench.ts",
+    r#"Deno.bench("alloc", () => {
+  new Array(1000).fill(0);
+});
+Deno.bench("noop", () => {});
+"#,
+  );
+
+  let output = context
+    .new_command()
+    .args("bench --memory --json alloc_bench.ts")
+    .split_output()
+    .run();
+  output.assert_exit_code(0);
+  let json: serde_json::Value = serde_json::from_str(output.stdout()).unwrap();
+  let benches = json["benches"].as_array().unwrap();
+  assert_eq!(benches.len(), 2);
+  for bench in benches {
+    let memory = &bench["results"][0]["ok"]["memory"];
+    assert!(memory["n"].as_u64().unwrap() > 0);
+    assert!(memory["heapDelta"].is_number());
+    assert!(memory["heapShrinks"].is_u64());
+  }
+
+  let output = context.new_command().args("bench alloc_bench.ts").run();
+  output.assert_exit_code(0);
+  assert_not_contains!(output.combined_output(), "heap/iter");
+
+  let output = context
+    .new_command()
+    .args("bench --memory alloc_bench.ts")
+    .run();
+  output.assert_exit_code(0);
+  assert_contains!(output.combined_output(), "heap/iter");
+}
@@ -1,6 +1,9 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
 
+use deno_core::serde_json;
+use deno_core::serde_json::json;
 use test_util as util;
+use util::assert_contains;
 use util::env_vars_for_npm_tests;
 use util::env_vars_for_npm_tests_no_sync_download;
 use util::TestContext;
@@ -396,3 +399,53 @@ fn npm_module_check_then_error() {
     .assert_matches_text("Check [WILDCARD]main.ts\nerror: TS2305[WILDCARD]has no exported member 'oldName'[WILDCARD]")
     .assert_exit_code(1);
 }
+
+#[test]
+fn check_reporter_github() {
+  let context = TestContextBuilder::new().use_temp_cwd().build();
+  let temp_dir = context.temp_dir();
+  temp_dir.write("main.ts", "const a: number = \"1\";\nconsole.log(a);\n");
+
+  let output = context
+    .new_command()
+    .args("check --reporter=github main.ts")
+    .split_output()
+    .run();
+  output.assert_exit_code(1);
+  assert_eq!(
+    output.stdout(),
+    "::error file=main.ts,line=1,endLine=1,col=7,endColumn=8,title=TS2322::Type 'string' is not assignable to type 'number'.\n"
+  );
+  // the regular output is still written to stderr
+  assert_contains!(output.stderr(), "error: TS2322");
+}
+
+#[test]
+fn check_reporter_sarif() {
+  let context = TestContextBuilder::new().use_temp_cwd().build();
+  let temp_dir = context.temp_dir();
+  temp_dir.write("main.ts", "const a: number = \"1\";\nconsole.log(a);\n");
+
+  let output = context
+    .new_command()
+    .args("check --reporter=sarif main.ts")
+    .split_output()
+    .run();
+  output.assert_exit_code(1);
+  let log: serde_json::Value = serde_json::from_str(output.stdout()).unwrap();
+  let result = &log["runs"][0]["results"][0];
+  assert_eq!(result["ruleId"], "TS2322");
+  assert_eq!(result["level"], "error");
+  assert_eq!(
+    result["locations"][0]["physicalLocation"],
+    json!({
+      "artifactLocation": { "uri": "main.ts" },
+      "region": {
+        "startLine": 1,
+        "startColumn": 7,
+        "endLine": 1,
+        "endColumn": 8,
+      },
+    })
+  );
+}
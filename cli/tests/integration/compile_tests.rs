@@ -1124,3 +1124,50 @@ fn compile_node_modules_symlink_outside() {
   let output = context.new_command().command_name(binary_path).run();
   output.assert_matches_file("compile/node_modules_symlink_outside/main.out");
 }
+
+#[test]
+fn compile_audit_permissions() {
+  let context = TestContextBuilder::new().use_temp_cwd().build();
+  let temp_dir = context.temp_dir();
+  temp_dir.write("data.txt", "data");
+  temp_dir.write("main.ts", "Deno.readTextFileSync('data.txt');\n");
+  let exe = if cfg!(windows) {
+    temp_dir.path().join("main.exe")
+  } else {
+    temp_dir.path().join("main")
+  };
+  context
+    .new_command()
+    .args_vec([
+      "compile",
+      "--allow-read",
+      "--audit-permissions=audit.jsonl",
+      "--output",
+      &exe.to_string_lossy(),
+      "main.ts",
+    ])
+    .run()
+    .skip_output_check()
+    .assert_exit_code(0);
+  // the checks are recorded when the binary runs, not while compiling
+  assert!(!temp_dir.path().join("audit.jsonl").exists());
+
+  context
+    .new_command()
+    .command_name(&exe)
+    .run()
+    .skip_output_check()
+    .assert_exit_code(0);
+  let audit_log = temp_dir.read_to_string("audit.jsonl");
+  let entry = audit_log
+    .lines()
+    .map(|line| {
+      deno_core::serde_json::from_str::<deno_core::serde_json::Value>(line)
+        .unwrap()
+    })
+    .find(|entry| entry["apiName"] == "Deno.readFileSync()")
+    .unwrap();
+  assert_eq!(entry["kind"], "read");
+  assert_eq!(entry["result"], "granted");
+  assert_contains!(entry["stack"].as_str().unwrap(), "main.ts:1:");
+}
@@ -1,8 +1,11 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
 
+use deno_core::serde_json;
+use deno_core::serde_json::json;
 use std::fs;
 use test_util as util;
 use test_util::TempDir;
+use util::assert_contains;
 use util::env_vars_for_npm_tests;
 use util::TestContext;
 use util::TestContextBuilder;
@@ -229,6 +232,192 @@ fn multifile_coverage() {
   output.assert_exit_code(0);
 }
 
+#[test]
+fn html_coverage() {
+  let context = TestContext::default();
+  let tempdir = context.temp_dir();
+  let tempdir = tempdir.path().join("cov");
+  let html_dir = context.temp_dir().path().join("html");
+
+  let output = context
+    .new_command()
+    .args_vec(vec![
+      "test".to_string(),
+      "--quiet".to_string(),
+      format!("--coverage={}", tempdir),
+      format!("coverage/multifile/"),
+    ])
+    .run();
+
+  output.assert_exit_code(0);
+  output.skip_output_check();
+
+  let output = context
+    .new_command()
+    .args_vec(vec![
+      "coverage".to_string(),
+      "--html".to_string(),
+      format!("--output={}", html_dir),
+      format!("{}/", tempdir),
+    ])
+    .run();
+
+  output.assert_exit_code(0);
+  assert_contains!(
+    output.combined_output(),
+    "HTML coverage report has been generated at"
+  );
+
+  let index = html_dir.join("index.html").read_to_string();
+  assert_contains!(index, "<a href=\"mod.js.html\">mod.js</a>");
+  let page = html_dir.join("mod.js.html").read_to_string();
+  assert_contains!(page, "if (a) {");
+  assert_contains!(page, "<tr class=\"hit\">");
+  assert_contains!(page, "<td class=\"line-branches\">1/1</td>");
+}
+
+#[test]
+fn cobertura_and_json_summary_coverage() {
+  let context = TestContext::default();
+  let tempdir = context.temp_dir();
+  let tempdir = tempdir.path().join("cov");
+
+  let output = context
+    .new_command()
+    .args_vec(vec![
+      "test".to_string(),
+      "--quiet".to_string(),
+      format!("--coverage={}", tempdir),
+      format!("coverage/multifile/"),
+    ])
+    .run();
+
+  output.assert_exit_code(0);
+  output.skip_output_check();
+
+  let output = context
+    .new_command()
+    .args_vec(vec![
+      "coverage".to_string(),
+      "--cobertura".to_string(),
+      format!("{}/", tempdir),
+    ])
+    .split_output()
+    .run();
+
+  output.assert_exit_code(0);
+  let xml = output.stdout();
+  assert_contains!(xml, "<coverage lines-valid=\"7\" lines-covered=\"7\"");
+  assert_contains!(xml, "<class name=\"mod.js\" filename=\"mod.js\"");
+  assert_contains!(
+    xml,
+    "<line number=\"2\" hits=\"4\" branch=\"true\" condition-coverage=\"100% (1/1)\"/>"
+  );
+
+  let output = context
+    .new_command()
+    .args_vec(vec![
+      "coverage".to_string(),
+      "--json".to_string(),
+      format!("{}/", tempdir),
+    ])
+    .split_output()
+    .run();
+
+  output.assert_exit_code(0);
+  let summary: serde_json::Value =
+    serde_json::from_str(output.stdout()).unwrap();
+  assert_eq!(
+    summary["total"],
+    json!({
+      "lines": { "found": 7, "hit": 7, "percent": 100.0 },
+      "branches": { "found": 1, "hit": 1, "percent": 100.0 },
+      "functions": { "found": 1, "hit": 1, "percent": 100.0 },
+    })
+  );
+  let files = summary["files"].as_object().unwrap();
+  assert_eq!(files.len(), 1);
+  assert!(files.keys().next().unwrap().ends_with("multifile/mod.js"));
+}
+
+#[test]
+fn coverage_thresholds() {
+  let context = TestContextBuilder::new().use_temp_cwd().build();
+  let temp_dir = context.temp_dir();
+  temp_dir.write(
+    "deno.json",
+    r#"{
+  "coverage": {
+    "thresholds": { "lines": 100 },
+    "overrides": [
+      { "include": ["legacy/"], "thresholds": { "lines": 10 } }
+    ]
+  }
+}"#,
+  );
+  let module = r#"export function sign(n) {
+  if (n < 0) {
+    return -1;
+  }
+  return 1;
+}
+"#;
+  temp_dir.create_dir_all("legacy");
+  temp_dir.write("mod.js", module);
+  temp_dir.write("legacy/mod.js", module);
+  temp_dir.write(
+    "mod_test.js",
+    r#"import { sign } from "./mod.js";
+import { sign as legacySign } from "./legacy/mod.js";
+Deno.test("sign", () => {
+  sign(1);
+  legacySign(1);
+});
+"#,
+  );
+
+  let output = context
+    .new_command()
+    .args("test --quiet --coverage=cov")
+    .run();
+  output.assert_exit_code(1);
+  let out = util::strip_ansi_codes(output.combined_output()).to_string();
+  assert_contains!(out, "Coverage thresholds were not met:");
+  // the global thresholds apply to the totals of the files that no override
+  // matches
+  assert_contains!(out, "All files: lines ");
+  assert_contains!(out, "is below 100%");
+  assert!(!out.contains("legacy/mod.js"));
+
+  let output = context
+    .new_command()
+    .args("coverage --quiet --lcov cov")
+    .run();
+  output.assert_exit_code(1);
+  let out = util::strip_ansi_codes(output.combined_output()).to_string();
+  assert_contains!(out, "Coverage thresholds were not met:");
+
+  // files that an override matches are checked one by one
+  temp_dir.write(
+    "deno.json",
+    r#"{
+  "coverage": {
+    "overrides": [
+      { "include": ["legacy/"], "thresholds": { "lines": 100 } }
+    ]
+  }
+}"#,
+  );
+  let output = context
+    .new_command()
+    .args("coverage --quiet --lcov cov")
+    .run();
+  output.assert_exit_code(1);
+  let out = util::strip_ansi_codes(output.combined_output()).to_string();
+  assert_contains!(out, "legacy/mod.js: lines ");
+  assert!(!out.contains("All files"));
+}
+
 fn no_snaps_included(test_name: &str, extension: &str) {
   let context = TestContext::default();
   let tempdir = context.temp_dir();
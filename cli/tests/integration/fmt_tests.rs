@@ -207,6 +207,46 @@ itest!(fmt_stdin_json {
   output_str: Some("{ \"key\": \"value\" }\n"),
 });
 
+itest!(fmt_stdin_css {
+  args: "fmt --unstable-css --ext=css -",
+  input: Some("a{color:red}"),
+  output_str: Some("a {\n  color: red;\n}\n"),
+});
+
+itest!(fmt_stdin_yaml {
+  args: "fmt --unstable-yaml --ext=yaml -",
+  input: Some("key:    value\nlist:\n    - a\n"),
+  output_str: Some("key: value\nlist:\n  - a\n"),
+});
+
+itest!(fmt_stdin_markdown_css {
+  args: "fmt --unstable-css --ext=md -",
+  input: Some("```css\na{color:red}\n```\n"),
+  output_str: Some("```css\na {\n  color: red;\n}\n```\n"),
+});
+
+itest!(fmt_stdin_markdown_css_not_enabled {
+  args: "fmt --ext=md -",
+  input: Some("```css\na{color:red}\n```\n"),
+  output_str: Some("```css\na{color:red}\n```\n"),
+});
+
+#[test]
+fn fmt_stdin_css_not_enabled() {
+  let context = TestContext::default();
+  let output = context
+    .new_command()
+    .env("NO_COLOR", "1")
+    .args("fmt --ext=css -")
+    .stdin("a{color:red}")
+    .run();
+  output.assert_exit_code(1);
+  assert_contains!(
+    output.combined_output(),
+    "Formatting CSS files is unstable. Enable it with --unstable-css"
+  );
+}
+
 itest!(fmt_stdin_check_formatted {
   args: "fmt --check -",
   input: Some("const a = 1;\n"),
@@ -342,3 +382,57 @@ fn fmt_with_glob_config_and_flags() {
 
   assert_contains!(output, "Found 2 not formatted files in 2 files");
 }
+
+#[test]
+fn fmt_css_html_yaml() {
+  let context = TestContextBuilder::new().use_temp_cwd().build();
+  let temp_dir = context.temp_dir();
+  temp_dir.write("deno.json", "{ \"fmt\": { \"indentWidth\": 4 } }\n");
+  temp_dir.write("style.scss", "a{b{color:red}}");
+  temp_dir.write(
+    "index.html",
+    "<html><body><style>a{color:red}</style></body></html>",
+  );
+  temp_dir.write("ci.yml", "on:    push\n");
+
+  // the new languages are only formatted when they are enabled
+  let output = context.new_command().args("fmt --check").run();
+  output.assert_exit_code(0);
+  assert_contains!(output.combined_output(), "Checked 1 file");
+
+  temp_dir.write(
+    "deno.json",
+    concat!(
+      "{\n",
+      "    \"fmt\": { \"indentWidth\": 4 },\n",
+      "    \"unstable\": [\"fmt-css\", \"fmt-html\", \"fmt-yaml\"]\n",
+      "}\n",
+    ),
+  );
+  let output = context.new_command().args("fmt --check").run();
+  output.assert_exit_code(1);
+  assert_contains!(
+    output.combined_output(),
+    "Found 3 not formatted files in 4 files"
+  );
+
+  context
+    .new_command()
+    .args("fmt")
+    .run()
+    .assert_exit_code(0)
+    .skip_output_check();
+  assert_eq!(
+    temp_dir.read_to_string("style.scss"),
+    "a {\n    b {\n        color: red;\n    }\n}\n"
+  );
+  assert_eq!(temp_dir.read_to_string("ci.yml"), "on: push\n");
+  assert_contains!(temp_dir.read_to_string("index.html"), "color: red;");
+
+  context
+    .new_command()
+    .args("fmt --check")
+    .run()
+    .assert_exit_code(0)
+    .skip_output_check();
+}
@@ -0,0 +1,101 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+
+use test_util as util;
+use util::assert_contains;
+use util::TestContextBuilder;
+
+#[test]
+fn kv_export_import_roundtrip() {
+  let context = TestContextBuilder::new().use_temp_cwd().build();
+  let temp_dir = context.temp_dir();
+  temp_dir.write(
+    "seed.ts",
+    r#"const db = await Deno.openKv("./source.sqlite3");
+await db.set(["users", 1n], { name: "alice" });
+await db.set(["bytes"], new Uint8Array([1, 2, 3]));
+await db.set(["counter"], new Deno.KvU64(42n));
+await db.set(["temporary"], "soon gone", { expireIn: 60 * 60 * 1000 });
+db.close();
+"#,
+  );
+  temp_dir.write(
+    "check.ts",
+    r#"const db = await Deno.openKv("./target.sqlite3");
+for await (const entry of db.list({ prefix: [] })) {
+  console.log(Deno.inspect(entry.key), Deno.inspect(entry.value));
+}
+db.close();
+"#,
+  );
+
+  let output = context
+    .new_command()
+    .args("run --unstable -A seed.ts")
+    .run();
+  output.assert_exit_code(0);
+  output.skip_output_check();
+
+  let output = context
+    .new_command()
+    .args("kv export ./source.sqlite3 --output backup.ndjson")
+    .split_output()
+    .run();
+  output.assert_exit_code(0);
+  assert_contains!(output.stderr(), "Exported 4 entries");
+  let backup = temp_dir.read_to_string("backup.ndjson");
+  assert_eq!(backup.lines().count(), 4);
+  assert_contains!(backup, "\"expireAt\":");
+
+  let output = context
+    .new_command()
+    .args("kv import ./target.sqlite3 backup.ndjson")
+    .split_output()
+    .run();
+  output.assert_exit_code(0);
+  assert_contains!(output.stderr(), "Imported 4 entries");
+
+  let output = context
+    .new_command()
+    .env("NO_COLOR", "1")
+    .args("run --unstable -A check.ts")
+    .split_output()
+    .run();
+  output.assert_exit_code(0);
+  assert_eq!(
+    output.stdout(),
+    concat!(
+      "[ \"bytes\" ] Uint8Array(3) [ 1, 2, 3 ]\n",
+      "[ \"counter\" ] [Deno.KvU64: 42n]\n",
+      "[ \"temporary\" ] \"soon gone\"\n",
+      "[ \"users\", 1n ] { name: \"alice\" }\n",
+    )
+  );
+}
+
+#[test]
+fn kv_import_invalid_entry() {
+  let context = TestContextBuilder::new().use_temp_cwd().build();
+  let output = context
+    .new_command()
+    .args("kv import ./target.sqlite3")
+    .stdin("not json\n")
+    .split_output()
+    .run();
+  output.assert_exit_code(1);
+  assert_contains!(output.stderr(), "Invalid entry on line 1");
+}
+
+#[test]
+fn kv_export_remote_database() {
+  let context = TestContextBuilder::new().use_temp_cwd().build();
+  let output = context
+    .new_command()
+    .args("kv export https://example.com/my-db")
+    .split_output()
+    .run();
+  output.assert_exit_code(1);
+  assert_contains!(
+    output.stderr(),
+    "Exporting remote databases is not supported"
+  );
+}
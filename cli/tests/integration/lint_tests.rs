@@ -1,5 +1,7 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
 
+use deno_core::serde_json;
+use deno_core::serde_json::json;
 use test_util::assert_contains;
 use test_util::TestContextBuilder;
 
@@ -209,3 +211,127 @@ fn lint_with_glob_config_and_flags() {
   assert_contains!(output, "Found 2 problems");
   assert_contains!(output, "Checked 2 files");
 }
+
+#[test]
+fn lint_fix() {
+  let context = TestContextBuilder::new().use_temp_cwd().build();
+  let temp_dir = context.temp_dir();
+  temp_dir.write(
+    "main.ts",
+    "let a = 1;;\nlet b = 2;\nb++;\nconsole.log(a, b);\nvar c = 3;\nconsole.log(c);\n",
+  );
+
+  let output = context.new_command().args("lint --fix main.ts").run();
+  // `no-var` has no fix, so it is still reported
+  output.assert_exit_code(1);
+  let output = output.combined_output();
+  assert_contains!(output, "(no-var)");
+  assert_contains!(output, "Found 1 problem");
+  assert_eq!(
+    temp_dir.read_to_string("main.ts"),
+    "const a = 1;\nlet b = 2;\nb++;\nconsole.log(a, b);\nvar c = 3;\nconsole.log(c);\n",
+  );
+}
+
+itest!(fix_stdin {
+  args: "lint --fix -",
+  input: Some("let a = 1;\nconsole.log(a);\n"),
+  output_str: Some("error: Lint fix on standard input is not supported.\n"),
+  exit_code: 1,
+});
+
+#[test]
+fn lint_reporter_sarif() {
+  let context = TestContextBuilder::new().use_temp_cwd().build();
+  let temp_dir = context.temp_dir();
+  temp_dir.write("main.ts", "var a = 1;\nconsole.log(a);\n");
+
+  let output = context
+    .new_command()
+    .args("lint --reporter=sarif main.ts")
+    .split_output()
+    .run();
+  output.assert_exit_code(1);
+  let log: serde_json::Value = serde_json::from_str(output.stdout()).unwrap();
+  assert_eq!(log["version"], "2.1.0");
+  let run = &log["runs"][0];
+  assert_eq!(run["tool"]["driver"]["name"], "deno lint");
+  assert_eq!(
+    run["tool"]["driver"]["rules"],
+    json!([{ "id": "no-var", "helpUri": "https://lint.deno.land/#no-var" }])
+  );
+  let result = &run["results"][0];
+  assert_eq!(result["ruleId"], "no-var");
+  assert_eq!(
+    result["locations"][0]["physicalLocation"],
+    json!({
+      "artifactLocation": { "uri": "main.ts" },
+      "region": {
+        "startLine": 1,
+        "startColumn": 1,
+        "endLine": 1,
+        "endColumn": 11,
+      },
+    })
+  );
+}
+
+#[test]
+fn lint_reporter_github() {
+  let context = TestContextBuilder::new().use_temp_cwd().build();
+  let temp_dir = context.temp_dir();
+  temp_dir.write("main.ts", "var a = 1;\nconsole.log(a);\n");
+
+  let output = context
+    .new_command()
+    .args("lint --reporter=github main.ts")
+    .split_output()
+    .run();
+  output.assert_exit_code(1);
+  assert_contains!(
+    output.stdout(),
+    "::error file=main.ts,line=1,endLine=1,col=1,endColumn=11,title=no-var::"
+  );
+  assert_contains!(output.stderr(), "Found 1 problem");
+}
+
+#[test]
+fn lint_plugins() {
+  let context = TestContextBuilder::new().use_temp_cwd().build();
+  let temp_dir = context.temp_dir();
+  temp_dir.write("deno.json", r#"{ "lintPlugins": ["./plugin.ts"] }"#);
+  temp_dir.write(
+    "plugin.ts",
+    r#"interface Context {
+  imports: { specifier: string; range: [number, number] }[];
+  report(diagnostic: { range: [number, number]; message: string }): void;
+}
+
+export default {
+  name: "acme",
+  rules: {
+    "no-lodash": {
+      lint(context: Context) {
+        for (const { specifier, range } of context.imports) {
+          if (specifier === "npm:lodash") {
+            context.report({ range, message: "Use the standard library." });
+          }
+        }
+      },
+    },
+  },
+};
+"#,
+  );
+  temp_dir.write(
+    "main.ts",
+    "import _ from \"npm:lodash\";\n// deno-lint-ignore acme/no-lodash\nimport lodash from \"npm:lodash\";\nconsole.log(_, lodash);\n",
+  );
+
+  let output = context.new_command().args("lint main.ts").run();
+  output.assert_exit_code(1);
+  let output = output.combined_output();
+  assert_contains!(output, "(acme/no-lodash) Use the standard library.");
+  assert_contains!(output, "main.ts:1:15");
+  assert_contains!(output, "Found 1 problem");
+}
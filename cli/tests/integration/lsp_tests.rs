@@ -733,7 +733,15 @@ fn lsp_deno_task() {
     r#"{
     "tasks": {
       "build": "deno test",
-      "some:test": "deno bundle mod.ts"
+      "some:test": "deno bundle mod.ts",
+      "lint": {
+        "command": "deno lint",
+        "description": "Lints the project"
+      },
+      "ci": {
+        "description": "Runs the checks",
+        "dependencies": ["lint", "build"]
+      }
     }
   }"#,
   );
@@ -754,6 +762,12 @@ fn lsp_deno_task() {
       }, {
         "name": "some:test",
         "detail": "deno bundle mod.ts"
+      }, {
+        "name": "lint",
+        "detail": "deno lint - Lints the project"
+      }, {
+        "name": "ci",
+        "detail": "Runs the checks"
       }
     ])
   );
@@ -7313,6 +7327,135 @@ fn lsp_format_mbc() {
   client.shutdown();
 }
 
+#[test]
+fn lsp_format_range() {
+  let context = TestContextBuilder::new().use_temp_cwd().build();
+  let mut client = context.new_lsp_command().build();
+  client.initialize_default();
+  client.did_open(json!({
+    "textDocument": {
+      "uri": "file:///a/file.ts",
+      "languageId": "typescript",
+      "version": 1,
+      "text": "const a = 'a'\nconsole.log( a );\n"
+    }
+  }));
+  let res = client.write_request(
+    "textDocument/rangeFormatting",
+    json!({
+      "textDocument": {
+        "uri": "file:///a/file.ts"
+      },
+      "range": {
+        "start": { "line": 1, "character": 0 },
+        "end": { "line": 2, "character": 0 }
+      },
+      "options": {
+        "tabSize": 2,
+        "insertSpaces": true
+      }
+    }),
+  );
+  assert_eq!(
+    res,
+    json!([{
+      "range": {
+        "start": { "line": 1, "character": 12 },
+        "end": { "line": 1, "character": 13 }
+      },
+      "newText": ""
+    }, {
+      "range": {
+        "start": { "line": 1, "character": 14 },
+        "end": { "line": 1, "character": 15 }
+      },
+      "newText": ""
+    }])
+  );
+  client.shutdown();
+}
+
+#[test]
+fn lsp_format_on_type() {
+  let context = TestContextBuilder::new().use_temp_cwd().build();
+  let mut client = context.new_lsp_command().build();
+  client.initialize_default();
+  client.did_open(json!({
+    "textDocument": {
+      "uri": "file:///a/file.ts",
+      "languageId": "typescript",
+      "version": 1,
+      "text": "const a = 'a'\nfunction f() {\nconsole.log( a );\n}\nf( );\n"
+    }
+  }));
+  let res = client.write_request(
+    "textDocument/onTypeFormatting",
+    json!({
+      "textDocument": {
+        "uri": "file:///a/file.ts"
+      },
+      "position": { "line": 3, "character": 1 },
+      "ch": "}",
+      "options": {
+        "tabSize": 2,
+        "insertSpaces": true
+      }
+    }),
+  );
+  // only the block closed by the brace is formatted
+  assert_eq!(
+    res,
+    json!([{
+      "range": {
+        "start": { "line": 2, "character": 0 },
+        "end": { "line": 2, "character": 0 }
+      },
+      "newText": "  "
+    }, {
+      "range": {
+        "start": { "line": 2, "character": 12 },
+        "end": { "line": 2, "character": 13 }
+      },
+      "newText": ""
+    }, {
+      "range": {
+        "start": { "line": 2, "character": 14 },
+        "end": { "line": 2, "character": 15 }
+      },
+      "newText": ""
+    }])
+  );
+
+  // incomplete code is left alone without showing an error
+  client.write_notification(
+    "textDocument/didChange",
+    json!({
+      "textDocument": {
+        "uri": "file:///a/file.ts",
+        "version": 2
+      },
+      "contentChanges": [{ "text": "function f() {\nif (;\n" }]
+    }),
+  );
+  let res = client.write_request(
+    "textDocument/onTypeFormatting",
+    json!({
+      "textDocument": {
+        "uri": "file:///a/file.ts"
+      },
+      "position": { "line": 1, "character": 5 },
+      "ch": ";",
+      "options": {
+        "tabSize": 2,
+        "insertSpaces": true
+      }
+    }),
+  );
+  assert_eq!(res, json!(null));
+  client.assert_no_notification("window/showMessage");
+  client.shutdown();
+}
+
 #[test]
 fn lsp_format_exclude_with_config() {
   let context = TestContextBuilder::new().use_temp_cwd().build();
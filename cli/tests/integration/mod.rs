@@ -121,6 +121,8 @@ mod inspector;
 mod install;
 #[path = "js_unit_tests.rs"]
 mod js_unit_tests;
+#[path = "kv_tests.rs"]
+mod kv;
 #[path = "lint_tests.rs"]
 mod lint;
 #[path = "lsp_tests.rs"]
@@ -4,6 +4,7 @@ use deno_core::serde_json::json;
 use deno_core::url;
 use deno_runtime::deno_fetch::reqwest;
 use pretty_assertions::assert_eq;
+use std::io::BufRead;
 use std::io::Read;
 use std::io::Write;
 use std::process::Command;
@@ -362,6 +363,82 @@ fn webstorage_location_shares_origin() {
   assert_eq!(output.stdout, b"Storage { length: 1, hello: \"deno\" }\n");
 }
 
+#[test]
+fn broadcast_channel_sqlite_backend() {
+  let deno_dir = util::new_deno_dir();
+  let run_args = [
+    "run",
+    "--unstable",
+    "--broadcast-channel-backend=sqlite",
+    "--location=https://example.com",
+  ];
+
+  let mut listener = util::deno_cmd_with_deno_dir(&deno_dir)
+    .current_dir(util::testdata_path())
+    .args(run_args)
+    .arg("run/broadcast_channel/listener.ts")
+    .stdout(Stdio::piped())
+    .spawn()
+    .unwrap();
+  let mut stdout =
+    std::io::BufReader::new(listener.stdout.take().unwrap()).lines();
+  assert_eq!(stdout.next().unwrap().unwrap(), "ready");
+
+  let output = util::deno_cmd_with_deno_dir(&deno_dir)
+    .current_dir(util::testdata_path())
+    .args(run_args)
+    .arg("run/broadcast_channel/sender.ts")
+    .spawn()
+    .unwrap()
+    .wait_with_output()
+    .unwrap();
+  assert!(output.status.success());
+
+  assert_eq!(
+    stdout.next().unwrap().unwrap(),
+    "hello from another process"
+  );
+  assert!(listener.wait().unwrap().success());
+}
+
+itest!(webstorage_quota {
+  args: "run --location https://quota.example.com --local-storage-quota=1 run/webstorage/quota.ts",
+  output: "run/webstorage/quota.ts.out",
+});
+
+// tests that changes to localStorage are dispatched as `storage` events to
+// other processes sharing the same origin.
+#[test]
+fn webstorage_storage_event() {
+  let deno_dir = util::new_deno_dir();
+  let run_args = ["run", "--location=https://example.com"];
+
+  let mut listener = util::deno_cmd_with_deno_dir(&deno_dir)
+    .current_dir(util::testdata_path())
+    .args(run_args)
+    .arg("run/webstorage/storage_event_listener.ts")
+    .stdout(Stdio::piped())
+    .spawn()
+    .unwrap();
+  let mut stdout =
+    std::io::BufReader::new(listener.stdout.take().unwrap()).lines();
+  assert_eq!(stdout.next().unwrap().unwrap(), "ready");
+
+  let output = util::deno_cmd_with_deno_dir(&deno_dir)
+    .current_dir(util::testdata_path())
+    .args(run_args)
+    .arg("run/webstorage/storage_event_sender.ts")
+    .spawn()
+    .unwrap()
+    .wait_with_output()
+    .unwrap();
+  assert!(output.status.success());
+
+  assert_eq!(stdout.next().unwrap().unwrap(), "theme: null -> dark");
+  assert_eq!(stdout.next().unwrap().unwrap(), "true");
+  assert!(listener.wait().unwrap().success());
+}
+
 // test to ensure that when a --config file is set, but no --location, that
 // storage persists against unique configuration files.
 #[test]
@@ -3657,6 +3734,19 @@ itest!(node_env_var_allowlist {
   exit_code: 1,
 });
 
+// the in-memory Cache API backend doesn't need an origin and doesn't persist
+// anything between runs.
+itest!(cache_api_memory_backend {
+  args: "run --cache-storage-backend=memory run/cache_api_memory_backend.ts",
+  output: "run/cache_api_memory_backend.ts.out",
+});
+
+// the least recently used responses are evicted once a cache is over quota.
+itest!(cache_api_quota {
+  args: "run --unstable --cache-quota=1 run/cache_api_quota.ts",
+  output: "run/cache_api_quota.ts.out",
+});
+
 #[test]
 fn cache_test() {
   let _g = util::http_server();
@@ -4694,3 +4784,49 @@ console.log(returnsHi());"#,
 ")
     .assert_exit_code(1);
 }
+
+#[test]
+fn run_audit_permissions() {
+  let context = TestContextBuilder::new().use_temp_cwd().build();
+  let temp_dir = context.temp_dir();
+  temp_dir.write("data.txt", "data");
+  temp_dir.write(
+    "main.ts",
+    "Deno.readTextFileSync('data.txt');\ntry { Deno.env.get('HOME'); } catch {}\n",
+  );
+
+  context
+    .new_command()
+    .args(
+      "run --no-prompt --allow-read --audit-permissions=audit.jsonl main.ts",
+    )
+    .run()
+    .assert_exit_code(0)
+    .skip_output_check();
+
+  let entries = temp_dir
+    .read_to_string("audit.jsonl")
+    .lines()
+    .map(|line| {
+      deno_core::serde_json::from_str::<deno_core::serde_json::Value>(line)
+        .unwrap()
+    })
+    .collect::<Vec<_>>();
+  let read_entry = entries
+    .iter()
+    .find(|entry| entry["apiName"] == "Deno.readFileSync()")
+    .unwrap();
+  assert_eq!(read_entry["kind"], "read");
+  assert!(read_entry["descriptor"]
+    .as_str()
+    .unwrap()
+    .ends_with("data.txt"));
+  assert_eq!(read_entry["result"], "granted");
+  assert_eq!(read_entry["prompted"], false);
+  assert_contains!(read_entry["stack"].as_str().unwrap(), "main.ts:1:");
+
+  let env_entry = entries.iter().find(|entry| entry["kind"] == "env").unwrap();
+  assert_eq!(env_entry["descriptor"], "HOME");
+  assert_eq!(env_entry["result"], "denied");
+  assert_contains!(env_entry["stack"].as_str().unwrap(), "main.ts:2:");
+}
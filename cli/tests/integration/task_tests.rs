@@ -5,6 +5,7 @@
 
 use test_util::env_vars_for_npm_tests;
 use test_util::TestContext;
+use test_util::TestContextBuilder;
 
 itest!(task_no_args {
   args: "task -q --config task/deno_json/deno.json",
@@ -287,3 +288,95 @@ itest!(task_deno_no_pre_post {
   exit_code: 0,
   envs: vec![("NO_COLOR".to_string(), "1".to_string())],
 });
+
+itest!(task_dependencies_no_args {
+  args: "task -q --config task/dependencies/deno.json",
+  output: "task/dependencies/task_no_args.out",
+  envs: vec![("NO_COLOR".to_string(), "1".to_string())],
+  exit_code: 1,
+});
+
+itest!(task_dependencies_cycle {
+  args: "task -q --config task/dependencies/deno.json cycle1",
+  output: "task/dependencies/task_cycle.out",
+  envs: vec![("NO_COLOR".to_string(), "1".to_string())],
+  exit_code: 1,
+});
+
+itest!(task_dependencies_unknown {
+  args: "task -q --config task/dependencies/deno.json unknown",
+  output: "task/dependencies/task_unknown.out",
+  envs: vec![("NO_COLOR".to_string(), "1".to_string())],
+  exit_code: 1,
+});
+
+itest!(task_dependencies_fail {
+  args: "task -q --config task/dependencies/deno.json after_fail",
+  output_str: Some(""),
+  envs: vec![("NO_COLOR".to_string(), "1".to_string())],
+  exit_code: 3,
+});
+
+#[test]
+fn task_dependencies_parallel() {
+  let output = TestContext::default()
+    .new_command()
+    .args("task -q --config task/dependencies/deno.json all")
+    .env("NO_COLOR", "1")
+    .run();
+  output.assert_exit_code(0);
+  let lines = output.combined_output().lines().collect::<Vec<_>>();
+  // `a` runs first, then `b` and `c` run in parallel
+  assert_eq!(lines.len(), 3);
+  assert_eq!(lines[0], "[a] a");
+  let mut rest = lines[1..].to_vec();
+  rest.sort();
+  assert_eq!(rest, vec!["[b] b", "[c] c"]);
+}
+
+#[test]
+fn task_skips_unchanged_inputs() {
+  let context = TestContextBuilder::new().use_temp_cwd().build();
+  let temp_dir = context.temp_dir();
+  temp_dir.write(
+    "deno.json",
+    r#"{
+  "tasks": {
+    "build": {
+      "command": "echo built > out.txt",
+      "inputs": ["src"],
+      "outputs": ["out.txt"]
+    }
+  }
+}
+"#,
+  );
+  temp_dir.create_dir_all("src");
+  temp_dir.write("src/a.txt", "a");
+
+  let run_build = || {
+    let output = context
+      .new_command()
+      .args("task build")
+      .env("NO_COLOR", "1")
+      .run();
+    output.assert_exit_code(0);
+    output
+      .combined_output()
+      .contains("skipped, inputs unchanged")
+  };
+
+  assert!(!run_build());
+  assert_eq!(temp_dir.read_to_string("out.txt"), "built\n");
+  assert!(run_build());
+
+  // changing an input runs the task again
+  temp_dir.write("src/a.txt", "b");
+  assert!(!run_build());
+  assert!(run_build());
+
+  // so does removing an output
+  temp_dir.remove_file("out.txt");
+  assert!(!run_build());
+  assert!(temp_dir.path().join("out.txt").exists());
+}
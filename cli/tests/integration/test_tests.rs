@@ -1,5 +1,6 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
 
+use deno_core::serde_json;
 use deno_core::url::Url;
 use test_util as util;
 use util::assert_contains;
@@ -187,6 +188,18 @@ itest!(fail_fast {
   output: "test/fail_fast.out",
 });
 
+itest!(retry {
+  args: "test --retry=1 test/retry.ts",
+  exit_code: 1,
+  output: "test/retry.out",
+});
+
+itest!(retry_steps {
+  args: "test --retry=1 test/retry_steps.ts",
+  exit_code: 0,
+  output: "test/retry_steps.out",
+});
+
 itest!(only {
   args: "test test/only.ts",
   exit_code: 1,
@@ -640,3 +653,78 @@ fn conditionally_loads_type_graph() {
     .run();
   assert_not_contains!(output.combined_output(), "type_reference.d.ts");
 }
+
+#[test]
+fn json_reporter() {
+  let context = TestContext::default();
+  let output = context
+    .new_command()
+    .args("test --reporter=json test/steps/failing_steps.ts")
+    .split_output()
+    .run();
+  output.assert_exit_code(1);
+
+  let events = output
+    .stdout()
+    .lines()
+    .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+    .collect::<Vec<_>>();
+  let kinds = events
+    .iter()
+    .map(|event| event["type"].as_str().unwrap())
+    .collect::<Vec<_>>();
+  // tests are registered while the module is evaluated, before the plan
+  let plan = kinds.iter().position(|kind| *kind == "plan").unwrap();
+  let first_wait = kinds.iter().position(|kind| *kind == "wait").unwrap();
+  assert!(plan < first_wait);
+  assert_eq!(kinds.last(), Some(&"summary"));
+  for kind in ["register", "wait", "result", "stepRegister", "stepResult"] {
+    assert!(kinds.contains(&kind), "missing {kind} event");
+  }
+
+  let register = events.iter().find(|e| e["type"] == "register").unwrap();
+  assert_eq!(
+    register["location"]["file"],
+    "./test/steps/failing_steps.ts"
+  );
+  assert!(register["location"]["line"].as_u64().unwrap() > 0);
+  assert!(events
+    .iter()
+    .any(|e| e["type"] == "result" && e["status"] == "failed"));
+  assert!(events
+    .iter()
+    .filter(|e| e["type"] == "result")
+    .all(|e| e["elapsed"].is_u64()));
+}
+
+#[test]
+fn shard() {
+  let context = TestContext::default();
+  let output = context
+    .new_command()
+    .args("test --shard=1/2 test/shard")
+    .run();
+  output.assert_exit_code(0);
+  let output_text = output.combined_output();
+  assert_contains!(output_text, "./test/shard/a_test.ts");
+  assert_not_contains!(output_text, "./test/shard/b_test.ts");
+  assert_contains!(output_text, "./test/shard/c_test.ts");
+  assert_contains!(output_text, "ok | 2 passed | 0 failed");
+
+  let output = context
+    .new_command()
+    .args("test --shard=2/2 test/shard")
+    .run();
+  output.assert_exit_code(0);
+  let output_text = output.combined_output();
+  assert_not_contains!(output_text, "./test/shard/a_test.ts");
+  assert_contains!(output_text, "./test/shard/b_test.ts");
+  assert_not_contains!(output_text, "./test/shard/c_test.ts");
+
+  let output = context
+    .new_command()
+    .args("test --shard=4/4 test/shard")
+    .run();
+  output.assert_exit_code(0);
+  assert_contains!(output.combined_output(), "No test modules in shard 4/4");
+}
@@ -0,0 +1,12 @@
+const timeout = setTimeout(() => {
+  console.log("timed out");
+  Deno.exit(1);
+}, 10_000);
+
+const channel = new BroadcastChannel("invalidations");
+channel.onmessage = (e) => {
+  console.log(e.data);
+  clearTimeout(timeout);
+  channel.close();
+};
+console.log("ready");
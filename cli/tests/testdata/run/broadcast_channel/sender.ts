@@ -0,0 +1,5 @@
+const channel = new BroadcastChannel("invalidations");
+channel.postMessage("hello from another process");
+// give the message time to be sent before closing the channel
+await new Promise((resolve) => setTimeout(resolve, 500));
+channel.close();
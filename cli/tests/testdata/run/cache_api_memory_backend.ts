@@ -0,0 +1,6 @@
+const cache = await caches.open("v1");
+console.log(await cache.match("https://deno.land/") === undefined);
+await cache.put("https://deno.land/", new Response("hello"));
+const res = await cache.match("https://deno.land/");
+console.log(await res?.text());
+console.log(await caches.keys());
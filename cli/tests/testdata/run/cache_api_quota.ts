@@ -0,0 +1,10 @@
+await caches.delete("quota");
+const cache = await caches.open("quota");
+const body = "a".repeat(600 * 1024);
+await cache.put("https://deno.land/a", new Response(body));
+await cache.put("https://deno.land/b", new Response(body));
+console.log(await cache.match("https://deno.land/a") === undefined);
+console.log(await cache.match("https://deno.land/b") !== undefined);
+const usage = (await Deno.cacheUsage()).find((c) => c.cacheName === "quota")!;
+console.log(usage.entries, usage.size <= 1024 * 1024);
+await caches.delete("quota");
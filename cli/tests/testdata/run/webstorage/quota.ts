@@ -0,0 +1,8 @@
+try {
+  localStorage.setItem("k", "v".repeat(2 * 1024 * 1024));
+} catch (err) {
+  console.log(err.name);
+}
+localStorage.setItem("k", "v");
+const { usage, quota } = await navigator.storage.estimate();
+console.log(usage > 0, quota);
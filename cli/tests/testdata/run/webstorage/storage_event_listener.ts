@@ -0,0 +1,12 @@
+const timeout = setTimeout(() => {
+  console.log("timed out");
+  Deno.exit(1);
+}, 10_000);
+
+localStorage.clear();
+globalThis.addEventListener("storage", (e) => {
+  console.log(`${e.key}: ${e.oldValue} -> ${e.newValue}`);
+  console.log(e.storageArea === localStorage);
+  clearTimeout(timeout);
+});
+console.log("ready");
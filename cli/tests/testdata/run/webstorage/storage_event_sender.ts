@@ -0,0 +1 @@
+localStorage.setItem("theme", "dark");
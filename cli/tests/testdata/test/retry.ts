@@ -0,0 +1,23 @@
+let flakyAttempts = 0;
+Deno.test("flaky", () => {
+  flakyAttempts++;
+  if (flakyAttempts < 2) {
+    throw new Error("failed on the first attempt");
+  }
+});
+
+let optionAttempts = 0;
+Deno.test({
+  name: "flaky with retry option",
+  retry: 3,
+  fn() {
+    optionAttempts++;
+    if (optionAttempts < 4) {
+      throw new Error(`failed on attempt ${optionAttempts}`);
+    }
+  },
+});
+
+Deno.test("always fails", () => {
+  throw new Error("always fails");
+});
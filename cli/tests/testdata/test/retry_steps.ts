@@ -0,0 +1,9 @@
+let attempts = 0;
+Deno.test("flaky step", async (t) => {
+  attempts++;
+  await t.step("step", () => {
+    if (attempts < 2) {
+      throw new Error("failed on the first attempt");
+    }
+  });
+});
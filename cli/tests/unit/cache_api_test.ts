@@ -189,3 +189,57 @@ Deno.test(async function cachePutOverwrite() {
   const res_ = await cache.match(request);
   assertEquals(await res_?.text(), "res2");
 });
+
+Deno.test(async function cacheStorageKeys() {
+  await caches.open("cache-keys-a");
+  await caches.open("cache-keys-b");
+  const keys = await caches.keys();
+  assert(keys.indexOf("cache-keys-a") < keys.indexOf("cache-keys-b"));
+  assert(await caches.delete("cache-keys-a"));
+  assertFalse((await caches.keys()).includes("cache-keys-a"));
+  assert(await caches.delete("cache-keys-b"));
+});
+
+Deno.test(async function cacheStorageUsage() {
+  const cache = await caches.open("cache-usage");
+  await cache.put("https://example.com/usage", new Response("hello"));
+  const usage = (await Deno.cacheUsage()).find((c) =>
+    c.cacheName === "cache-usage"
+  );
+  assertEquals(usage?.entries, 1);
+  assert(usage!.size >= "hello".length);
+  assert(await caches.delete("cache-usage"));
+});
+
+Deno.test(async function cacheMatchExpired() {
+  const cacheName = "cache-v1";
+  const cache = await caches.open(cacheName);
+
+  const request = "https://example.com/expired";
+  await cache.put(
+    request,
+    new Response("stale", { headers: { "cache-control": "max-age=0" } }),
+  );
+  assertEquals(await cache.match(request), undefined);
+
+  await cache.put(
+    request,
+    new Response("stale", {
+      headers: { "expires": "Wed, 21 Oct 2015 07:28:00 GMT" },
+    }),
+  );
+  assertEquals(await cache.match(request), undefined);
+
+  await cache.put(
+    request,
+    new Response("fresh", {
+      headers: {
+        "cache-control": "public, max-age=3600",
+        "expires": "Wed, 21 Oct 2015 07:28:00 GMT",
+      },
+    }),
+  );
+  const res = await cache.match(request);
+  assertEquals(await res?.text(), "fresh");
+  assert(await cache.delete(request));
+});
@@ -1819,6 +1819,79 @@ Deno.test({
   },
 });
 
+dbTest("watch", async (db) => {
+  await db.set(["a"], 1);
+  const stream = db.watch([["a"], ["b"]]);
+  const reader = stream.getReader();
+  try {
+    let { value: entries } = await reader.read();
+    assertEquals(entries!.length, 2);
+    assertEquals(entries![0].value, 1);
+    assertEquals(entries![1].value, null);
+    assertEquals(entries![1].versionstamp, null);
+
+    // Writes to unwatched keys don't produce updates.
+    await db.set(["c"], 3);
+    await db.set(["b"], 2);
+    ({ value: entries } = await reader.read());
+    assertEquals(entries![0].value, 1);
+    assertEquals(entries![1].value, 2);
+
+    await db.delete(["a"]);
+    ({ value: entries } = await reader.read());
+    assertEquals(entries![0].value, null);
+    assertEquals(entries![0].versionstamp, null);
+    assertEquals(entries![1].value, 2);
+  } finally {
+    await reader.cancel();
+  }
+});
+
+Deno.test({
+  name: "watch ends when the database is closed",
+  async fn() {
+    const db = await Deno.openKv(":memory:");
+    const reader = db.watch([["a"]]).getReader();
+    await reader.read();
+    db.close();
+    const { done } = await reader.read();
+    assert(done);
+  },
+});
+
+Deno.test({
+  name: "watch sees writes from another handle to the same file",
+  async fn() {
+    const filename = await Deno.makeTempFile({ prefix: "watch_db" });
+    const db = await Deno.openKv(filename);
+    const other = await Deno.openKv(filename);
+    const reader = db.watch([["a"]]).getReader();
+    try {
+      let { value: entries } = await reader.read();
+      assertEquals(entries![0].value, null);
+
+      await other.set(["a"], 1);
+      ({ value: entries } = await reader.read());
+      assertEquals(entries![0].value, 1);
+    } finally {
+      await reader.cancel();
+      other.close();
+      db.close();
+      await Deno.remove(filename);
+    }
+  },
+});
+
+dbTest("watch too many keys", (db) => {
+  const keys = Array.from({ length: 11 }, (_, i) => [i]);
+  const reader = db.watch(keys).getReader();
+  return assertRejects(
+    () => reader.read(),
+    TypeError,
+    "too many keys (max 10)",
+  );
+});
+
 dbTest("atomic operation is exposed", (db) => {
   assert(Deno.AtomicOperation);
   const ao = db.atomic();
@@ -2071,3 +2144,36 @@ Deno.test({
     }
   },
 });
+
+Deno.test({
+  name: "remote backend queue",
+  async fn() {
+    // Use a database of our own so that messages enqueued by other test
+    // processes talking to the same test server are never delivered here.
+    const databaseId = crypto.randomUUID();
+    const db = await Deno.openKv(
+      `http://localhost:4545/kv_remote_authorize?databaseId=${databaseId}`,
+    );
+    const nonce = crypto.randomUUID();
+    let attempts = 0;
+    const promise = deferred();
+    const listener = db.listenQueue((msg) => {
+      assertEquals(msg, nonce);
+      attempts += 1;
+      if (attempts === 1) {
+        // Fail the first delivery to exercise redelivery.
+        throw new Error("injected error");
+      }
+      promise.resolve();
+    });
+    try {
+      const res = await db.enqueue(nonce);
+      assert(res.ok);
+      await promise;
+      assertEquals(attempts, 2);
+    } finally {
+      db.close();
+      await listener;
+    }
+  },
+});
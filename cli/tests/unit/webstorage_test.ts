@@ -1,7 +1,7 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
 // deno-lint-ignore-file no-explicit-any
 
-import { assert, assertThrows } from "./test_util.ts";
+import { assert, assertEquals, assertThrows } from "./test_util.ts";
 
 Deno.test({ permissions: "none" }, function webStoragesReassignable() {
   // Can reassign to web storages
@@ -40,3 +40,17 @@ Deno.test(function webstorageSizeLimit() {
     "Exceeded maximum storage size",
   );
 });
+
+Deno.test({ permissions: "none" }, function storageEventConstructor() {
+  const event = new StorageEvent("storage", {
+    key: "k",
+    oldValue: "a",
+    newValue: "b",
+  });
+  assertEquals(event.type, "storage");
+  assertEquals(event.key, "k");
+  assertEquals(event.oldValue, "a");
+  assertEquals(event.newValue, "b");
+  assertEquals(event.url, "");
+  assertEquals(event.storageArea, null);
+});
@@ -0,0 +1,279 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+
+//! Bench results saved to disk with `--save-baseline`, so that a later run
+//! can be compared against them with `--compare-baseline`.
+//!
+//! Benchmarks are matched by origin, group and name. Origins are stored
+//! relative to the current directory, so a baseline saved in one checkout
+//! can be compared against a run in another.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use deno_core::anyhow::Context;
+use deno_core::error::generic_error;
+use deno_core::error::AnyError;
+use deno_core::serde_json;
+use deno_core::url::Url;
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::mitata;
+use super::BenchDescription;
+use super::BenchStats;
+use crate::colors;
+use crate::tools::test::fmt::to_relative_path_or_remote_url;
+use crate::version::get_user_agent;
+
+const BASELINE_VERSION: u32 = 1;
+
+/// Two-sided critical value of the normal distribution for 95% confidence.
+/// Benchmarks run enough iterations for the normal approximation of Welch's
+/// t-test to hold.
+const CRITICAL_VALUE: f64 = 1.96;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct BaselineFile {
+  version: u32,
+  runtime: String,
+  cpu: String,
+  benches: Vec<BaselineBench>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct BaselineBench {
+  origin: String,
+  group: Option<String>,
+  name: String,
+  stats: BenchStats,
+}
+
+type BenchKey = (String, Option<String>, String);
+
+#[derive(Debug, Clone)]
+pub struct Baseline {
+  benches: HashMap<BenchKey, BenchStats>,
+}
+
+/// How a benchmark compares to its baseline measurement.
+#[derive(Debug)]
+pub struct BenchComparison<'a> {
+  pub desc: &'a BenchDescription,
+  pub baseline: BenchStats,
+  pub current: &'a BenchStats,
+  /// Change of the average time, in percent of the baseline average. `None`
+  /// if the baseline average is zero.
+  pub change: Option<f64>,
+  /// Whether the change is statistically significant.
+  pub significant: bool,
+}
+
+impl BenchComparison<'_> {
+  /// Whether the benchmark got significantly slower by more than
+  /// `max_regression` percent.
+  pub fn regressed(&self, max_regression: f64) -> bool {
+    self.significant
+      && self.change.is_some_and(|change| change > max_regression)
+  }
+}
+
+fn cwd_url() -> Url {
+  Url::from_directory_path(std::env::current_dir().unwrap()).unwrap()
+}
+
+fn bench_key(cwd: &Url, desc: &BenchDescription) -> BenchKey {
+  (
+    to_relative_path_or_remote_url(cwd, &desc.origin),
+    desc.group.clone(),
+    desc.name.clone(),
+  )
+}
+
+pub fn save_baseline(
+  path: &Path,
+  measurements: &[(BenchDescription, BenchStats)],
+) -> Result<(), AnyError> {
+  let cwd = cwd_url();
+  let file = BaselineFile {
+    version: BASELINE_VERSION,
+    runtime: format!("{} {}", get_user_agent(), env!("TARGET")),
+    cpu: mitata::cpu::name(),
+    benches: measurements
+      .iter()
+      .filter(|(desc, _)| !desc.warmup)
+      .map(|(desc, stats)| {
+        let (origin, group, name) = bench_key(&cwd, desc);
+        BaselineBench {
+          origin,
+          group,
+          name,
+          stats: stats.clone(),
+        }
+      })
+      .collect(),
+  };
+  let json = serde_json::to_string_pretty(&file)?;
+  std::fs::write(path, json)
+    .with_context(|| format!("Failed to write {}", path.display()))?;
+  log::info!("{} baseline to {}", colors::green("Saved"), path.display());
+  Ok(())
+}
+
+impl Baseline {
+  pub fn load(path: &Path) -> Result<Self, AnyError> {
+    let text = std::fs::read_to_string(path)
+      .with_context(|| format!("Failed to read {}", path.display()))?;
+    let file: BaselineFile = serde_json::from_str(&text)
+      .with_context(|| format!("Invalid bench baseline {}", path.display()))?;
+    if file.version != BASELINE_VERSION {
+      return Err(generic_error(format!(
+        "Unsupported bench baseline version {} in {}",
+        file.version,
+        path.display()
+      )));
+    }
+    Ok(Self {
+      benches: file
+        .benches
+        .into_iter()
+        .map(|bench| ((bench.origin, bench.group, bench.name), bench.stats))
+        .collect(),
+    })
+  }
+
+  /// Compares the measurements to the baseline. Benchmarks that are not in
+  /// the baseline are left out.
+  pub fn compare<'a>(
+    &self,
+    measurements: &'a [(BenchDescription, BenchStats)],
+  ) -> Vec<BenchComparison<'a>> {
+    let cwd = cwd_url();
+    measurements
+      .iter()
+      .filter(|(desc, _)| !desc.warmup)
+      .filter_map(|(desc, current)| {
+        let baseline = self.benches.get(&bench_key(&cwd, desc))?;
+        Some(BenchComparison {
+          desc,
+          baseline: baseline.clone(),
+          current,
+          change: percent_change(baseline.avg, current.avg),
+          significant: is_significant(baseline, current),
+        })
+      })
+      .collect()
+  }
+}
+
+/// Returns the change from `baseline` to `current` in percent of `baseline`,
+/// or `None` if that is not a finite number.
+fn percent_change(baseline: f64, current: f64) -> Option<f64> {
+  let change = (current - baseline) / baseline * 100.0;
+  change.is_finite().then_some(change)
+}
+
+/// Welch's t-test on the averages of two measurements.
+fn is_significant(a: &BenchStats, b: &BenchStats) -> bool {
+  let standard_error =
+    (a.stddev.powi(2) / a.n as f64 + b.stddev.powi(2) / b.n as f64).sqrt();
+  if standard_error == 0.0 {
+    return a.avg != b.avg;
+  }
+  ((b.avg - a.avg) / standard_error).abs() > CRITICAL_VALUE
+}
+
+pub fn report_comparisons(path: &Path, comparisons: &[BenchComparison]) {
+  log::info!(
+    "\n{}",
+    colors::gray(format!("compared with baseline {}", path.display()))
+  );
+  let name_width = comparisons
+    .iter()
+    .map(|c| c.desc.name.chars().count())
+    .max()
+    .unwrap_or(0)
+    .max("benchmark".len());
+  log::info!(
+    "{:<name_width$}  {:>14}  {:>14}  {:>9}",
+    "benchmark",
+    "baseline",
+    "current",
+    "change",
+  );
+  for comparison in comparisons {
+    let change = match comparison.change {
+      None => colors::gray(format!("{:>9}", "n/a")).to_string(),
+      Some(change) => {
+        let text = format!("{:>+8.2}%", change);
+        if !comparison.significant {
+          colors::gray(text).to_string()
+        } else if change > 0.0 {
+          colors::red(text).to_string()
+        } else {
+          colors::green(text).to_string()
+        }
+      }
+    };
+    log::info!(
+      "{:<name_width$}  {:>14}  {:>14}  {}{}",
+      comparison.desc.name,
+      mitata::fmt_duration(comparison.baseline.avg),
+      mitata::fmt_duration(comparison.current.avg),
+      change,
+      if comparison.significant {
+        ""
+      } else {
+        " (not significant)"
+      },
+    );
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn stats(n: u64, avg: f64, stddev: f64) -> BenchStats {
+    BenchStats {
+      n,
+      min: avg - stddev,
+      max: avg + stddev,
+      avg,
+      stddev,
+      p75: avg,
+      p99: avg,
+      p995: avg,
+      p999: avg,
+      high_precision: true,
+      used_explicit_timers: false,
+      memory: None,
+    }
+  }
+
+  #[test]
+  fn change() {
+    assert_eq!(percent_change(100.0, 110.0), Some(10.0));
+    assert_eq!(percent_change(100.0, 50.0), Some(-50.0));
+    // no-op benchmarks can have an average of zero
+    assert_eq!(percent_change(0.0, 10.0), None);
+    assert_eq!(percent_change(0.0, 0.0), None);
+  }
+
+  #[test]
+  fn significance() {
+    // Well separated relative to the noise.
+    assert!(is_significant(
+      &stats(1000, 100.0, 10.0),
+      &stats(1000, 110.0, 10.0)
+    ));
+    // The difference is within the noise.
+    assert!(!is_significant(
+      &stats(10, 100.0, 50.0),
+      &stats(10, 110.0, 50.0)
+    ));
+    assert!(!is_significant(
+      &stats(10, 100.0, 0.0),
+      &stats(10, 100.0, 0.0)
+    ));
+  }
+}
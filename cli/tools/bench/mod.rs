@@ -1,6 +1,7 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
 
 use crate::args::BenchFlags;
+use crate::args::BenchOptions;
 use crate::args::CliOptions;
 use crate::args::Flags;
 use crate::colors;
@@ -41,13 +42,18 @@ use serde::Deserialize;
 use serde::Serialize;
 use std::collections::HashSet;
 use std::path::Path;
+use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::sync::mpsc::unbounded_channel;
 use tokio::sync::mpsc::UnboundedSender;
 
+mod baseline;
 mod mitata;
 mod reporters;
 
+use baseline::report_comparisons;
+use baseline::save_baseline;
+use baseline::Baseline;
 use reporters::BenchReporter;
 use reporters::ConsoleReporter;
 use reporters::JsonReporter;
@@ -56,7 +62,32 @@ use reporters::JsonReporter;
 struct BenchSpecifierOptions {
   filter: TestFilter,
   json: bool,
+  memory: bool,
   log_level: Option<log::Level>,
+  save_baseline: Option<PathBuf>,
+  compare_baseline: Option<(PathBuf, Arc<Baseline>)>,
+  max_regression: Option<u32>,
+}
+
+impl BenchSpecifierOptions {
+  fn resolve(
+    bench_options: &BenchOptions,
+    log_level: Option<log::Level>,
+  ) -> Result<Self, AnyError> {
+    let compare_baseline = match &bench_options.compare_baseline {
+      Some(path) => Some((path.clone(), Arc::new(Baseline::load(path)?))),
+      None => None,
+    };
+    Ok(Self {
+      filter: TestFilter::from_flag(&bench_options.filter),
+      json: bench_options.json,
+      memory: bench_options.memory,
+      log_level,
+      save_baseline: bench_options.save_baseline.clone(),
+      compare_baseline,
+      max_regression: bench_options.max_regression,
+    })
+  }
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Deserialize)]
@@ -112,12 +143,29 @@ pub struct BenchStats {
   pub min: f64,
   pub max: f64,
   pub avg: f64,
+  pub stddev: f64,
   pub p75: f64,
   pub p99: f64,
   pub p995: f64,
   pub p999: f64,
   pub high_precision: bool,
   pub used_explicit_timers: bool,
+  /// Collected with `--memory` or the `memory` bench option.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub memory: Option<BenchMemoryStats>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BenchMemoryStats {
+  /// Number of iterations that were sampled.
+  pub n: u64,
+  /// Average growth of the used heap per iteration, in bytes, over the
+  /// iterations during which the used heap did not shrink.
+  pub heap_delta: f64,
+  /// Number of iterations during which the used heap shrank. This hints at
+  /// garbage collections, but is not a count of them.
+  pub heap_shrinks: u64,
 }
 
 impl BenchReport {
@@ -166,12 +214,16 @@ async fn bench_specifier(
   specifier: ModuleSpecifier,
   sender: UnboundedSender<BenchEvent>,
   filter: TestFilter,
+  collect_memory: bool,
 ) -> Result<(), AnyError> {
   let mut worker = worker_factory
     .create_custom_worker(
       specifier.clone(),
       PermissionsContainer::new(permissions),
-      vec![ops::bench::deno_bench::init_ops(sender.clone())],
+      vec![ops::bench::deno_bench::init_ops(
+        sender.clone(),
+        collect_memory,
+      )],
       Default::default(),
     )
     .await?;
@@ -254,6 +306,7 @@ async fn bench_specifiers(
         specifier,
         sender,
         options.filter,
+        options.memory,
       );
       create_and_run_current_thread(future)
     })
@@ -324,6 +377,33 @@ async fn bench_specifiers(
         return Err(generic_error("Bench failed"));
       }
 
+      if let Some(path) = &options.save_baseline {
+        save_baseline(path, &report.measurements)?;
+      }
+
+      if let Some((path, baseline)) = &options.compare_baseline {
+        let comparisons = baseline.compare(&report.measurements);
+        report_comparisons(path, &comparisons);
+        if let Some(max_regression) = options.max_regression {
+          let regressed = comparisons
+            .iter()
+            .filter(|c| c.regressed(max_regression as f64))
+            .count();
+          if regressed > 0 {
+            return Err(generic_error(format!(
+              "Bench failed because {} {} regressed by more than {}%",
+              regressed,
+              if regressed == 1 {
+                "benchmark"
+              } else {
+                "benchmarks"
+              },
+              max_regression
+            )));
+          }
+        }
+      }
+
       Ok(())
     })
   };
@@ -392,11 +472,7 @@ pub async fn run_benchmarks(
     worker_factory,
     &permissions,
     specifiers,
-    BenchSpecifierOptions {
-      filter: TestFilter::from_flag(&bench_options.filter),
-      json: bench_options.json,
-      log_level,
-    },
+    BenchSpecifierOptions::resolve(&bench_options, log_level)?,
   )
   .await?;
 
@@ -493,11 +569,7 @@ pub async fn run_benchmarks_with_watch(
           worker_factory,
           &permissions,
           specifiers,
-          BenchSpecifierOptions {
-            filter: TestFilter::from_flag(&bench_options.filter),
-            json: bench_options.json,
-            log_level,
-          },
+          BenchSpecifierOptions::resolve(&bench_options, log_level)?,
         )
         .await?;
 
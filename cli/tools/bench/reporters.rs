@@ -236,6 +236,18 @@ impl BenchReporter for ConsoleReporter {
           )
         );
 
+        if let Some(memory) = &stats.memory {
+          println!(
+            "{}",
+            colors::gray(format!(
+              "  heap/iter: {}  heap shrinks: {} in {} iterations",
+              crate::display::human_size(memory.heap_delta),
+              memory.heap_shrinks,
+              memory.n
+            ))
+          );
+        }
+
         if !stats.high_precision && stats.used_explicit_timers {
           println!("{}", colors::yellow(format!("Warning: start() and end() calls in \"{}\" are ignored because it averages less\nthan 0.01s per iteration. Remove them for better results.", &desc.name)));
         }
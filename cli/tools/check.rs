@@ -7,6 +7,7 @@ use std::sync::Arc;
 use deno_ast::MediaType;
 use deno_ast::ModuleSpecifier;
 use deno_core::error::AnyError;
+use deno_core::url::Url;
 use deno_graph::Module;
 use deno_graph::ModuleGraph;
 use deno_runtime::colors;
@@ -16,6 +17,7 @@ use deno_semver::package::PackageReq;
 use once_cell::sync::Lazy;
 use regex::Regex;
 
+use crate::args::CheckReporterConfig;
 use crate::args::CliOptions;
 use crate::args::TsConfig;
 use crate::args::TsConfigType;
@@ -26,6 +28,15 @@ use crate::cache::FastInsecureHasher;
 use crate::cache::TypeCheckCache;
 use crate::npm::CliNpmResolver;
 use crate::tsc;
+use crate::tsc::Diagnostic;
+use crate::tsc::DiagnosticCategory;
+use crate::tsc::Diagnostics;
+use crate::util::github_actions;
+use crate::util::github_actions::AnnotationLevel;
+use crate::util::github_actions::AnnotationLocation;
+use crate::util::sarif::SarifLevel;
+use crate::util::sarif::SarifLocation;
+use crate::util::sarif::SarifRun;
 use crate::version;
 
 /// Options for performing a check of a module graph. Note that the decision to
@@ -185,6 +196,96 @@ impl TypeChecker {
   }
 }
 
+/// Writes the type errors of `deno check` to stdout in the format of the
+/// reporter. The result is passed through, so the errors are still printed to
+/// stderr as usual.
+pub fn report_check_result(
+  reporter: CheckReporterConfig,
+  result: Result<(), AnyError>,
+) -> Result<(), AnyError> {
+  let diagnostics = match &result {
+    Ok(()) => None,
+    Err(err) => match err.downcast_ref::<Diagnostics>() {
+      Some(diagnostics) => Some(diagnostics),
+      None => return result,
+    },
+  };
+  match reporter {
+    CheckReporterConfig::Pretty => {}
+    CheckReporterConfig::Sarif => {
+      let mut run = SarifRun::new(
+        "deno check",
+        "https://deno.land/manual/basics/typescript",
+      );
+      for diagnostic in diagnostics.into_iter().flat_map(|d| d.iter()) {
+        let level = match diagnostic.category {
+          DiagnosticCategory::Error => SarifLevel::Error,
+          DiagnosticCategory::Warning => SarifLevel::Warning,
+          _ => SarifLevel::Note,
+        };
+        let location =
+          diagnostic_location(diagnostic).map(|(specifier, start, end)| {
+            SarifLocation {
+              specifier,
+              start_line: start.line + 1,
+              start_column: start.character + 1,
+              end_line: end.line + 1,
+              end_column: end.character + 1,
+            }
+          });
+        run.add_result(
+          &format!("TS{}", diagnostic.code),
+          None,
+          level,
+          &diagnostic.message(),
+          location.as_ref(),
+        );
+      }
+      println!("{}", run.to_json());
+    }
+    CheckReporterConfig::Github => {
+      for diagnostic in diagnostics.into_iter().flat_map(|d| d.iter()) {
+        let level = match diagnostic.category {
+          DiagnosticCategory::Error => AnnotationLevel::Error,
+          DiagnosticCategory::Warning => AnnotationLevel::Warning,
+          _ => AnnotationLevel::Notice,
+        };
+        let location =
+          diagnostic_location(diagnostic).map(|(specifier, start, end)| {
+            AnnotationLocation {
+              specifier,
+              line: start.line + 1,
+              column: start.character + 1,
+              end_line: end.line + 1,
+              end_column: end.character + 1,
+            }
+          });
+        println!(
+          "{}",
+          github_actions::format_annotation(
+            level,
+            &format!("TS{}", diagnostic.code),
+            &diagnostic.message(),
+            location.as_ref(),
+          )
+        );
+      }
+    }
+  }
+  result
+}
+
+fn diagnostic_location(
+  diagnostic: &Diagnostic,
+) -> Option<(Url, &tsc::Position, &tsc::Position)> {
+  let specifier = Url::parse(diagnostic.file_name.as_ref()?).ok()?;
+  Some((
+    specifier,
+    diagnostic.start.as_ref()?,
+    diagnostic.end.as_ref()?,
+  ))
+}
+
 enum CheckHashResult {
   Hash(u64),
   NoFiles,
@@ -0,0 +1,207 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+
+//! A coverage report in the Cobertura xml format. Files are grouped into one
+//! package per directory.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+use deno_ast::ModuleSpecifier;
+use deno_core::error::AnyError;
+
+use super::branches_by_line;
+use super::html::escape_html;
+use super::relative_paths;
+use super::CoverageReport;
+use super::CoverageReporter;
+use super::CoverageStats;
+
+pub struct CoberturaCoverageReporter {
+  output: Option<PathBuf>,
+  files: Vec<CoberturaFile>,
+}
+
+struct CoberturaFile {
+  url: ModuleSpecifier,
+  /// Zero based line index and hit count.
+  lines: Vec<(usize, i64)>,
+  /// Hit and found branches, by zero based line index.
+  branches: BTreeMap<usize, (usize, usize)>,
+  /// Name, zero based line index and execution count.
+  functions: Vec<(String, usize, i64)>,
+  stats: CoverageStats,
+}
+
+impl CoberturaCoverageReporter {
+  pub fn new(output: Option<PathBuf>) -> CoberturaCoverageReporter {
+    CoberturaCoverageReporter {
+      output,
+      files: Vec::new(),
+    }
+  }
+
+  fn to_xml(&self) -> String {
+    let urls = self.files.iter().map(|f| &f.url).collect::<Vec<_>>();
+    let (root, paths) = relative_paths(&urls);
+
+    let mut packages: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+    for (index, path) in paths.iter().enumerate() {
+      let dir = path[..path.len() - 1].join("/");
+      packages.entry(dir).or_default().push(index);
+    }
+
+    let mut total = CoverageStats::default();
+    for file in &self.files {
+      total.add(&file.stats);
+    }
+    let timestamp = SystemTime::now()
+      .duration_since(SystemTime::UNIX_EPOCH)
+      .unwrap()
+      .as_millis();
+
+    let mut xml = String::new();
+    xml.push_str("<?xml version=\"1.0\" ?>\n");
+    xml.push_str("<!DOCTYPE coverage SYSTEM \"http://cobertura.sourceforge.net/xml/coverage-04.dtd\">\n");
+    writeln!(
+      xml,
+      "<coverage lines-valid=\"{}\" lines-covered=\"{}\" line-rate=\"{}\" branches-valid=\"{}\" branches-covered=\"{}\" branch-rate=\"{}\" timestamp=\"{timestamp}\" complexity=\"0\" version=\"0.1\">",
+      total.lines_found,
+      total.lines_hit,
+      rate(total.lines_hit, total.lines_found),
+      total.branches_found,
+      total.branches_hit,
+      rate(total.branches_hit, total.branches_found),
+    )
+    .unwrap();
+    xml.push_str("  <sources>\n");
+    if let Some(root) = &root {
+      writeln!(
+        xml,
+        "    <source>{}</source>",
+        escape_html(&root.to_string_lossy())
+      )
+      .unwrap();
+    }
+    xml.push_str("  </sources>\n");
+    xml.push_str("  <packages>\n");
+    for (dir, indexes) in &packages {
+      let mut stats = CoverageStats::default();
+      for &index in indexes {
+        stats.add(&self.files[index].stats);
+      }
+      let name = if dir.is_empty() { "." } else { dir.as_str() };
+      writeln!(
+        xml,
+        "    <package name=\"{}\" line-rate=\"{}\" branch-rate=\"{}\" complexity=\"0\">",
+        escape_html(name),
+        rate(stats.lines_hit, stats.lines_found),
+        rate(stats.branches_hit, stats.branches_found),
+      )
+      .unwrap();
+      xml.push_str("      <classes>\n");
+      for &index in indexes {
+        write_class(&mut xml, &self.files[index], &paths[index]);
+      }
+      xml.push_str("      </classes>\n");
+      xml.push_str("    </package>\n");
+    }
+    xml.push_str("  </packages>\n");
+    xml.push_str("</coverage>\n");
+    xml
+  }
+}
+
+fn write_class(xml: &mut String, file: &CoberturaFile, path: &[String]) {
+  let stats = &file.stats;
+  writeln!(
+    xml,
+    "        <class name=\"{}\" filename=\"{}\" line-rate=\"{}\" branch-rate=\"{}\" complexity=\"0\">",
+    escape_html(path.last().unwrap()),
+    escape_html(&path.join("/")),
+    rate(stats.lines_hit, stats.lines_found),
+    rate(stats.branches_hit, stats.branches_found),
+  )
+  .unwrap();
+
+  xml.push_str("          <methods>\n");
+  for (name, line_index, count) in &file.functions {
+    writeln!(
+      xml,
+      "            <method name=\"{}\" signature=\"\" line-rate=\"{}\" branch-rate=\"0\" complexity=\"0\">",
+      escape_html(name),
+      if *count > 0 { 1 } else { 0 },
+    )
+    .unwrap();
+    writeln!(
+      xml,
+      "              <lines><line number=\"{}\" hits=\"{count}\"/></lines>",
+      line_index + 1
+    )
+    .unwrap();
+    xml.push_str("            </method>\n");
+  }
+  xml.push_str("          </methods>\n");
+
+  xml.push_str("          <lines>\n");
+  for (line_index, count) in &file.lines {
+    match file.branches.get(line_index) {
+      Some((hit, found)) => writeln!(
+        xml,
+        "            <line number=\"{}\" hits=\"{count}\" branch=\"true\" condition-coverage=\"{}% ({hit}/{found})\"/>",
+        line_index + 1,
+        hit * 100 / found,
+      ),
+      None => writeln!(
+        xml,
+        "            <line number=\"{}\" hits=\"{count}\" branch=\"false\"/>",
+        line_index + 1,
+      ),
+    }
+    .unwrap();
+  }
+  xml.push_str("          </lines>\n");
+  xml.push_str("        </class>\n");
+}
+
+/// Cobertura rates are in the range 0 to 1. Code without lines or branches
+/// is fully covered.
+fn rate(hit: usize, found: usize) -> String {
+  if found == 0 {
+    "1".to_string()
+  } else {
+    format!("{:.4}", hit as f64 / found as f64)
+  }
+}
+
+impl CoverageReporter for CoberturaCoverageReporter {
+  fn report(
+    &mut self,
+    coverage_report: &CoverageReport,
+    _file_text: &str,
+  ) -> Result<(), AnyError> {
+    self.files.push(CoberturaFile {
+      url: coverage_report.url.clone(),
+      lines: coverage_report.found_lines.clone(),
+      branches: branches_by_line(coverage_report),
+      functions: coverage_report
+        .named_functions
+        .iter()
+        .map(|f| (f.name.clone(), f.line_index, f.execution_count))
+        .collect(),
+      stats: CoverageStats::from_report(coverage_report),
+    });
+    Ok(())
+  }
+
+  fn done(&mut self) -> Result<(), AnyError> {
+    let xml = self.to_xml();
+    match &self.output {
+      Some(path) => std::fs::write(path, xml)?,
+      None => std::io::stdout().write_all(xml.as_bytes())?,
+    }
+    Ok(())
+  }
+}
@@ -0,0 +1,377 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+
+//! A browsable html coverage report. Every directory gets an `index.html`
+//! listing its subdirectories and files, and every file gets a page with its
+//! source, where uncovered lines and partially taken branches are highlighted.
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+
+use deno_ast::ModuleSpecifier;
+use deno_core::anyhow::Context;
+use deno_core::error::AnyError;
+
+use super::branches_by_line;
+use super::ratio_percent;
+use super::relative_paths;
+use super::CoverageReport;
+use super::CoverageReporter;
+use super::CoverageStats;
+
+const STYLE: &str = include_str!("./style.css");
+
+/// Characters that are percent-encoded in a path segment of a link. Besides
+/// the characters that would end or change the meaning of the segment (a `:`
+/// could make it look like a url scheme), this includes the characters that are special in html attributes, so that an
+/// encoded segment can be used in an `href` as is.
+const HREF_SEGMENT: &percent_encoding::AsciiSet = &percent_encoding::CONTROLS
+  .add(b' ')
+  .add(b'"')
+  .add(b'#')
+  .add(b'%')
+  .add(b'&')
+  .add(b'\'')
+  .add(b'/')
+  .add(b':')
+  .add(b'<')
+  .add(b'>')
+  .add(b'?')
+  .add(b'[')
+  .add(b'\\')
+  .add(b']')
+  .add(b'^')
+  .add(b'`')
+  .add(b'{')
+  .add(b'|')
+  .add(b'}');
+
+pub struct HtmlCoverageReporter {
+  output_dir: PathBuf,
+  files: Vec<FileReport>,
+}
+
+struct FileReport {
+  url: ModuleSpecifier,
+  source: String,
+  /// Hit counts, by zero based line index.
+  line_counts: HashMap<usize, i64>,
+  /// Hit and found branches, by zero based line index.
+  branches: BTreeMap<usize, (usize, usize)>,
+  stats: CoverageStats,
+}
+
+impl HtmlCoverageReporter {
+  pub fn new(output_dir: PathBuf) -> HtmlCoverageReporter {
+    HtmlCoverageReporter {
+      output_dir,
+      files: Vec::new(),
+    }
+  }
+
+  fn write_directory_page(
+    &self,
+    dir: &[String],
+    entries: &DirectoryEntries,
+    dir_stats: &BTreeMap<Vec<String>, CoverageStats>,
+    paths: &[Vec<String>],
+  ) -> Result<(), AnyError> {
+    let mut rows = String::new();
+    for subdir in &entries.subdirs {
+      let mut path = dir.to_vec();
+      path.push(subdir.clone());
+      write_list_row(
+        &mut rows,
+        &format!("{}/index.html", encode_href_segment(subdir)),
+        &format!("{}/", subdir),
+        &dir_stats[&path],
+      );
+    }
+    for &index in &entries.files {
+      let name = paths[index].last().unwrap();
+      write_list_row(
+        &mut rows,
+        &format!("{}.html", encode_href_segment(name)),
+        name,
+        &self.files[index].stats,
+      );
+    }
+
+    let body = format!(
+      "{}<table class=\"list\">\n<tr><th>Name</th><th>Lines</th><th>Branches</th><th>Functions</th></tr>\n{}</table>\n",
+      summary_html(&dir_stats[dir]),
+      rows
+    );
+    let page = page_html(dir, None, &body);
+    let path = join_segments(&self.output_dir, dir).join("index.html");
+    write_file(&path, &page)
+  }
+
+  fn write_file_page(
+    &self,
+    file: &FileReport,
+    path: &[String],
+  ) -> Result<(), AnyError> {
+    let mut rows = String::new();
+    for (line_index, line) in file.source.lines().enumerate() {
+      let count = file.line_counts.get(&line_index);
+      let class = match count {
+        Some(0) => " class=\"miss\"",
+        Some(_) => " class=\"hit\"",
+        None => "",
+      };
+      let count = count.map(|c| format!("{c}x")).unwrap_or_default();
+      let branches = match file.branches.get(&line_index) {
+        Some((hit, found)) if hit < found => {
+          format!("<td class=\"line-branches partial\">{hit}/{found}</td>")
+        }
+        Some((hit, found)) => {
+          format!("<td class=\"line-branches\">{hit}/{found}</td>")
+        }
+        None => "<td class=\"line-branches\"></td>".to_string(),
+      };
+      writeln!(
+        rows,
+        "<tr{class}><td class=\"line-number\">{}</td><td class=\"line-count\">{count}</td>{branches}<td class=\"line-code\">{}</td></tr>",
+        line_index + 1,
+        escape_html(line)
+      )
+      .unwrap();
+    }
+
+    let (dir, name) = path.split_at(path.len() - 1);
+    let body = format!(
+      "{}<table class=\"source\">\n{}</table>\n",
+      summary_html(&file.stats),
+      rows
+    );
+    let page = page_html(dir, Some(&name[0]), &body);
+    let path =
+      join_segments(&self.output_dir, dir).join(format!("{}.html", name[0]));
+    write_file(&path, &page)
+  }
+}
+
+#[derive(Default)]
+struct DirectoryEntries {
+  subdirs: BTreeSet<String>,
+  files: Vec<usize>,
+}
+
+impl CoverageReporter for HtmlCoverageReporter {
+  fn report(
+    &mut self,
+    coverage_report: &CoverageReport,
+    file_text: &str,
+  ) -> Result<(), AnyError> {
+    self.files.push(FileReport {
+      url: coverage_report.url.clone(),
+      source: file_text.to_string(),
+      line_counts: coverage_report.found_lines.iter().copied().collect(),
+      branches: branches_by_line(coverage_report),
+      stats: CoverageStats::from_report(coverage_report),
+    });
+    Ok(())
+  }
+
+  fn done(&mut self) -> Result<(), AnyError> {
+    let urls = self.files.iter().map(|f| &f.url).collect::<Vec<_>>();
+    let (_, paths) = relative_paths(&urls);
+
+    let mut directories: BTreeMap<Vec<String>, DirectoryEntries> =
+      BTreeMap::new();
+    let mut dir_stats: BTreeMap<Vec<String>, CoverageStats> = BTreeMap::new();
+    directories.entry(vec![]).or_default();
+    dir_stats.entry(vec![]).or_default();
+    for (index, path) in paths.iter().enumerate() {
+      let dir = &path[..path.len() - 1];
+      for depth in 0..=dir.len() {
+        let ancestor = dir[..depth].to_vec();
+        dir_stats
+          .entry(ancestor.clone())
+          .or_default()
+          .add(&self.files[index].stats);
+        let entries = directories.entry(ancestor).or_default();
+        if depth < dir.len() {
+          entries.subdirs.insert(dir[depth].clone());
+        } else {
+          entries.files.push(index);
+        }
+      }
+    }
+
+    for (dir, entries) in &directories {
+      self.write_directory_page(dir, entries, &dir_stats, &paths)?;
+    }
+    for (file, path) in self.files.iter().zip(&paths) {
+      self.write_file_page(file, path)?;
+    }
+
+    log::info!(
+      "HTML coverage report has been generated at {}",
+      self.output_dir.join("index.html").display()
+    );
+    Ok(())
+  }
+}
+
+fn join_segments(base: &Path, segments: &[String]) -> PathBuf {
+  let mut path = base.to_path_buf();
+  for segment in segments {
+    path.push(segment);
+  }
+  path
+}
+
+fn write_file(path: &Path, contents: &str) -> Result<(), AnyError> {
+  if let Some(parent) = path.parent() {
+    fs::create_dir_all(parent)?;
+  }
+  fs::write(path, contents)
+    .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+fn page_html(dir: &[String], file_name: Option<&str>, body: &str) -> String {
+  // Links are relative to the page, which is inside `dir`.
+  let mut breadcrumbs = String::new();
+  let root_link = "../".repeat(dir.len());
+  write!(
+    breadcrumbs,
+    "<a href=\"{root_link}index.html\">All files</a>"
+  )
+  .unwrap();
+  for (i, segment) in dir.iter().enumerate() {
+    let link = "../".repeat(dir.len() - i - 1);
+    write!(
+      breadcrumbs,
+      " / <a href=\"{link}index.html\">{}</a>",
+      escape_html(segment)
+    )
+    .unwrap();
+  }
+  if let Some(file_name) = file_name {
+    write!(breadcrumbs, " / {}", escape_html(file_name)).unwrap();
+  }
+  let title = match file_name {
+    Some(file_name) => file_name.to_string(),
+    None if dir.is_empty() => "All files".to_string(),
+    None => dir.join("/"),
+  };
+
+  format!(
+    "<!DOCTYPE html>
+<html>
+<head>
+<meta charset=\"utf-8\">
+<title>Coverage report - {}</title>
+<style>
+{STYLE}</style>
+</head>
+<body>
+<h1>{breadcrumbs}</h1>
+{body}</body>
+</html>
+",
+    escape_html(&title)
+  )
+}
+
+fn summary_html(stats: &CoverageStats) -> String {
+  let item = |label: &str, hit: usize, found: usize| {
+    format!(
+      "<div><span class=\"ratio\">{}</span> {label} <span>{hit}/{found}</span></div>",
+      format_ratio(hit, found)
+    )
+  };
+  format!(
+    "<div class=\"summary\">{}{}{}</div>\n",
+    item("Lines", stats.lines_hit, stats.lines_found),
+    item("Branches", stats.branches_hit, stats.branches_found),
+    item("Functions", stats.functions_hit, stats.functions_found),
+  )
+}
+
+fn write_list_row(
+  out: &mut String,
+  href: &str,
+  name: &str,
+  stats: &CoverageStats,
+) {
+  let cell = |hit: usize, found: usize| {
+    let class = match ratio_percent(hit, found) {
+      Some(ratio) if ratio >= 90.0 => "high",
+      Some(ratio) if ratio >= 75.0 => "medium",
+      Some(_) => "low",
+      None => "",
+    };
+    format!(
+      "<td class=\"{class}\">{} ({hit}/{found})</td>",
+      format_ratio(hit, found)
+    )
+  };
+  writeln!(
+    out,
+    "<tr><td><a href=\"{href}\">{}</a></td>{}{}{}</tr>",
+    escape_html(name),
+    cell(stats.lines_hit, stats.lines_found),
+    cell(stats.branches_hit, stats.branches_found),
+    cell(stats.functions_hit, stats.functions_found),
+  )
+  .unwrap();
+}
+
+fn format_ratio(hit: usize, found: usize) -> String {
+  match ratio_percent(hit, found) {
+    Some(ratio) => format!("{ratio:.2}%"),
+    None => "-".to_string(),
+  }
+}
+
+/// Percent-encodes a path segment for use in an `href`.
+fn encode_href_segment(segment: &str) -> String {
+  percent_encoding::utf8_percent_encode(segment, HREF_SEGMENT).to_string()
+}
+
+pub fn escape_html(text: &str) -> String {
+  let mut escaped = String::with_capacity(text.len());
+  for c in text.chars() {
+    match c {
+      '&' => escaped.push_str("&amp;"),
+      '<' => escaped.push_str("&lt;"),
+      '>' => escaped.push_str("&gt;"),
+      '"' => escaped.push_str("&quot;"),
+      '\'' => escaped.push_str("&#39;"),
+      _ => escaped.push(c),
+    }
+  }
+  escaped
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_escape_html() {
+    assert_eq!(
+      escape_html("if (a < b && c > \"d\") {}"),
+      "if (a &lt; b &amp;&amp; c &gt; &quot;d&quot;) {}"
+    );
+  }
+
+  #[test]
+  fn test_encode_href_segment() {
+    assert_eq!(encode_href_segment("mod.ts"), "mod.ts");
+    assert_eq!(
+      encode_href_segment("a #1?b=100%.ts"),
+      "a%20%231%3Fb=100%25.ts"
+    );
+    assert_eq!(encode_href_segment("<\"&'>"), "%3C%22%26%27%3E");
+    assert_eq!(encode_href_segment("c:d.ts"), "c%3Ad.ts");
+    assert_eq!(encode_href_segment("ü.ts"), "%C3%BC.ts");
+  }
+}
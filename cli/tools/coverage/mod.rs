@@ -1,8 +1,13 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
 
 use crate::args::CoverageFlags;
+use crate::args::CoverageOptions;
+use crate::args::CoverageThresholds;
+use crate::args::CoverageType;
 use crate::args::FileFlags;
 use crate::args::Flags;
+use crate::args::DEFAULT_COVERAGE_EXCLUDE;
+use crate::args::DEFAULT_COVERAGE_INCLUDE;
 use crate::colors;
 use crate::factory::CliFactory;
 use crate::tools::fmt::format_json;
@@ -22,6 +27,8 @@ use deno_core::url::Url;
 use deno_core::LocalInspectorSession;
 use deno_core::ModuleCode;
 use regex::Regex;
+use serde::Serialize;
+use std::collections::BTreeMap;
 use std::fs;
 use std::fs::File;
 use std::io::BufWriter;
@@ -33,6 +40,8 @@ use std::path::PathBuf;
 use text_lines::TextLines;
 use uuid::Uuid;
 
+mod cobertura;
+mod html;
 mod json_types;
 mod merge;
 mod range_tree;
@@ -170,6 +179,51 @@ struct CoverageReport {
   output: Option<PathBuf>,
 }
 
+/// Line, branch and function totals of one or more coverage reports.
+#[derive(Clone, Copy, Default)]
+struct CoverageStats {
+  lines_found: usize,
+  lines_hit: usize,
+  branches_found: usize,
+  branches_hit: usize,
+  functions_found: usize,
+  functions_hit: usize,
+}
+
+impl CoverageStats {
+  fn from_report(coverage_report: &CoverageReport) -> Self {
+    Self {
+      lines_found: coverage_report.found_lines.len(),
+      lines_hit: coverage_report
+        .found_lines
+        .iter()
+        .filter(|(_, count)| *count > 0)
+        .count(),
+      branches_found: coverage_report.branches.len(),
+      branches_hit: coverage_report
+        .branches
+        .iter()
+        .filter(|b| b.is_hit)
+        .count(),
+      functions_found: coverage_report.named_functions.len(),
+      functions_hit: coverage_report
+        .named_functions
+        .iter()
+        .filter(|f| f.execution_count > 0)
+        .count(),
+    }
+  }
+
+  fn add(&mut self, other: &CoverageStats) {
+    self.lines_found += other.lines_found;
+    self.lines_hit += other.lines_hit;
+    self.branches_found += other.branches_found;
+    self.branches_hit += other.branches_hit;
+    self.functions_found += other.functions_found;
+    self.functions_hit += other.functions_hit;
+  }
+}
+
 fn generate_coverage_report(
   script_coverage: &ScriptCoverage,
   script_source: String,
@@ -360,17 +414,22 @@ fn generate_coverage_report(
   coverage_report
 }
 
-enum CoverageReporterKind {
-  Pretty,
-  Lcov,
-}
-
 fn create_reporter(
-  kind: CoverageReporterKind,
+  kind: &CoverageType,
+  output: Option<PathBuf>,
 ) -> Box<dyn CoverageReporter + Send> {
   match kind {
-    CoverageReporterKind::Lcov => Box::new(LcovCoverageReporter::new()),
-    CoverageReporterKind::Pretty => Box::new(PrettyCoverageReporter::new()),
+    CoverageType::Lcov => Box::new(LcovCoverageReporter::new()),
+    CoverageType::Pretty => Box::new(PrettyCoverageReporter::new()),
+    CoverageType::Html => Box::new(html::HtmlCoverageReporter::new(
+      output.unwrap_or_else(|| PathBuf::from("html_cov")),
+    )),
+    CoverageType::Cobertura => {
+      Box::new(cobertura::CoberturaCoverageReporter::new(output))
+    }
+    CoverageType::JsonSummary => {
+      Box::new(JsonSummaryCoverageReporter::new(output))
+    }
   }
 }
 
@@ -381,7 +440,7 @@ trait CoverageReporter {
     file_text: &str,
   ) -> Result<(), AnyError>;
 
-  fn done(&mut self);
+  fn done(&mut self) -> Result<(), AnyError>;
 }
 
 struct LcovCoverageReporter {}
@@ -483,7 +542,103 @@ impl CoverageReporter for LcovCoverageReporter {
     Ok(())
   }
 
-  fn done(&mut self) {}
+  fn done(&mut self) -> Result<(), AnyError> {
+    Ok(())
+  }
+}
+
+#[derive(Serialize)]
+struct JsonCoverageMetric {
+  found: usize,
+  hit: usize,
+  /// Code without any lines, branches or functions is fully covered.
+  percent: f64,
+}
+
+impl JsonCoverageMetric {
+  fn new(hit: usize, found: usize) -> Self {
+    Self {
+      found,
+      hit,
+      percent: ratio_percent(hit, found).unwrap_or(100.0),
+    }
+  }
+}
+
+#[derive(Serialize)]
+struct JsonCoverageSummaryEntry {
+  lines: JsonCoverageMetric,
+  branches: JsonCoverageMetric,
+  functions: JsonCoverageMetric,
+}
+
+impl From<&CoverageStats> for JsonCoverageSummaryEntry {
+  fn from(stats: &CoverageStats) -> Self {
+    Self {
+      lines: JsonCoverageMetric::new(stats.lines_hit, stats.lines_found),
+      branches: JsonCoverageMetric::new(
+        stats.branches_hit,
+        stats.branches_found,
+      ),
+      functions: JsonCoverageMetric::new(
+        stats.functions_hit,
+        stats.functions_found,
+      ),
+    }
+  }
+}
+
+#[derive(Serialize)]
+struct JsonCoverageSummary {
+  total: JsonCoverageSummaryEntry,
+  files: BTreeMap<String, JsonCoverageSummaryEntry>,
+}
+
+struct JsonSummaryCoverageReporter {
+  output: Option<PathBuf>,
+  total: CoverageStats,
+  files: BTreeMap<String, CoverageStats>,
+}
+
+impl JsonSummaryCoverageReporter {
+  pub fn new(output: Option<PathBuf>) -> JsonSummaryCoverageReporter {
+    JsonSummaryCoverageReporter {
+      output,
+      total: CoverageStats::default(),
+      files: BTreeMap::new(),
+    }
+  }
+}
+
+impl CoverageReporter for JsonSummaryCoverageReporter {
+  fn report(
+    &mut self,
+    coverage_report: &CoverageReport,
+    _file_text: &str,
+  ) -> Result<(), AnyError> {
+    let stats = CoverageStats::from_report(coverage_report);
+    self.total.add(&stats);
+    self.files.insert(coverage_report.url.to_string(), stats);
+    Ok(())
+  }
+
+  fn done(&mut self) -> Result<(), AnyError> {
+    let summary = JsonCoverageSummary {
+      total: (&self.total).into(),
+      files: self
+        .files
+        .iter()
+        .map(|(url, stats)| (url.clone(), stats.into()))
+        .collect(),
+    };
+    let mut json = serde_json::to_string_pretty(&summary)?;
+    json.push('\n');
+    match &self.output {
+      Some(path) => fs::write(path, json)?,
+      None => io::stdout().write_all(json.as_bytes())?,
+    }
+    Ok(())
+  }
 }
 
 struct PrettyCoverageReporter {}
@@ -556,7 +711,59 @@ impl CoverageReporter for PrettyCoverageReporter {
     Ok(())
   }
 
-  fn done(&mut self) {}
+  fn done(&mut self) -> Result<(), AnyError> {
+    Ok(())
+  }
+}
+
+/// Returns the closest common directory of the local files, and the path
+/// segments of every file. Local files are relative to the common directory,
+/// remote files are placed in a directory named after their host.
+fn relative_paths(
+  urls: &[&ModuleSpecifier],
+) -> (Option<PathBuf>, Vec<Vec<String>>) {
+  let file_paths = urls
+    .iter()
+    .map(|url| url.to_file_path().ok())
+    .collect::<Vec<_>>();
+  let mut root: Option<PathBuf> = None;
+  for path in file_paths.iter().flatten() {
+    let parent = path.parent().unwrap_or(path);
+    root = Some(match root {
+      None => parent.to_path_buf(),
+      Some(root) => root
+        .ancestors()
+        .find(|ancestor| parent.starts_with(ancestor))
+        .map(|ancestor| ancestor.to_path_buf())
+        .unwrap_or_default(),
+    });
+  }
+
+  let paths = urls
+    .iter()
+    .zip(file_paths)
+    .map(|(url, path)| match (path, &root) {
+      (Some(path), Some(root)) => path
+        .strip_prefix(root)
+        .unwrap_or(&path)
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().to_string())
+        .collect(),
+      _ => {
+        let mut segments = vec![url.host_str().unwrap_or("_").to_string()];
+        segments.extend(
+          url
+            .path_segments()
+            .into_iter()
+            .flatten()
+            .filter(|s| !s.is_empty())
+            .map(|s| s.to_string()),
+        );
+        segments
+      }
+    })
+    .collect();
+  (root, paths)
 }
 
 fn collect_coverages(
@@ -629,18 +836,188 @@ pub async fn cover_files(
   }
 
   let factory = CliFactory::from_flags(flags).await?;
+  let coverage_options = factory.cli_options().resolve_coverage_options()?;
+
+  let mut reporter =
+    create_reporter(&coverage_flags.r#type, coverage_flags.output.clone());
+
+  let out_mode = match coverage_flags.output {
+    // The html reporter writes a directory of its own.
+    Some(_) if coverage_flags.r#type == CoverageType::Html => None,
+    Some(ref path) => match File::create(path) {
+      Ok(_) => Some(PathBuf::from(path)),
+      Err(e) => {
+        return Err(anyhow!("Failed to create output file: {}", e));
+      }
+    },
+    None => None,
+  };
+
+  let coverage_reports = generate_coverage_reports(
+    &factory,
+    coverage_flags.files,
+    coverage_flags.include,
+    coverage_flags.exclude,
+    &out_mode,
+  )
+  .await?;
+
+  for (coverage_report, file_text) in &coverage_reports {
+    reporter.report(coverage_report, file_text)?;
+  }
+  reporter.done()?;
+
+  check_coverage_thresholds(&coverage_options, &coverage_reports)
+}
+
+/// Checks the coverage profiles that `deno test --coverage` wrote to
+/// `coverage_dir` against the thresholds of the config file, if any.
+pub async fn check_test_coverage(
+  flags: Flags,
+  coverage_dir: &str,
+) -> Result<(), AnyError> {
+  let factory = CliFactory::from_flags(flags).await?;
+  let coverage_options = factory.cli_options().resolve_coverage_options()?;
+  if !coverage_options.has_thresholds() {
+    return Ok(());
+  }
+
+  let coverage_reports = generate_coverage_reports(
+    &factory,
+    FileFlags {
+      include: vec![PathBuf::from(coverage_dir)],
+      ignore: vec![],
+    },
+    vec![DEFAULT_COVERAGE_INCLUDE.to_string()],
+    vec![DEFAULT_COVERAGE_EXCLUDE.to_string()],
+    &None,
+  )
+  .await?;
+  check_coverage_thresholds(&coverage_options, &coverage_reports)
+}
+
+/// Checks the coverage reports against the thresholds of the config file.
+/// Files that an override matches are checked one by one against the
+/// thresholds of the override, and the global thresholds are checked against
+/// the totals of all other files.
+fn check_coverage_thresholds(
+  coverage_options: &CoverageOptions,
+  coverage_reports: &[(CoverageReport, String)],
+) -> Result<(), AnyError> {
+  let mut failures = Vec::new();
+  let mut totals = CoverageStats::default();
+  for (coverage_report, _) in coverage_reports {
+    let stats = CoverageStats::from_report(coverage_report);
+    let override_thresholds = coverage_report
+      .url
+      .to_file_path()
+      .ok()
+      .and_then(|path| coverage_options.override_thresholds_for(&path));
+    match override_thresholds {
+      Some(thresholds) => check_stats_thresholds(
+        coverage_report.url.as_str(),
+        &thresholds,
+        &stats,
+        &mut failures,
+      ),
+      None => totals.add(&stats),
+    }
+  }
+  check_stats_thresholds(
+    "All files",
+    &coverage_options.thresholds,
+    &totals,
+    &mut failures,
+  );
+
+  if failures.is_empty() {
+    return Ok(());
+  }
+  Err(generic_error(format!(
+    "Coverage thresholds were not met:\n{}",
+    failures.join("\n")
+  )))
+}
+
+fn check_stats_thresholds(
+  name: &str,
+  thresholds: &CoverageThresholds,
+  stats: &CoverageStats,
+  failures: &mut Vec<String>,
+) {
+  let metrics = [
+    (
+      "lines",
+      thresholds.lines,
+      stats.lines_hit,
+      stats.lines_found,
+    ),
+    (
+      "branches",
+      thresholds.branches,
+      stats.branches_hit,
+      stats.branches_found,
+    ),
+    (
+      "functions",
+      thresholds.functions,
+      stats.functions_hit,
+      stats.functions_found,
+    ),
+  ];
+  for (metric, threshold, hit, found) in metrics {
+    let (Some(threshold), Some(ratio)) = (threshold, ratio_percent(hit, found))
+    else {
+      continue;
+    };
+    if ratio < threshold {
+      failures.push(format!(
+        "  {name}: {metric} {ratio:.3}% ({hit}/{found}) is below {threshold}%"
+      ));
+    }
+  }
+}
+
+/// Returns the hit and found branches of a report, by zero based line index.
+fn branches_by_line(
+  coverage_report: &CoverageReport,
+) -> BTreeMap<usize, (usize, usize)> {
+  let mut branches: BTreeMap<usize, (usize, usize)> = BTreeMap::new();
+  for branch in &coverage_report.branches {
+    let entry = branches.entry(branch.line_index).or_default();
+    if branch.is_hit {
+      entry.0 += 1;
+    }
+    entry.1 += 1;
+  }
+  branches
+}
+
+/// Returns the percentage of `hit` in `found`, or `None` if nothing was
+/// found.
+fn ratio_percent(hit: usize, found: usize) -> Option<f64> {
+  if found == 0 {
+    None
+  } else {
+    Some(hit as f64 / found as f64 * 100.0)
+  }
+}
+
+async fn generate_coverage_reports(
+  factory: &CliFactory,
+  files: FileFlags,
+  include: Vec<String>,
+  exclude: Vec<String>,
+  out_mode: &Option<PathBuf>,
+) -> Result<Vec<(CoverageReport, String)>, AnyError> {
   let root_dir_url = factory.npm_resolver().await?.root_dir_url();
   let file_fetcher = factory.file_fetcher()?;
   let cli_options = factory.cli_options();
   let emitter = factory.emitter()?;
 
-  let script_coverages = collect_coverages(coverage_flags.files)?;
-  let script_coverages = filter_coverages(
-    script_coverages,
-    coverage_flags.include,
-    coverage_flags.exclude,
-    root_dir_url.as_str(),
-  );
+  let script_coverages = collect_coverages(files)?;
+  let script_coverages =
+    filter_coverages(script_coverages, include, exclude, root_dir_url.as_str());
 
   let proc_coverages: Vec<_> = script_coverages
     .into_iter()
@@ -654,24 +1031,7 @@ pub async fn cover_files(
     vec![]
   };
 
-  let reporter_kind = if coverage_flags.lcov {
-    CoverageReporterKind::Lcov
-  } else {
-    CoverageReporterKind::Pretty
-  };
-
-  let mut reporter = create_reporter(reporter_kind);
-
-  let out_mode = match coverage_flags.output {
-    Some(ref path) => match File::create(path) {
-      Ok(_) => Some(PathBuf::from(path)),
-      Err(e) => {
-        return Err(anyhow!("Failed to create output file: {}", e));
-      }
-    },
-    None => None,
-  };
-
+  let mut coverage_reports = Vec::with_capacity(script_coverages.len());
   for script_coverage in script_coverages {
     let module_specifier = deno_core::resolve_url_or_path(
       &script_coverage.url,
@@ -729,15 +1089,53 @@ pub async fn cover_files(
       &script_coverage,
       transpiled_code.as_str().to_owned(),
       &source_map,
-      &out_mode,
+      out_mode,
     );
 
     if !coverage_report.found_lines.is_empty() {
-      reporter.report(&coverage_report, &original_source)?;
+      coverage_reports.push((coverage_report, original_source.to_string()));
     }
   }
 
-  reporter.done();
+  Ok(coverage_reports)
+}
 
-  Ok(())
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn relative_paths_with_common_root() {
+    let file = |url: &str| ModuleSpecifier::parse(url).unwrap();
+    let prefix = if cfg!(windows) {
+      "file:///C:"
+    } else {
+      "file://"
+    };
+    let files = vec![
+      file(&format!("{prefix}/project/src/mod.ts")),
+      file(&format!("{prefix}/project/src/util/fs.ts")),
+      file(&format!("{prefix}/project/main.ts")),
+      file("https://deno.land/std/path/mod.ts"),
+    ];
+    let (root, paths) = relative_paths(&files.iter().collect::<Vec<_>>());
+    assert_eq!(
+      root,
+      Some(
+        ModuleSpecifier::parse(&format!("{prefix}/project"))
+          .unwrap()
+          .to_file_path()
+          .unwrap()
+      )
+    );
+    assert_eq!(
+      paths,
+      vec![
+        vec!["src", "mod.ts"],
+        vec!["src", "util", "fs.ts"],
+        vec!["main.ts"],
+        vec!["deno.land", "std", "path", "mod.ts"],
+      ]
+    );
+  }
 }
@@ -1,7 +1,10 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
 
 //! This module provides file formatting utilities using
-//! [`dprint-plugin-typescript`](https://github.com/dprint/dprint-plugin-typescript).
+//! [`dprint-plugin-typescript`](https://github.com/dprint/dprint-plugin-typescript),
+//! [`malva`](https://github.com/g-plane/malva),
+//! [`markup_fmt`](https://github.com/g-plane/markup_fmt) and
+//! [`pretty_yaml`](https://github.com/g-plane/pretty_yaml).
 //!
 //! At the moment it is only consumed using CLI but in
 //! the future it can be easily extended to provide
@@ -14,6 +17,7 @@ use crate::args::FmtFlags;
 use crate::args::FmtOptions;
 use crate::args::FmtOptionsConfig;
 use crate::args::ProseWrap;
+use crate::args::UnstableFmtOptions;
 use crate::colors;
 use crate::factory::CliFactory;
 use crate::util::diff::diff;
@@ -33,6 +37,7 @@ use deno_core::unsync::spawn_blocking;
 use log::debug;
 use log::info;
 use log::warn;
+use std::borrow::Cow;
 use std::fs;
 use std::io::stdin;
 use std::io::stdout;
@@ -75,13 +80,14 @@ pub async fn format(flags: Flags, fmt_flags: FmtFlags) -> Result<(), AnyError> {
           let cli_options = factory.cli_options();
           let fmt_options = cli_options.resolve_fmt_options(fmt_flags)?;
           let files =
-            collect_fmt_files(&fmt_options.files).and_then(|files| {
-              if files.is_empty() {
-                Err(generic_error("No target files found."))
-              } else {
-                Ok(files)
-              }
-            })?;
+            collect_fmt_files(&fmt_options.files, fmt_options.unstable)
+              .and_then(|files| {
+                if files.is_empty() {
+                  Err(generic_error("No target files found."))
+                } else {
+                  Ok(files)
+                }
+              })?;
           _ = sender.send(files.clone());
           let refmt_files = if let Some(paths) = changed_paths {
             if fmt_options.check {
@@ -111,13 +117,14 @@ pub async fn format(flags: Flags, fmt_flags: FmtFlags) -> Result<(), AnyError> {
     let factory = CliFactory::from_flags(flags).await?;
     let cli_options = factory.cli_options();
     let fmt_options = cli_options.resolve_fmt_options(fmt_flags)?;
-    let files = collect_fmt_files(&fmt_options.files).and_then(|files| {
-      if files.is_empty() {
-        Err(generic_error("No target files found."))
-      } else {
-        Ok(files)
-      }
-    })?;
+    let files = collect_fmt_files(&fmt_options.files, fmt_options.unstable)
+      .and_then(|files| {
+        if files.is_empty() {
+          Err(generic_error("No target files found."))
+        } else {
+          Ok(files)
+        }
+      })?;
     format_files(factory, fmt_options, files).await?;
   }
 
@@ -131,24 +138,39 @@ async fn format_files(
 ) -> Result<(), AnyError> {
   let caches = factory.caches()?;
   let check = fmt_options.check;
+  // markdown code blocks are formatted differently depending on the enabled
+  // unstable languages
   let incremental_cache = Arc::new(IncrementalCache::new(
     caches.fmt_incremental_cache_db(),
-    &fmt_options.options,
+    &(&fmt_options.options, fmt_options.unstable),
     &paths,
   ));
   if check {
-    check_source_files(paths, fmt_options.options, incremental_cache.clone())
-      .await?;
+    check_source_files(
+      paths,
+      fmt_options.options,
+      fmt_options.unstable,
+      incremental_cache.clone(),
+    )
+    .await?;
   } else {
-    format_source_files(paths, fmt_options.options, incremental_cache.clone())
-      .await?;
+    format_source_files(
+      paths,
+      fmt_options.options,
+      fmt_options.unstable,
+      incremental_cache.clone(),
+    )
+    .await?;
   }
   incremental_cache.wait_completion().await;
   Ok(())
 }
 
-fn collect_fmt_files(files: &FilesConfig) -> Result<Vec<PathBuf>, AnyError> {
-  FileCollector::new(is_supported_ext_fmt)
+fn collect_fmt_files(
+  files: &FilesConfig,
+  unstable_options: UnstableFmtOptions,
+) -> Result<Vec<PathBuf>, AnyError> {
+  FileCollector::new(|path| is_supported_ext_fmt(path, unstable_options))
     .ignore_git_folder()
     .ignore_node_modules()
     .ignore_vendor_folder()
@@ -157,10 +179,12 @@ fn collect_fmt_files(files: &FilesConfig) -> Result<Vec<PathBuf>, AnyError> {
 }
 
 /// Formats markdown (using <https://github.com/dprint/dprint-plugin-markdown>) and its code blocks
-/// (ts/tsx, js/jsx).
+/// (ts/tsx, js/jsx, json/jsonc, and css/scss/sass/less, html and yaml when
+/// enabled).
 fn format_markdown(
   file_text: &str,
   fmt_options: &FmtOptionsConfig,
+  unstable_options: &UnstableFmtOptions,
 ) -> Result<Option<String>, AnyError> {
   let markdown_config = get_resolved_markdown_config(fmt_options);
   dprint_plugin_markdown::format_text(
@@ -168,21 +192,15 @@ fn format_markdown(
     &markdown_config,
     move |tag, text, line_width| {
       let tag = tag.to_lowercase();
-      if matches!(
-        tag.as_str(),
-        "ts"
-          | "tsx"
-          | "js"
-          | "jsx"
-          | "cjs"
-          | "cts"
-          | "mjs"
-          | "mts"
-          | "javascript"
-          | "typescript"
-          | "json"
-          | "jsonc"
-      ) {
+      let is_supported = match tag.as_str() {
+        "ts" | "tsx" | "js" | "jsx" | "cjs" | "cts" | "mjs" | "mts"
+        | "javascript" | "typescript" | "json" | "jsonc" => true,
+        "css" | "scss" | "sass" | "less" => unstable_options.css,
+        "html" => unstable_options.html,
+        "yaml" | "yml" => unstable_options.yaml,
+        _ => false,
+      };
+      if is_supported {
         // It's important to tell dprint proper file extension, otherwise
         // it might parse the file twice.
         let extension = match tag.as_str() {
@@ -191,21 +209,26 @@ fn format_markdown(
           rest => rest,
         };
 
-        if matches!(extension, "json" | "jsonc") {
-          let mut json_config = get_resolved_json_config(fmt_options);
-          json_config.line_width = line_width;
-          dprint_plugin_json::format_text(text, &json_config)
-        } else {
-          let fake_filename =
-            PathBuf::from(format!("deno_fmt_stdin.{extension}"));
-          let mut codeblock_config =
-            get_resolved_typescript_config(fmt_options);
-          codeblock_config.line_width = line_width;
-          dprint_plugin_typescript::format_text(
-            &fake_filename,
-            text,
-            &codeblock_config,
-          )
+        let mut codeblock_options = fmt_options.clone();
+        codeblock_options.line_width = Some(line_width);
+        let fake_filename =
+          PathBuf::from(format!("deno_fmt_stdin.{extension}"));
+        match extension {
+          "json" | "jsonc" => format_json(text, &codeblock_options),
+          "css" | "scss" | "sass" | "less" => {
+            format_css(&fake_filename, text, &codeblock_options)
+          }
+          "html" => format_html(&fake_filename, text, &codeblock_options),
+          "yaml" | "yml" => format_yaml(text, &codeblock_options),
+          _ => {
+            let codeblock_config =
+              get_resolved_typescript_config(&codeblock_options);
+            dprint_plugin_typescript::format_text(
+              &fake_filename,
+              text,
+              &codeblock_config,
+            )
+          }
         }
       } else {
         Ok(None)
@@ -214,6 +237,96 @@ fn format_markdown(
   )
 }
 
+/// Formats CSS, SCSS, Sass and Less using <https://github.com/g-plane/malva>.
+pub fn format_css(
+  file_path: &Path,
+  file_text: &str,
+  fmt_options: &FmtOptionsConfig,
+) -> Result<Option<String>, AnyError> {
+  let syntax = match get_extension(file_path).as_deref() {
+    Some("scss") => malva::Syntax::Scss,
+    Some("sass") => malva::Syntax::Sass,
+    Some("less") => malva::Syntax::Less,
+    _ => malva::Syntax::Css,
+  };
+  let formatted = malva::format_text(
+    file_text,
+    syntax,
+    &get_resolved_malva_config(fmt_options),
+  )
+  .map_err(|err| generic_error(err.to_string()))?;
+  Ok(changed_text(file_text, formatted))
+}
+
+/// Formats HTML using <https://github.com/g-plane/markup_fmt>, along with the
+/// contents of its `<script>` and `<style>` elements.
+pub fn format_html(
+  file_path: &Path,
+  file_text: &str,
+  fmt_options: &FmtOptionsConfig,
+) -> Result<Option<String>, AnyError> {
+  let formatted = markup_fmt::format_text(
+    file_text,
+    markup_fmt::Language::Html,
+    &get_resolved_markup_fmt_config(fmt_options),
+    |path, text, print_width| {
+      let mut embedded_options = fmt_options.clone();
+      embedded_options.line_width = Some(print_width as u32);
+      // the path only tells the language of the embedded code, e.g.
+      // `script.ts` or `style.css`
+      let ext = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or("ts");
+      let embedded_path = file_path.with_extension(ext);
+      let formatted = match ext {
+        "css" | "scss" | "sass" | "less" => {
+          format_css(&embedded_path, text, &embedded_options)?
+        }
+        "json" | "jsonc" => format_json(text, &embedded_options)?,
+        _ => dprint_plugin_typescript::format_text(
+          &embedded_path,
+          text,
+          &get_resolved_typescript_config(&embedded_options),
+        )?,
+      };
+      Ok::<_, AnyError>(match formatted {
+        Some(formatted) => Cow::Owned(formatted),
+        None => Cow::Borrowed(text),
+      })
+    },
+  )
+  .map_err(|err| match err {
+    markup_fmt::FormatError::Syntax(err) => generic_error(err.to_string()),
+    markup_fmt::FormatError::External(errors) => {
+      let messages = errors.iter().map(|err| format!("{err:#}"));
+      generic_error(messages.collect::<Vec<_>>().join("\n\n"))
+    }
+  })?;
+  Ok(changed_text(file_text, formatted))
+}
+
+/// Formats YAML using <https://github.com/g-plane/pretty_yaml>.
+pub fn format_yaml(
+  file_text: &str,
+  fmt_options: &FmtOptionsConfig,
+) -> Result<Option<String>, AnyError> {
+  let formatted = pretty_yaml::format_text(
+    file_text,
+    &get_resolved_pretty_yaml_config(fmt_options),
+  )
+  .map_err(|err| generic_error(err.to_string()))?;
+  Ok(changed_text(file_text, formatted))
+}
+
+fn changed_text(file_text: &str, formatted: String) -> Option<String> {
+  if formatted == file_text {
+    None
+  } else {
+    Some(formatted)
+  }
+}
+
 /// Formats JSON and JSONC using the rules provided by .deno()
 /// of configuration builder of <https://github.com/dprint/dprint-plugin-json>.
 /// See <https://github.com/dprint/dprint-plugin-json/blob/cfa1052dbfa0b54eb3d814318034cdc514c813d7/src/configuration/builder.rs#L87> for configuration.
@@ -225,26 +338,51 @@ pub fn format_json(
   dprint_plugin_json::format_text(file_text, &config)
 }
 
-/// Formats a single TS, TSX, JS, JSX, JSONC, JSON, or MD file.
+/// Formats a single TS, TSX, JS, JSX, JSONC, JSON or MD file, or a CSS, SCSS,
+/// Sass, Less, HTML or YAML file if formatting it is enabled.
 pub fn format_file(
   file_path: &Path,
   file_text: &str,
   fmt_options: &FmtOptionsConfig,
+  unstable_options: &UnstableFmtOptions,
 ) -> Result<Option<String>, AnyError> {
   let ext = get_extension(file_path).unwrap_or_default();
   if matches!(
     ext.as_str(),
     "md" | "mkd" | "mkdn" | "mdwn" | "mdown" | "markdown"
   ) {
-    format_markdown(file_text, fmt_options)
+    format_markdown(file_text, fmt_options, unstable_options)
   } else if matches!(ext.as_str(), "json" | "jsonc") {
     format_json(file_text, fmt_options)
+  } else if matches!(ext.as_str(), "css" | "scss" | "sass" | "less") {
+    ensure_unstable_language(unstable_options.css, "CSS", "css")?;
+    format_css(file_path, file_text, fmt_options)
+  } else if matches!(ext.as_str(), "html" | "htm") {
+    ensure_unstable_language(unstable_options.html, "HTML", "html")?;
+    format_html(file_path, file_text, fmt_options)
+  } else if matches!(ext.as_str(), "yaml" | "yml") {
+    ensure_unstable_language(unstable_options.yaml, "YAML", "yaml")?;
+    format_yaml(file_text, fmt_options)
   } else {
     let config = get_resolved_typescript_config(fmt_options);
     dprint_plugin_typescript::format_text(file_path, file_text, &config)
   }
 }
 
+fn ensure_unstable_language(
+  enabled: bool,
+  language: &str,
+  name: &str,
+) -> Result<(), AnyError> {
+  if enabled {
+    Ok(())
+  } else {
+    bail!(
+      "Formatting {language} files is unstable. Enable it with --unstable-{name} or by adding \"fmt-{name}\" to the \"unstable\" option in the config file."
+    )
+  }
+}
+
 pub fn format_parsed_source(
   parsed_source: &ParsedSource,
   fmt_options: &FmtOptionsConfig,
@@ -258,6 +396,7 @@ pub fn format_parsed_source(
 async fn check_source_files(
   paths: Vec<PathBuf>,
   fmt_options: FmtOptionsConfig,
+  unstable_options: UnstableFmtOptions,
   incremental_cache: Arc<IncrementalCache>,
 ) -> Result<(), AnyError> {
   let not_formatted_files_count = Arc::new(AtomicUsize::new(0));
@@ -278,7 +417,8 @@ async fn check_source_files(
         return Ok(());
       }
 
-      match format_file(&file_path, &file_text, &fmt_options) {
+      match format_file(&file_path, &file_text, &fmt_options, &unstable_options)
+      {
         Ok(Some(formatted_text)) => {
           not_formatted_files_count.fetch_add(1, Ordering::Relaxed);
           let _g = output_lock.lock();
@@ -339,6 +479,7 @@ async fn check_source_files(
 async fn format_source_files(
   paths: Vec<PathBuf>,
   fmt_options: FmtOptionsConfig,
+  unstable_options: UnstableFmtOptions,
   incremental_cache: Arc<IncrementalCache>,
 ) -> Result<(), AnyError> {
   let formatted_files_count = Arc::new(AtomicUsize::new(0));
@@ -361,7 +502,9 @@ async fn format_source_files(
         &file_path,
         &file_contents.text,
         &fmt_options,
-        format_file,
+        |file_path, file_text, fmt_options| {
+          format_file(file_path, file_text, fmt_options, &unstable_options)
+        },
       ) {
         Ok(Some(formatted_text)) => {
           incremental_cache.update_file(&file_path, &formatted_text);
@@ -478,7 +621,12 @@ fn format_stdin(fmt_options: FmtOptions, ext: &str) -> Result<(), AnyError> {
     bail!("Failed to read from stdin");
   }
   let file_path = PathBuf::from(format!("_stdin.{ext}"));
-  let formatted_text = format_file(&file_path, &source, &fmt_options.options)?;
+  let formatted_text = format_file(
+    &file_path,
+    &source,
+    &fmt_options.options,
+    &fmt_options.unstable,
+  )?;
   if fmt_options.check {
     if formatted_text.is_some() {
       println!("Not formatted stdin");
@@ -586,6 +734,77 @@ fn get_resolved_json_config(
   builder.build()
 }
 
+fn get_resolved_malva_config(
+  options: &FmtOptionsConfig,
+) -> malva::config::FormatOptions {
+  use malva::config::*;
+
+  FormatOptions {
+    layout: LayoutOptions {
+      print_width: options.line_width.unwrap_or(80) as usize,
+      use_tabs: options.use_tabs.unwrap_or_default(),
+      indent_width: options.indent_width.unwrap_or(2) as usize,
+      line_break: LineBreak::Lf,
+    },
+    language: LanguageOptions {
+      quotes: if options.single_quote == Some(true) {
+        Quotes::PreferSingle
+      } else {
+        Quotes::PreferDouble
+      },
+      ignore_comment_directive: "deno-fmt-ignore".into(),
+      ..Default::default()
+    },
+  }
+}
+
+fn get_resolved_markup_fmt_config(
+  options: &FmtOptionsConfig,
+) -> markup_fmt::config::FormatOptions {
+  use markup_fmt::config::*;
+
+  FormatOptions {
+    layout: LayoutOptions {
+      print_width: options.line_width.unwrap_or(80) as usize,
+      use_tabs: options.use_tabs.unwrap_or_default(),
+      indent_width: options.indent_width.unwrap_or(2) as usize,
+      line_break: LineBreak::Lf,
+    },
+    language: LanguageOptions {
+      quotes: if options.single_quote == Some(true) {
+        Quotes::Single
+      } else {
+        Quotes::Double
+      },
+      ..Default::default()
+    },
+  }
+}
+
+fn get_resolved_pretty_yaml_config(
+  options: &FmtOptionsConfig,
+) -> pretty_yaml::config::FormatOptions {
+  use pretty_yaml::config::*;
+
+  // YAML doesn't allow tabs for indentation, so `useTabs` is ignored
+  FormatOptions {
+    layout: LayoutOptions {
+      print_width: options.line_width.unwrap_or(80) as usize,
+      indent_width: options.indent_width.unwrap_or(2) as usize,
+      line_break: LineBreak::Lf,
+    },
+    language: LanguageOptions {
+      quotes: if options.single_quote == Some(true) {
+        Quotes::PreferSingle
+      } else {
+        Quotes::PreferDouble
+      },
+      ignore_comment_directive: "deno-fmt-ignore".into(),
+      ..Default::default()
+    },
+  }
+}
+
 struct FileContents {
   text: String,
   had_bom: bool,
@@ -668,27 +887,19 @@ where
 
 /// This function is similar to is_supported_ext but adds additional extensions
 /// supported by `deno fmt`.
-fn is_supported_ext_fmt(path: &Path) -> bool {
+fn is_supported_ext_fmt(
+  path: &Path,
+  unstable_options: UnstableFmtOptions,
+) -> bool {
   if let Some(ext) = get_extension(path) {
-    matches!(
-      ext.as_str(),
-      "ts"
-        | "tsx"
-        | "js"
-        | "jsx"
-        | "cjs"
-        | "cts"
-        | "mjs"
-        | "mts"
-        | "json"
-        | "jsonc"
-        | "md"
-        | "mkd"
-        | "mkdn"
-        | "mdwn"
-        | "mdown"
-        | "markdown"
-    )
+    match ext.as_str() {
+      "ts" | "tsx" | "js" | "jsx" | "cjs" | "cts" | "mjs" | "mts" | "json"
+      | "jsonc" | "md" | "mkd" | "mkdn" | "mdwn" | "mdown" | "markdown" => true,
+      "css" | "scss" | "sass" | "less" => unstable_options.css,
+      "html" | "htm" => unstable_options.html,
+      "yaml" | "yml" => unstable_options.yaml,
+      _ => false,
+    }
   } else {
     false
   }
@@ -700,29 +911,64 @@ mod test {
 
   #[test]
   fn test_is_supported_ext_fmt() {
-    assert!(!is_supported_ext_fmt(Path::new("tests/subdir/redirects")));
-    assert!(is_supported_ext_fmt(Path::new("README.md")));
-    assert!(is_supported_ext_fmt(Path::new("readme.MD")));
-    assert!(is_supported_ext_fmt(Path::new("readme.mkd")));
-    assert!(is_supported_ext_fmt(Path::new("readme.mkdn")));
-    assert!(is_supported_ext_fmt(Path::new("readme.mdwn")));
-    assert!(is_supported_ext_fmt(Path::new("readme.mdown")));
-    assert!(is_supported_ext_fmt(Path::new("readme.markdown")));
-    assert!(is_supported_ext_fmt(Path::new("lib/typescript.d.ts")));
-    assert!(is_supported_ext_fmt(Path::new("testdata/run/001_hello.js")));
-    assert!(is_supported_ext_fmt(Path::new("testdata/run/002_hello.ts")));
-    assert!(is_supported_ext_fmt(Path::new("foo.jsx")));
-    assert!(is_supported_ext_fmt(Path::new("foo.tsx")));
-    assert!(is_supported_ext_fmt(Path::new("foo.TS")));
-    assert!(is_supported_ext_fmt(Path::new("foo.TSX")));
-    assert!(is_supported_ext_fmt(Path::new("foo.JS")));
-    assert!(is_supported_ext_fmt(Path::new("foo.JSX")));
-    assert!(is_supported_ext_fmt(Path::new("foo.mjs")));
-    assert!(!is_supported_ext_fmt(Path::new("foo.mjsx")));
-    assert!(is_supported_ext_fmt(Path::new("foo.jsonc")));
-    assert!(is_supported_ext_fmt(Path::new("foo.JSONC")));
-    assert!(is_supported_ext_fmt(Path::new("foo.json")));
-    assert!(is_supported_ext_fmt(Path::new("foo.JsON")));
+    let stable = UnstableFmtOptions::default();
+    let unstable = UnstableFmtOptions {
+      css: true,
+      html: true,
+      yaml: true,
+    };
+    assert!(!is_supported_ext_fmt(
+      Path::new("tests/subdir/redirects"),
+      stable
+    ));
+    assert!(is_supported_ext_fmt(Path::new("README.md"), stable));
+    assert!(is_supported_ext_fmt(Path::new("readme.MD"), stable));
+    assert!(is_supported_ext_fmt(Path::new("readme.mkd"), stable));
+    assert!(is_supported_ext_fmt(Path::new("readme.mkdn"), stable));
+    assert!(is_supported_ext_fmt(Path::new("readme.mdwn"), stable));
+    assert!(is_supported_ext_fmt(Path::new("readme.mdown"), stable));
+    assert!(is_supported_ext_fmt(Path::new("readme.markdown"), stable));
+    assert!(is_supported_ext_fmt(
+      Path::new("lib/typescript.d.ts"),
+      stable
+    ));
+    assert!(is_supported_ext_fmt(
+      Path::new("testdata/run/001_hello.js"),
+      stable
+    ));
+    assert!(is_supported_ext_fmt(
+      Path::new("testdata/run/002_hello.ts"),
+      stable
+    ));
+    assert!(is_supported_ext_fmt(Path::new("foo.jsx"), stable));
+    assert!(is_supported_ext_fmt(Path::new("foo.tsx"), stable));
+    assert!(is_supported_ext_fmt(Path::new("foo.TS"), stable));
+    assert!(is_supported_ext_fmt(Path::new("foo.TSX"), stable));
+    assert!(is_supported_ext_fmt(Path::new("foo.JS"), stable));
+    assert!(is_supported_ext_fmt(Path::new("foo.JSX"), stable));
+    assert!(is_supported_ext_fmt(Path::new("foo.mjs"), stable));
+    assert!(!is_supported_ext_fmt(Path::new("foo.mjsx"), stable));
+    assert!(is_supported_ext_fmt(Path::new("foo.jsonc"), stable));
+    assert!(is_supported_ext_fmt(Path::new("foo.JSONC"), stable));
+    assert!(is_supported_ext_fmt(Path::new("foo.json"), stable));
+    assert!(is_supported_ext_fmt(Path::new("foo.JsON"), stable));
+    assert!(!is_supported_ext_fmt(Path::new("foo.css"), stable));
+    assert!(is_supported_ext_fmt(Path::new("foo.css"), unstable));
+    assert!(!is_supported_ext_fmt(Path::new("foo.scss"), stable));
+    assert!(is_supported_ext_fmt(Path::new("foo.scss"), unstable));
+    assert!(!is_supported_ext_fmt(Path::new("foo.sass"), stable));
+    assert!(is_supported_ext_fmt(Path::new("foo.sass"), unstable));
+    assert!(!is_supported_ext_fmt(Path::new("foo.less"), stable));
+    assert!(is_supported_ext_fmt(Path::new("foo.less"), unstable));
+    assert!(!is_supported_ext_fmt(Path::new("foo.html"), stable));
+    assert!(is_supported_ext_fmt(Path::new("foo.html"), unstable));
+    assert!(!is_supported_ext_fmt(Path::new("foo.HTM"), stable));
+    assert!(is_supported_ext_fmt(Path::new("foo.HTM"), unstable));
+    assert!(!is_supported_ext_fmt(Path::new("foo.yaml"), stable));
+    assert!(is_supported_ext_fmt(Path::new("foo.yaml"), unstable));
+    assert!(!is_supported_ext_fmt(Path::new("foo.yml"), stable));
+    assert!(is_supported_ext_fmt(Path::new("foo.yml"), unstable));
+    assert!(!is_supported_ext_fmt(Path::new("foo.xml"), stable));
   }
 
   #[test]
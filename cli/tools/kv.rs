@@ -0,0 +1,92 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+
+use std::cell::RefCell;
+use std::io::BufReader;
+use std::io::BufWriter;
+use std::rc::Rc;
+
+use deno_core::anyhow::bail;
+use deno_core::anyhow::Context;
+use deno_core::error::AnyError;
+use deno_core::OpState;
+use deno_runtime::deno_kv::dynamic::MultiBackendDbHandler;
+use deno_runtime::deno_kv::export::export_database;
+use deno_runtime::deno_kv::export::import_database;
+use deno_runtime::deno_kv::sqlite::SqliteDbHandler;
+use deno_runtime::deno_kv::Database;
+use deno_runtime::deno_kv::DatabaseHandler;
+use deno_runtime::permissions::PermissionsContainer;
+
+use crate::args::KvFlags;
+use crate::args::KvSubcommand;
+use crate::colors;
+
+pub async fn kv_command(kv_flags: KvFlags) -> Result<(), AnyError> {
+  // The databases are named explicitly on the command line, so there is
+  // nothing to sandbox here.
+  let mut op_state = OpState::new(0);
+  op_state.put(PermissionsContainer::allow_all());
+  let state = Rc::new(RefCell::new(op_state));
+
+  match kv_flags.subcommand {
+    KvSubcommand::Export { database, output } => {
+      if is_remote_database(&database) {
+        bail!(
+          "Exporting remote databases is not supported, because the remote protocol does not report the expiration times of entries"
+        );
+      }
+      let db = SqliteDbHandler::<PermissionsContainer>::new(None)
+        .open(state, Some(database.clone()))
+        .await?;
+      let count = match output {
+        Some(path) => {
+          let file = std::fs::File::create(&path)
+            .with_context(|| format!("Failed to create {}", path.display()))?;
+          export_database(&db, &mut BufWriter::new(file)).await?
+        }
+        None => {
+          let stdout = std::io::stdout();
+          export_database(&db, &mut BufWriter::new(stdout.lock())).await?
+        }
+      };
+      db.close();
+      log::info!(
+        "{} {} entries from {}",
+        colors::green("Exported"),
+        count,
+        database
+      );
+    }
+    KvSubcommand::Import { database, input } => {
+      // The remote protocol cannot set expiration times.
+      let supports_expiration = !is_remote_database(&database);
+      let handler =
+        MultiBackendDbHandler::remote_or_sqlite::<PermissionsContainer>(None);
+      let db = handler.open(state.clone(), Some(database.clone())).await?;
+      let count = match input {
+        Some(path) => {
+          let file = std::fs::File::open(&path)
+            .with_context(|| format!("Failed to open {}", path.display()))?;
+          import_database(&db, state, BufReader::new(file), supports_expiration)
+            .await?
+        }
+        None => {
+          let stdin = std::io::stdin();
+          import_database(&db, state, stdin.lock(), supports_expiration).await?
+        }
+      };
+      db.close();
+      log::info!(
+        "{} {} entries into {}",
+        colors::green("Imported"),
+        count,
+        database
+      );
+    }
+  }
+  Ok(())
+}
+
+fn is_remote_database(database: &str) -> bool {
+  database.starts_with("https://") || database.starts_with("http://")
+}
@@ -13,14 +13,22 @@ use crate::factory::CliFactory;
 use crate::tools::fmt::run_parallelized;
 use crate::util::file_watcher;
 use crate::util::fs::FileCollector;
+use crate::util::github_actions;
+use crate::util::github_actions::AnnotationLevel;
+use crate::util::github_actions::AnnotationLocation;
 use crate::util::path::is_supported_ext;
+use crate::util::sarif::SarifLevel;
+use crate::util::sarif::SarifLocation;
+use crate::util::sarif::SarifRun;
 use crate::util::sync::AtomicFlag;
 use deno_ast::MediaType;
+use deno_ast::ParsedSource;
 use deno_core::anyhow::bail;
 use deno_core::error::generic_error;
 use deno_core::error::AnyError;
 use deno_core::error::JsStackFrame;
 use deno_core::serde_json;
+use deno_core::url::Url;
 use deno_lint::diagnostic::LintDiagnostic;
 use deno_lint::linter::Linter;
 use deno_lint::linter::LinterBuilder;
@@ -38,6 +46,11 @@ use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::Mutex;
 
+mod fix;
+mod plugins;
+
+pub use plugins::LintPluginHost;
+
 use crate::cache::IncrementalCache;
 
 static STDIN_FILE_NAME: &str = "_stdin.ts";
@@ -47,10 +60,17 @@ fn create_reporter(kind: LintReporterKind) -> Box<dyn LintReporter + Send> {
     LintReporterKind::Pretty => Box::new(PrettyLintReporter::new()),
     LintReporterKind::Json => Box::new(JsonLintReporter::new()),
     LintReporterKind::Compact => Box::new(CompactLintReporter::new()),
+    LintReporterKind::Sarif => Box::new(SarifLintReporter::new()),
+    LintReporterKind::Github => Box::new(GithubLintReporter::new()),
   }
 }
 
 pub async fn lint(flags: Flags, lint_flags: LintFlags) -> Result<(), AnyError> {
+  if lint_flags.fix && lint_flags.is_stdin() {
+    return Err(generic_error(
+      "Lint fix on standard input is not supported.",
+    ));
+  }
   if let Some(watch_flags) = &lint_flags.watch {
     if lint_flags.is_stdin() {
       return Err(generic_error(
@@ -106,7 +126,12 @@ pub async fn lint(flags: Flags, lint_flags: LintFlags) -> Result<(), AnyError> {
       let reporter_kind = lint_options.reporter_kind;
       let reporter_lock = Arc::new(Mutex::new(create_reporter(reporter_kind)));
       let lint_rules = get_config_rules_err_empty(lint_options.rules)?;
-      let r = lint_stdin(lint_rules);
+      let plugins = if lint_options.plugins.is_empty() {
+        None
+      } else {
+        Some(LintPluginHost::new(lint_options.plugins)?)
+      };
+      let r = lint_stdin(lint_rules, plugins);
       let success =
         handle_lint_result(STDIN_FILE_NAME, r, reporter_lock.clone());
       reporter_lock.lock().unwrap().close(1);
@@ -148,7 +173,13 @@ async fn lint_files(
     },
     &paths,
   ));
+  let plugins = if lint_options.plugins.is_empty() {
+    None
+  } else {
+    Some(LintPluginHost::new(lint_options.plugins)?)
+  };
   let target_files_len = paths.len();
+  let fix = lint_options.fix;
   let reporter_kind = lint_options.reporter_kind;
   let reporter_lock =
     Arc::new(Mutex::new(create_reporter(reporter_kind.clone())));
@@ -159,17 +190,25 @@ async fn lint_files(
     let lint_rules = lint_rules.clone();
     let reporter_lock = reporter_lock.clone();
     let incremental_cache = incremental_cache.clone();
+    let plugins = plugins.clone();
     move |file_path| {
       let file_text = fs::read_to_string(&file_path)?;
 
-      // don't bother rechecking this file if it didn't have any diagnostics before
-      if incremental_cache.is_file_same(&file_path, &file_text) {
+      // don't bother rechecking this file if it didn't have any diagnostics
+      // before, unless there are plugins, which may have changed since then
+      if plugins.is_none()
+        && incremental_cache.is_file_same(&file_path, &file_text)
+      {
         return Ok(());
       }
 
-      let r = lint_file(&file_path, file_text, lint_rules);
+      let r = if fix {
+        lint_file_and_fix(&file_path, file_text, lint_rules, plugins.clone())
+      } else {
+        lint_file(&file_path, file_text, lint_rules, plugins.clone())
+      };
       if let Ok((file_diagnostics, file_text)) = &r {
-        if file_diagnostics.is_empty() {
+        if file_diagnostics.is_empty() && plugins.is_none() {
           // update the incremental cache if there were no diagnostics
           incremental_cache.update_file(&file_path, file_text)
         }
@@ -249,42 +288,139 @@ pub fn print_rules_list(json: bool, maybe_rules_tags: Option<Vec<String>>) {
 pub fn create_linter(
   media_type: MediaType,
   rules: Vec<&'static dyn LintRule>,
-) -> Linter {
-  LinterBuilder::default()
+  plugins: Option<LintPluginHost>,
+) -> CliLinter {
+  let linter = LinterBuilder::default()
     .ignore_file_directive("deno-lint-ignore-file")
     .ignore_diagnostic_directive("deno-lint-ignore")
     .media_type(media_type)
     .rules(rules)
-    .build()
+    .build();
+  CliLinter { linter, plugins }
+}
+
+/// A linter for the built-in rules and the rules of the lint plugins.
+pub struct CliLinter {
+  linter: Linter,
+  plugins: Option<LintPluginHost>,
+}
+
+impl CliLinter {
+  pub fn lint(
+    self,
+    file_name: String,
+    source_code: String,
+  ) -> Result<(ParsedSource, Vec<LintDiagnostic>), AnyError> {
+    let Self { linter, plugins } = self;
+    let (parsed_source, mut diagnostics) =
+      linter.lint(file_name, source_code)?;
+    if let Some(plugins) = plugins {
+      add_plugin_diagnostics(&plugins, &parsed_source, &mut diagnostics)?;
+    }
+    Ok((parsed_source, diagnostics))
+  }
+
+  pub fn lint_with_ast(
+    self,
+    parsed_source: &ParsedSource,
+  ) -> Result<Vec<LintDiagnostic>, AnyError> {
+    let Self { linter, plugins } = self;
+    let mut diagnostics = linter.lint_with_ast(parsed_source);
+    if let Some(plugins) = plugins {
+      add_plugin_diagnostics(&plugins, parsed_source, &mut diagnostics)?;
+    }
+    Ok(diagnostics)
+  }
+}
+
+fn add_plugin_diagnostics(
+  plugins: &LintPluginHost,
+  parsed_source: &ParsedSource,
+  diagnostics: &mut Vec<LintDiagnostic>,
+) -> Result<(), AnyError> {
+  plugins.retain_known_ignores(diagnostics);
+  diagnostics.extend(plugins.lint(parsed_source)?);
+  Ok(())
 }
 
 fn lint_file(
   file_path: &Path,
   source_code: String,
   lint_rules: Vec<&'static dyn LintRule>,
+  plugins: Option<LintPluginHost>,
 ) -> Result<(Vec<LintDiagnostic>, String), AnyError> {
   let file_name = file_path.to_string_lossy().to_string();
   let media_type = MediaType::from_path(file_path);
 
-  let linter = create_linter(media_type, lint_rules);
+  let linter = create_linter(media_type, lint_rules, plugins);
 
   let (_, file_diagnostics) = linter.lint(file_name, source_code.clone())?;
 
   Ok((file_diagnostics, source_code))
 }
 
+/// Lints the file and applies the available fixes, re-linting until no more
+/// fixes apply. The fixed file is written back to disk and the diagnostics
+/// that remain are returned.
+fn lint_file_and_fix(
+  file_path: &Path,
+  source_code: String,
+  lint_rules: Vec<&'static dyn LintRule>,
+  plugins: Option<LintPluginHost>,
+) -> Result<(Vec<LintDiagnostic>, String), AnyError> {
+  let file_name = file_path.to_string_lossy().to_string();
+  let media_type = MediaType::from_path(file_path);
+
+  let mut current_text = source_code.clone();
+  let mut count = 0;
+  loop {
+    let linter = create_linter(media_type, lint_rules.clone(), plugins.clone());
+    let (parsed_source, file_diagnostics) =
+      match linter.lint(file_name.clone(), current_text.clone()) {
+        Ok(result) => result,
+        Err(err) if count > 0 => bail!(
+          concat!(
+            "Applying lint fixes to {} produced invalid code. This indicates ",
+            "a bug in the lint fixes. Please report it.\n\n{:#}"
+          ),
+          file_path.display(),
+          err,
+        ),
+        Err(err) => return Err(err),
+      };
+    match fix::apply_fixes(&parsed_source, &file_diagnostics) {
+      Some(fixed_text) => current_text = fixed_text,
+      None => {
+        if current_text != source_code {
+          fs::write(file_path, &current_text)?;
+        }
+        return Ok((file_diagnostics, current_text));
+      }
+    }
+    count += 1;
+    if count == 5 {
+      bail!(
+        "Lint fixes not stable. Bailed after {} tries fixing {}.",
+        count,
+        file_path.display(),
+      );
+    }
+  }
+}
+
 /// Lint stdin and write result to stdout.
 /// Treats input as TypeScript.
 /// Compatible with `--json` flag.
 fn lint_stdin(
   lint_rules: Vec<&'static dyn LintRule>,
+  plugins: Option<LintPluginHost>,
 ) -> Result<(Vec<LintDiagnostic>, String), AnyError> {
   let mut source_code = String::new();
   if stdin().read_to_string(&mut source_code).is_err() {
     return Err(generic_error("Failed to read from stdin"));
   }
 
-  let linter = create_linter(MediaType::TypeScript, lint_rules);
+  let linter = create_linter(MediaType::TypeScript, lint_rules, plugins);
 
   let (_, file_diagnostics) =
     linter.lint(STDIN_FILE_NAME.to_string(), source_code.clone())?;
@@ -522,6 +658,129 @@ impl LintReporter for JsonLintReporter {
   }
 }
 
+/// Lint diagnostics reference files by path, except for standard input.
+fn file_name_to_specifier(file_name: &str) -> Option<Url> {
+  Url::from_file_path(file_name).ok()
+}
+
+fn message_with_hint(d: &LintDiagnostic) -> String {
+  match &d.hint {
+    Some(hint) => format!("{}\n\nhint: {}", d.message, hint),
+    None => d.message.clone(),
+  }
+}
+
+struct SarifLintReporter {
+  run: SarifRun,
+}
+
+impl SarifLintReporter {
+  fn new() -> SarifLintReporter {
+    SarifLintReporter {
+      run: SarifRun::new("deno lint", "https://lint.deno.land"),
+    }
+  }
+}
+
+impl LintReporter for SarifLintReporter {
+  fn visit_diagnostic(&mut self, d: &LintDiagnostic, _source_lines: Vec<&str>) {
+    let location =
+      file_name_to_specifier(&d.filename).map(|specifier| SarifLocation {
+        specifier,
+        start_line: d.range.start.line_index as u64 + 1,
+        start_column: d.range.start.column_index as u64 + 1,
+        end_line: d.range.end.line_index as u64 + 1,
+        end_column: d.range.end.column_index as u64 + 1,
+      });
+    self.run.add_result(
+      &d.code,
+      Some(format!("https://lint.deno.land/#{}", d.code)),
+      SarifLevel::Error,
+      &message_with_hint(d),
+      location.as_ref(),
+    );
+  }
+
+  fn visit_error(&mut self, file_path: &str, err: &AnyError) {
+    self.run.add_error(
+      &format!("Error linting: {err}"),
+      file_name_to_specifier(file_path).as_ref(),
+    );
+  }
+
+  fn close(&mut self, _check_count: usize) {
+    println!("{}", self.run.to_json());
+  }
+}
+
+struct GithubLintReporter {
+  lint_count: u32,
+}
+
+impl GithubLintReporter {
+  fn new() -> GithubLintReporter {
+    GithubLintReporter { lint_count: 0 }
+  }
+}
+
+impl LintReporter for GithubLintReporter {
+  fn visit_diagnostic(&mut self, d: &LintDiagnostic, _source_lines: Vec<&str>) {
+    self.lint_count += 1;
+
+    let location =
+      file_name_to_specifier(&d.filename).map(|specifier| AnnotationLocation {
+        specifier,
+        line: d.range.start.line_index as u64 + 1,
+        column: d.range.start.column_index as u64 + 1,
+        end_line: d.range.end.line_index as u64 + 1,
+        end_column: d.range.end.column_index as u64 + 1,
+      });
+    println!(
+      "{}",
+      github_actions::format_annotation(
+        AnnotationLevel::Error,
+        &d.code,
+        &message_with_hint(d),
+        location.as_ref(),
+      )
+    );
+  }
+
+  fn visit_error(&mut self, file_path: &str, err: &AnyError) {
+    let location =
+      file_name_to_specifier(file_path).map(|specifier| AnnotationLocation {
+        specifier,
+        line: 1,
+        column: 1,
+        end_line: 1,
+        end_column: 1,
+      });
+    println!(
+      "{}",
+      github_actions::format_annotation(
+        AnnotationLevel::Error,
+        "Error linting",
+        &err.to_string(),
+        location.as_ref(),
+      )
+    );
+  }
+
+  fn close(&mut self, check_count: usize) {
+    match self.lint_count {
+      1 => info!("Found 1 problem"),
+      n if n > 1 => info!("Found {} problems", self.lint_count),
+      _ => (),
+    }
+
+    match check_count {
+      n if n <= 1 => info!("Checked {} file", n),
+      n if n > 1 => info!("Checked {} files", n),
+      _ => unreachable!(),
+    }
+  }
+}
+
 fn sort_diagnostics(diagnostics: &mut [LintDiagnostic]) {
   // Sort so that we guarantee a deterministic output which is useful for tests
   diagnostics.sort_by(|a, b| {
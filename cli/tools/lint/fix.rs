@@ -0,0 +1,209 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+
+//! Automatic fixes for `deno lint --fix`.
+//!
+//! The rules only report diagnostics, so the fixes are derived here from the
+//! parsed source. A fix is only applied when it cannot change the behavior of
+//! the program; everything else is left for the user to fix by hand.
+
+use std::collections::HashSet;
+use std::ops::Range;
+
+use deno_ast::swc::ast::ClassMember;
+use deno_ast::swc::ast::ModuleItem;
+use deno_ast::swc::ast::Pat;
+use deno_ast::swc::ast::Program;
+use deno_ast::swc::ast::Stmt;
+use deno_ast::swc::ast::VarDecl;
+use deno_ast::swc::ast::VarDeclKind;
+use deno_ast::swc::visit::noop_visit_type;
+use deno_ast::swc::visit::Visit;
+use deno_ast::swc::visit::VisitWith;
+use deno_ast::ParsedSource;
+use deno_ast::SourceRangedForSpanned;
+use deno_ast::SourceTextInfo;
+use deno_lint::diagnostic::LintDiagnostic;
+
+/// Returns the source text with the fixes for the diagnostics applied, or
+/// `None` if none of the diagnostics can be fixed.
+pub fn apply_fixes(
+  parsed_source: &ParsedSource,
+  diagnostics: &[LintDiagnostic],
+) -> Option<String> {
+  let diagnostic_starts = |code: &str| {
+    diagnostics
+      .iter()
+      .filter(|d| d.code == code)
+      .map(|d| d.range.start.byte_index)
+      .collect::<HashSet<_>>()
+  };
+  let mut collector = FixCollector {
+    text_info: parsed_source.text_info(),
+    prefer_const: diagnostic_starts("prefer-const"),
+    no_extra_semi: diagnostic_starts("no-extra-semi"),
+    changes: Vec::new(),
+  };
+  if collector.prefer_const.is_empty() && collector.no_extra_semi.is_empty() {
+    return None;
+  }
+  let program = parsed_source.program();
+  let program: &Program = &program;
+  program.visit_with(&mut collector);
+  apply_changes(parsed_source.text_info().text_str(), collector.changes)
+}
+
+struct TextChange {
+  range: Range<usize>,
+  new_text: &'static str,
+}
+
+fn apply_changes(text: &str, mut changes: Vec<TextChange>) -> Option<String> {
+  if changes.is_empty() {
+    return None;
+  }
+  changes.sort_by_key(|change| change.range.start);
+  let mut result = String::with_capacity(text.len());
+  let mut last_end = 0;
+  for change in changes {
+    // overlapping changes are applied on the next pass
+    if change.range.start < last_end {
+      continue;
+    }
+    result.push_str(&text[last_end..change.range.start]);
+    result.push_str(change.new_text);
+    last_end = change.range.end;
+  }
+  result.push_str(&text[last_end..]);
+  Some(result)
+}
+
+struct FixCollector<'a> {
+  text_info: &'a SourceTextInfo,
+  /// Start byte indexes of the `prefer-const` diagnostics.
+  prefer_const: HashSet<usize>,
+  /// Start byte indexes of the `no-extra-semi` diagnostics.
+  no_extra_semi: HashSet<usize>,
+  changes: Vec<TextChange>,
+}
+
+impl FixCollector<'_> {
+  fn byte_range(&self, node: &impl SourceRangedForSpanned) -> Range<usize> {
+    let range = node.range();
+    let start = self.text_info.range().start;
+    (range.start - start)..(range.end - start)
+  }
+
+  fn add_change(
+    &mut self,
+    range: Range<usize>,
+    expected: &str,
+    new_text: &'static str,
+  ) {
+    // be defensive and only replace the text that the fix expects
+    if self.text_info.text_str().get(range.clone()) == Some(expected) {
+      self.changes.push(TextChange { range, new_text });
+    }
+  }
+
+  fn check_extra_semi(&mut self, range: Range<usize>) {
+    if self.no_extra_semi.contains(&range.start) {
+      self.add_change(range, ";", "");
+    }
+  }
+}
+
+impl Visit for FixCollector<'_> {
+  noop_visit_type!();
+
+  fn visit_var_decl(&mut self, decl: &VarDecl) {
+    // `let` becomes `const` only when every declarator was reported and has
+    // an initializer. Destructuring patterns are left alone.
+    let fixable = decl.kind == VarDeclKind::Let
+      && !decl.declare
+      && !decl.decls.is_empty()
+      && decl.decls.iter().all(|declarator| {
+        declarator.init.is_some()
+          && match &declarator.name {
+            Pat::Ident(ident) => {
+              let range = self.byte_range(&ident.id);
+              self.prefer_const.contains(&range.start)
+            }
+            _ => false,
+          }
+      });
+    if fixable {
+      let start = self.byte_range(decl).start;
+      self.add_change(start..start + 3, "let", "const");
+    }
+    decl.visit_children_with(self);
+  }
+
+  fn visit_stmts(&mut self, stmts: &[Stmt]) {
+    for stmt in stmts {
+      if let Stmt::Empty(empty) = stmt {
+        self.check_extra_semi(self.byte_range(empty));
+      }
+    }
+    stmts.visit_children_with(self);
+  }
+
+  fn visit_module_items(&mut self, items: &[ModuleItem]) {
+    for item in items {
+      if let ModuleItem::Stmt(Stmt::Empty(empty)) = item {
+        self.check_extra_semi(self.byte_range(empty));
+      }
+    }
+    items.visit_children_with(self);
+  }
+
+  fn visit_class_members(&mut self, members: &[ClassMember]) {
+    for member in members {
+      if let ClassMember::Empty(empty) = member {
+        self.check_extra_semi(self.byte_range(empty));
+      }
+    }
+    members.visit_children_with(self);
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use deno_ast::MediaType;
+  use deno_lint::rules::get_recommended_rules;
+
+  use super::apply_fixes;
+  use crate::tools::lint::create_linter;
+
+  fn fix(source: &str) -> Option<String> {
+    let linter =
+      create_linter(MediaType::TypeScript, get_recommended_rules(), None);
+    let (parsed_source, diagnostics) = linter
+      .lint("file:///a.ts".to_string(), source.to_string())
+      .unwrap();
+    apply_fixes(&parsed_source, &diagnostics)
+  }
+
+  #[test]
+  fn fixes_prefer_const() {
+    assert_eq!(
+      fix("let a = 1, b = 2;\nconsole.log(a, b);\n").as_deref(),
+      Some("const a = 1, b = 2;\nconsole.log(a, b);\n")
+    );
+    // `b` is reassigned, so the declaration has to stay a `let`
+    assert_eq!(fix("let a = 1, b = 2;\nb++;\nconsole.log(a, b);\n"), None);
+  }
+
+  #[test]
+  fn fixes_no_extra_semi() {
+    assert_eq!(
+      fix("function f() {};\nclass A {\n  a = 1;;\n}\nf();\nnew A();\n")
+        .as_deref(),
+      Some("function f() {}\nclass A {\n  a = 1;\n}\nf();\nnew A();\n")
+    );
+  }
+
+  #[test]
+  fn no_fixes() {
+    assert_eq!(fix("const a = 1;\nconsole.log(a);\n"), None);
+  }
+}
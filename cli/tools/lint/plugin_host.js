@@ -0,0 +1,84 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+
+// Runs the rules of lint plugins. This script is executed in a bare JS runtime
+// before the plugin modules are loaded, and is driven from
+// `cli/tools/lint/plugins.rs`.
+
+"use strict";
+
+((globalThis) => {
+  const rules = [];
+
+  function deepFreeze(value) {
+    if (typeof value === "object" && value !== null) {
+      for (const item of Object.values(value)) {
+        deepFreeze(item);
+      }
+      Object.freeze(value);
+    }
+    return value;
+  }
+
+  function register(modules) {
+    for (const { specifier, module } of modules) {
+      const plugin = module.default;
+      if (
+        typeof plugin !== "object" || plugin === null ||
+        typeof plugin.name !== "string" ||
+        typeof plugin.rules !== "object" || plugin.rules === null
+      ) {
+        throw new TypeError(
+          `Lint plugin "${specifier}" must default export an object with a "name" and "rules".`,
+        );
+      }
+      for (const [name, rule] of Object.entries(plugin.rules)) {
+        const code = `${plugin.name}/${name}`;
+        if (typeof rule?.lint !== "function") {
+          throw new TypeError(
+            `Lint rule "${code}" in "${specifier}" must have a "lint" function.`,
+          );
+        }
+        rules.push({ code, rule });
+      }
+    }
+  }
+
+  function run(file) {
+    deepFreeze(file);
+    const diagnostics = [];
+    for (const { code, rule } of rules) {
+      const context = Object.freeze({
+        filename: file.filename,
+        sourceText: file.sourceText,
+        imports: file.imports,
+        declarations: file.declarations,
+        report({ range, message, hint }) {
+          if (
+            !Array.isArray(range) || range.length !== 2 ||
+            !Number.isInteger(range[0]) || !Number.isInteger(range[1]) ||
+            range[0] < 0 || range[1] < range[0]
+          ) {
+            throw new TypeError(
+              `Lint rule "${code}" reported an invalid range, expected [start, end].`,
+            );
+          }
+          diagnostics.push({
+            code,
+            start: range[0],
+            end: range[1],
+            message: String(message),
+            hint: hint === undefined || hint === null ? null : String(hint),
+          });
+        },
+      });
+      rule.lint(context);
+    }
+    return diagnostics;
+  }
+
+  function ruleCodes() {
+    return rules.map(({ code }) => code);
+  }
+
+  globalThis.lintPluginHost = { register, ruleCodes, run };
+})(globalThis);
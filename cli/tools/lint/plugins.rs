@@ -0,0 +1,587 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+
+//! Lint rules from local plugin modules, listed in the `lintPlugins` option of
+//! the config file.
+//!
+//! A plugin module default exports an object with a `name` and a `rules`
+//! object. Each rule has a `lint(context)` function, which gets the text,
+//! imports and declarations of the file and calls `context.report()` for every
+//! problem. The rule codes are prefixed with the plugin name, such as
+//! `acme/no-lodash`.
+//!
+//! The plugins run in a bare JS runtime on a dedicated thread, without access
+//! to any Deno APIs, and may only import other local modules.
+
+use std::collections::HashSet;
+use std::fmt;
+use std::fs;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use deno_ast::swc::ast::CallExpr;
+use deno_ast::swc::ast::Callee;
+use deno_ast::swc::ast::ClassDecl;
+use deno_ast::swc::ast::ExportAll;
+use deno_ast::swc::ast::Expr;
+use deno_ast::swc::ast::FnDecl;
+use deno_ast::swc::ast::Ident;
+use deno_ast::swc::ast::ImportDecl;
+use deno_ast::swc::ast::Lit;
+use deno_ast::swc::ast::NamedExport;
+use deno_ast::swc::ast::Pat;
+use deno_ast::swc::ast::Program;
+use deno_ast::swc::ast::TsEnumDecl;
+use deno_ast::swc::ast::TsInterfaceDecl;
+use deno_ast::swc::ast::TsTypeAliasDecl;
+use deno_ast::swc::ast::VarDecl;
+use deno_ast::swc::ast::VarDeclKind;
+use deno_ast::swc::visit::Visit;
+use deno_ast::swc::visit::VisitWith;
+use deno_ast::MediaType;
+use deno_ast::ModuleSpecifier;
+use deno_ast::ParseParams;
+use deno_ast::ParsedSource;
+use deno_ast::SourceRangedForSpanned;
+use deno_ast::SourceTextInfo;
+use deno_core::anyhow::Context;
+use deno_core::error::generic_error;
+use deno_core::error::type_error;
+use deno_core::error::AnyError;
+use deno_core::futures::future;
+use deno_core::located_script_name;
+use deno_core::serde_json;
+use deno_core::serde_v8;
+use deno_core::v8;
+use deno_core::JsRuntime;
+use deno_core::ModuleLoader;
+use deno_core::ModuleSource;
+use deno_core::ModuleSourceFuture;
+use deno_core::ModuleType;
+use deno_core::ResolutionKind;
+use deno_core::RuntimeOptions;
+use deno_lint::diagnostic::LintDiagnostic;
+use deno_lint::diagnostic::Position;
+use deno_lint::diagnostic::Range;
+use deno_runtime::tokio_util::create_basic_runtime;
+use serde::Deserialize;
+use serde::Serialize;
+use tokio::sync::mpsc;
+use tokio::sync::oneshot;
+
+use crate::util::path::specifier_to_file_path;
+
+const PLUGIN_HOST_SRC: &str = include_str!("plugin_host.js");
+
+type PluginRequest = (
+  PluginFile,
+  oneshot::Sender<Result<Vec<PluginDiagnostic>, AnyError>>,
+);
+
+/// Runs the rules of the lint plugins. Cloning the host shares the runtime.
+#[derive(Clone)]
+pub struct LintPluginHost {
+  sender: mpsc::UnboundedSender<PluginRequest>,
+  rule_codes: Arc<HashSet<String>>,
+}
+
+impl fmt::Debug for LintPluginHost {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("LintPluginHost")
+      .field("rule_codes", &self.rule_codes)
+      .finish()
+  }
+}
+
+impl LintPluginHost {
+  /// Loads the plugin modules, blocking until they are evaluated.
+  pub fn new(plugins: Vec<ModuleSpecifier>) -> Result<Self, AnyError> {
+    let (sender, mut receiver) = mpsc::unbounded_channel::<PluginRequest>();
+    let (ready_sender, ready_receiver) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+      let runtime = create_basic_runtime();
+      runtime.block_on(async move {
+        let mut js_runtime = match load_plugins(&plugins).await {
+          Ok((js_runtime, rule_codes)) => {
+            let _ = ready_sender.send(Ok(rule_codes));
+            js_runtime
+          }
+          Err(err) => {
+            let _ = ready_sender.send(Err(err));
+            return;
+          }
+        };
+        while let Some((file, response_sender)) = receiver.recv().await {
+          let _ = response_sender.send(run_plugins(&mut js_runtime, &file));
+        }
+      });
+    });
+    let rule_codes = ready_receiver.recv().map_err(|_| {
+      generic_error("The lint plugin host stopped unexpectedly.")
+    })??;
+    Ok(Self {
+      sender,
+      rule_codes: Arc::new(rule_codes),
+    })
+  }
+
+  /// Runs the plugin rules on the file. This blocks the current thread until
+  /// the rules have run, so it must not be called from async code.
+  pub fn lint(
+    &self,
+    parsed_source: &ParsedSource,
+  ) -> Result<Vec<LintDiagnostic>, AnyError> {
+    let text = parsed_source.text_info().text_str();
+    let offsets = Utf16Offsets::new(text);
+    let file = collect_plugin_file(parsed_source, &offsets);
+    let (response_sender, response_receiver) = oneshot::channel();
+    self.sender.send((file, response_sender)).map_err(|_| {
+      generic_error("The lint plugin host stopped unexpectedly.")
+    })?;
+    let plugin_diagnostics =
+      response_receiver.blocking_recv().map_err(|_| {
+        generic_error("The lint plugin host stopped unexpectedly.")
+      })??;
+
+    let filename = parsed_source.specifier().to_string();
+    Ok(
+      plugin_diagnostics
+        .into_iter()
+        .filter_map(|d| {
+          let start = offsets.byte_index(d.start);
+          let end = offsets.byte_index(d.end.max(d.start));
+          let range = Range {
+            start: position(text, start),
+            end: position(text, end),
+          };
+          if is_ignored(text, range.start.line_index, &d.code) {
+            return None;
+          }
+          Some(LintDiagnostic {
+            range,
+            filename: filename.clone(),
+            message: d.message,
+            code: d.code,
+            hint: d.hint,
+          })
+        })
+        .collect(),
+    )
+  }
+
+  /// Removes the diagnostics of the built-in rules about ignore directives
+  /// that name plugin rules, which the built-in rules don't know about.
+  pub fn retain_known_ignores(&self, diagnostics: &mut Vec<LintDiagnostic>) {
+    diagnostics.retain(|d| {
+      !matches!(
+        d.code.as_str(),
+        "ban-unknown-rule-code" | "ban-unused-ignore"
+      ) || !self
+        .rule_codes
+        .iter()
+        .any(|code| d.message.contains(&format!("\"{code}\"")))
+    });
+  }
+}
+
+async fn load_plugins(
+  plugins: &[ModuleSpecifier],
+) -> Result<(JsRuntime, HashSet<String>), AnyError> {
+  let mut js_runtime = JsRuntime::new(RuntimeOptions {
+    module_loader: Some(Rc::new(PluginModuleLoader)),
+    ..Default::default()
+  });
+  js_runtime.execute_script_static(located_script_name!(), PLUGIN_HOST_SRC)?;
+
+  // a synthetic module that imports all plugins and registers them
+  let mut main_source = String::new();
+  let mut modules = Vec::new();
+  for (i, plugin) in plugins.iter().enumerate() {
+    let specifier = serde_json::to_string(plugin.as_str())?;
+    main_source.push_str(&format!("import * as plugin{i} from {specifier};\n"));
+    modules.push(format!("{{ specifier: {specifier}, module: plugin{i} }}"));
+  }
+  main_source.push_str(&format!(
+    "globalThis.lintPluginHost.register([{}]);\n",
+    modules.join(", ")
+  ));
+  let main_specifier =
+    ModuleSpecifier::parse("file:///$deno$lint_plugins.js").unwrap();
+  let id = js_runtime
+    .load_main_module(&main_specifier, Some(main_source.into()))
+    .await?;
+  let mut receiver = js_runtime.mod_evaluate(id);
+  tokio::select! {
+    biased;
+
+    maybe_result = &mut receiver => {
+      maybe_result.expect("Module evaluation result not provided.")?;
+    }
+
+    event_loop_result = js_runtime.run_event_loop(false) => {
+      event_loop_result?;
+      let maybe_result = receiver.await;
+      maybe_result.expect("Module evaluation result not provided.")?;
+    }
+  }
+
+  let rule_codes = js_runtime.execute_script_static(
+    located_script_name!(),
+    "globalThis.lintPluginHost.ruleCodes()",
+  )?;
+  let scope = &mut js_runtime.handle_scope();
+  let rule_codes = v8::Local::new(scope, rule_codes);
+  let rule_codes = serde_v8::from_v8::<HashSet<String>>(scope, rule_codes)?;
+  Ok((js_runtime, rule_codes))
+}
+
+fn run_plugins(
+  js_runtime: &mut JsRuntime,
+  file: &PluginFile,
+) -> Result<Vec<PluginDiagnostic>, AnyError> {
+  let request = format!(
+    "globalThis.lintPluginHost.run({})",
+    serde_json::to_string(file)?
+  );
+  let result =
+    js_runtime.execute_script(located_script_name!(), request.into())?;
+  let scope = &mut js_runtime.handle_scope();
+  let result = v8::Local::new(scope, result);
+  Ok(serde_v8::from_v8::<Vec<PluginDiagnostic>>(scope, result)?)
+}
+
+/// Loads the plugin modules and the local modules they import, transpiling
+/// TypeScript and JSX.
+struct PluginModuleLoader;
+
+impl ModuleLoader for PluginModuleLoader {
+  fn resolve(
+    &self,
+    specifier: &str,
+    referrer: &str,
+    _kind: ResolutionKind,
+  ) -> Result<ModuleSpecifier, AnyError> {
+    Ok(deno_core::resolve_import(specifier, referrer)?)
+  }
+
+  fn load(
+    &self,
+    specifier: &ModuleSpecifier,
+    _maybe_referrer: Option<&ModuleSpecifier>,
+    _is_dynamic: bool,
+  ) -> Pin<Box<ModuleSourceFuture>> {
+    Box::pin(future::ready(load_plugin_module(specifier)))
+  }
+}
+
+fn load_plugin_module(
+  specifier: &ModuleSpecifier,
+) -> Result<ModuleSource, AnyError> {
+  if specifier.scheme() != "file" {
+    return Err(type_error(format!(
+      "Lint plugins can only import local modules, but \"{specifier}\" was imported."
+    )));
+  }
+  let path = specifier_to_file_path(specifier)?;
+  let text = fs::read_to_string(&path).with_context(|| {
+    format!("Failed to read lint plugin {}", path.display())
+  })?;
+  let media_type = MediaType::from_specifier(specifier);
+  let (module_type, code) = match media_type {
+    MediaType::JavaScript | MediaType::Mjs => (ModuleType::JavaScript, text),
+    MediaType::Json => (ModuleType::Json, text),
+    MediaType::TypeScript
+    | MediaType::Mts
+    | MediaType::Jsx
+    | MediaType::Tsx => {
+      let parsed_source = deno_ast::parse_module(ParseParams {
+        specifier: specifier.to_string(),
+        text_info: SourceTextInfo::from_string(text),
+        media_type,
+        capture_tokens: false,
+        scope_analysis: false,
+        maybe_syntax: None,
+      })?;
+      let code = parsed_source.transpile(&Default::default())?.text;
+      (ModuleType::JavaScript, code)
+    }
+    _ => {
+      return Err(type_error(format!(
+        "Unsupported media type {media_type} for lint plugin module \"{specifier}\"."
+      )))
+    }
+  };
+  Ok(ModuleSource::new(module_type, code.into(), specifier))
+}
+
+/// The file as seen by the plugin rules. Ranges are `[start, end]` offsets
+/// into `sourceText`, counted in UTF-16 code units like JS string indexes.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PluginFile {
+  filename: String,
+  source_text: String,
+  imports: Vec<PluginImport>,
+  declarations: Vec<PluginDeclaration>,
+}
+
+#[derive(Serialize)]
+struct PluginImport {
+  specifier: String,
+  range: [usize; 2],
+}
+
+#[derive(Serialize)]
+struct PluginDeclaration {
+  name: String,
+  kind: &'static str,
+  range: [usize; 2],
+}
+
+#[derive(Deserialize)]
+struct PluginDiagnostic {
+  code: String,
+  start: usize,
+  end: usize,
+  message: String,
+  hint: Option<String>,
+}
+
+fn collect_plugin_file(
+  parsed_source: &ParsedSource,
+  offsets: &Utf16Offsets,
+) -> PluginFile {
+  let mut collector = FileCollector {
+    text_info: parsed_source.text_info(),
+    offsets,
+    imports: Vec::new(),
+    declarations: Vec::new(),
+  };
+  let program = parsed_source.program();
+  let program: &Program = &program;
+  program.visit_with(&mut collector);
+  PluginFile {
+    filename: parsed_source.specifier().to_string(),
+    source_text: parsed_source.text_info().text_str().to_string(),
+    imports: collector.imports,
+    declarations: collector.declarations,
+  }
+}
+
+struct FileCollector<'a> {
+  text_info: &'a SourceTextInfo,
+  offsets: &'a Utf16Offsets,
+  imports: Vec<PluginImport>,
+  declarations: Vec<PluginDeclaration>,
+}
+
+impl FileCollector<'_> {
+  fn range(&self, node: &impl SourceRangedForSpanned) -> [usize; 2] {
+    let range = node.range();
+    let start = self.text_info.range().start;
+    [
+      self.offsets.utf16_index(range.start - start),
+      self.offsets.utf16_index(range.end - start),
+    ]
+  }
+
+  fn add_import(&mut self, specifier: &deno_ast::swc::ast::Str) {
+    self.imports.push(PluginImport {
+      specifier: specifier.value.to_string(),
+      range: self.range(specifier),
+    });
+  }
+
+  fn add_declaration(&mut self, ident: &Ident, kind: &'static str) {
+    self.declarations.push(PluginDeclaration {
+      name: ident.sym.to_string(),
+      kind,
+      range: self.range(ident),
+    });
+  }
+}
+
+impl Visit for FileCollector<'_> {
+  fn visit_import_decl(&mut self, n: &ImportDecl) {
+    self.add_import(&n.src);
+  }
+
+  fn visit_named_export(&mut self, n: &NamedExport) {
+    if let Some(src) = &n.src {
+      self.add_import(src);
+    }
+  }
+
+  fn visit_export_all(&mut self, n: &ExportAll) {
+    self.add_import(&n.src);
+  }
+
+  fn visit_call_expr(&mut self, n: &CallExpr) {
+    if let Callee::Import(_) = &n.callee {
+      if let Some(arg) = n.args.first() {
+        if let Expr::Lit(Lit::Str(specifier)) = &*arg.expr {
+          self.add_import(specifier);
+        }
+      }
+    }
+    n.visit_children_with(self);
+  }
+
+  fn visit_fn_decl(&mut self, n: &FnDecl) {
+    self.add_declaration(&n.ident, "function");
+    n.visit_children_with(self);
+  }
+
+  fn visit_class_decl(&mut self, n: &ClassDecl) {
+    self.add_declaration(&n.ident, "class");
+    n.visit_children_with(self);
+  }
+
+  fn visit_var_decl(&mut self, n: &VarDecl) {
+    let kind = match n.kind {
+      VarDeclKind::Var => "var",
+      VarDeclKind::Let => "let",
+      VarDeclKind::Const => "const",
+    };
+    for declarator in &n.decls {
+      if let Pat::Ident(ident) = &declarator.name {
+        self.add_declaration(&ident.id, kind);
+      }
+    }
+    n.visit_children_with(self);
+  }
+
+  fn visit_ts_interface_decl(&mut self, n: &TsInterfaceDecl) {
+    self.add_declaration(&n.id, "interface");
+    n.visit_children_with(self);
+  }
+
+  fn visit_ts_type_alias_decl(&mut self, n: &TsTypeAliasDecl) {
+    self.add_declaration(&n.id, "type");
+    n.visit_children_with(self);
+  }
+
+  fn visit_ts_enum_decl(&mut self, n: &TsEnumDecl) {
+    self.add_declaration(&n.id, "enum");
+    n.visit_children_with(self);
+  }
+}
+
+/// Converts between byte indexes and UTF-16 offsets of a text.
+struct Utf16Offsets {
+  /// The UTF-16 offset of every byte index, or `None` for ASCII text where
+  /// they are the same.
+  by_byte_index: Option<Vec<usize>>,
+}
+
+impl Utf16Offsets {
+  fn new(text: &str) -> Self {
+    if text.is_ascii() {
+      return Self {
+        by_byte_index: None,
+      };
+    }
+    let mut by_byte_index = Vec::with_capacity(text.len() + 1);
+    let mut utf16_index = 0;
+    for c in text.chars() {
+      for _ in 0..c.len_utf8() {
+        by_byte_index.push(utf16_index);
+      }
+      utf16_index += c.len_utf16();
+    }
+    by_byte_index.push(utf16_index);
+    Self {
+      by_byte_index: Some(by_byte_index),
+    }
+  }
+
+  fn utf16_index(&self, byte_index: usize) -> usize {
+    match &self.by_byte_index {
+      Some(by_byte_index) => by_byte_index[byte_index],
+      None => byte_index,
+    }
+  }
+
+  /// The byte index of the character at the UTF-16 offset, clamped to the
+  /// end of the text.
+  fn byte_index(&self, utf16_index: usize) -> usize {
+    match &self.by_byte_index {
+      Some(by_byte_index) => {
+        by_byte_index.partition_point(|&index| index < utf16_index)
+      }
+      None => utf16_index,
+    }
+  }
+}
+
+fn position(text: &str, byte_index: usize) -> Position {
+  let byte_index = byte_index.min(text.len());
+  let before = &text[..byte_index];
+  let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+  Position {
+    line_index: before.matches('\n').count(),
+    column_index: before[line_start..].chars().count(),
+    byte_index,
+  }
+}
+
+/// Whether the diagnostic is ignored with a `// deno-lint-ignore-file` comment
+/// at the top of the file or a `// deno-lint-ignore` comment on the line
+/// before.
+fn is_ignored(text: &str, line_index: usize, code: &str) -> bool {
+  let ignores = |directive: &str, line: &str| {
+    let Some(rest) = line
+      .trim_start()
+      .strip_prefix("//")
+      .and_then(|comment| comment.trim_start().strip_prefix(directive))
+    else {
+      return false;
+    };
+    if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+      return false;
+    }
+    let codes = rest.split_whitespace().collect::<Vec<_>>();
+    codes.is_empty() || codes.contains(&code)
+  };
+  let ignores_file = text
+    .lines()
+    .take_while(|line| {
+      let line = line.trim_start();
+      line.is_empty() || line.starts_with("//") || line.starts_with("#!")
+    })
+    .any(|line| ignores("deno-lint-ignore-file", line));
+  ignores_file
+    || line_index > 0
+      && text
+        .lines()
+        .nth(line_index - 1)
+        .map(|line| ignores("deno-lint-ignore", line))
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn utf16_offsets() {
+    let text = "a😀b\né";
+    let offsets = Utf16Offsets::new(text);
+    assert_eq!(offsets.utf16_index(0), 0);
+    assert_eq!(offsets.utf16_index(1), 1);
+    assert_eq!(offsets.utf16_index(5), 3);
+    assert_eq!(offsets.utf16_index(text.len()), 6);
+    assert_eq!(offsets.byte_index(3), 5);
+    assert_eq!(offsets.byte_index(5), 7);
+    assert_eq!(offsets.byte_index(100), text.len());
+  }
+
+  #[test]
+  fn ignore_directives() {
+    let text = "// deno-lint-ignore-file acme/a\nfoo();\n// deno-lint-ignore acme/b\nbar();\n";
+    assert!(is_ignored(text, 1, "acme/a"));
+    assert!(!is_ignored(text, 1, "acme/b"));
+    assert!(is_ignored(text, 3, "acme/b"));
+    assert!(!is_ignored(text, 3, "acme/c"));
+  }
+}
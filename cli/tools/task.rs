@@ -2,23 +2,36 @@
 
 use crate::args::CliOptions;
 use crate::args::Flags;
+use crate::args::TaskDefinition;
 use crate::args::TaskFlags;
+use crate::cache::TaskCache;
 use crate::colors;
 use crate::factory::CliFactory;
 use crate::npm::CliNpmResolver;
 use crate::util::fs::canonicalize_path;
+use crate::util::fs::FileCollector;
+use crate::util::glob::expand_globs;
 use deno_core::anyhow::bail;
 use deno_core::anyhow::Context;
 use deno_core::error::AnyError;
 use deno_core::futures;
 use deno_core::futures::future::LocalBoxFuture;
+use deno_core::futures::stream::FuturesUnordered;
+use deno_core::futures::StreamExt;
+use deno_core::unsync::spawn_blocking;
 use deno_runtime::deno_node::NodeResolver;
 use deno_semver::package::PackageNv;
+use deno_task_shell::parser::SequentialList;
 use deno_task_shell::ExecuteResult;
 use deno_task_shell::ShellCommand;
 use deno_task_shell::ShellCommandContext;
+use deno_task_shell::ShellPipeReader;
+use deno_task_shell::ShellState;
 use indexmap::IndexMap;
 use std::collections::HashMap;
+use std::collections::HashSet;
+use std::io::Write;
+use std::path::Path;
 use std::path::PathBuf;
 use std::rc::Rc;
 use tokio::task::LocalSet;
@@ -44,7 +57,7 @@ pub async fn execute_script(
     }
   };
 
-  if let Some(script) = tasks_config.get(task_name) {
+  if tasks_config.contains_key(task_name) {
     let config_file_url = cli_options.maybe_config_file_specifier().unwrap();
     let config_file_path = if config_file_url.scheme() == "file" {
       config_file_url.to_file_path().unwrap()
@@ -55,15 +68,16 @@ pub async fn execute_script(
       Some(path) => canonicalize_path(&PathBuf::from(path))?,
       None => config_file_path.parent().unwrap().to_owned(),
     };
-    let script = get_script_with_args(script, cli_options);
-    output_task(task_name, &script);
-    let seq_list = deno_task_shell::parser::parse(&script)
-      .with_context(|| format!("Error parsing script '{task_name}'."))?;
-    let env_vars = collect_env_vars();
+    let task_cache = TaskCache::new(factory.caches()?.task_cache_db());
+    let tasks = resolve_task_graph(
+      &tasks_config,
+      task_name,
+      cli_options,
+      &config_file_path,
+      &task_cache,
+    )?;
     let local = LocalSet::new();
-    let future =
-      deno_task_shell::execute(seq_list, env_vars, &cwd, Default::default());
-    let exit_code = local.run_until(future).await;
+    let exit_code = local.run_until(run_task_graph(tasks, cwd)).await;
     Ok(exit_code)
   } else if package_json_scripts.contains_key(task_name) {
     let package_json_deps_provider = factory.package_json_deps_provider();
@@ -140,6 +154,370 @@ pub async fn execute_script(
   }
 }
 
+/// A task of the config file that is ready to run.
+struct ResolvedTask {
+  name: String,
+  /// The parsed command, or `None` for a task that only has dependencies.
+  command: Option<(String, SequentialList)>,
+  dependencies: Vec<String>,
+  /// Set when the task declares inputs, so it can be skipped when they
+  /// didn't change.
+  cache: Option<TaskCacheEntry>,
+}
+
+struct TaskCacheEntry {
+  cache: TaskCache,
+  /// Identifies the task across runs.
+  key: String,
+  base_dir: PathBuf,
+  inputs: Vec<String>,
+  outputs: Vec<String>,
+}
+
+impl TaskCacheEntry {
+  /// Hashes the script along with the paths and contents of the input files.
+  /// This walks and reads the input files, so it runs on a blocking thread to
+  /// not hold up the other tasks.
+  async fn input_hash(&self, script: &str) -> Result<String, AnyError> {
+    let base_dir = self.base_dir.clone();
+    let inputs = self.inputs.clone();
+    let script = script.to_string();
+    spawn_blocking(move || hash_task_inputs(&base_dir, &inputs, &script))
+      .await?
+  }
+
+  fn outputs_exist(&self) -> bool {
+    self
+      .outputs
+      .iter()
+      .all(|output| self.base_dir.join(output).exists())
+  }
+}
+
+fn hash_task_inputs(
+  base_dir: &Path,
+  inputs: &[String],
+  script: &str,
+) -> Result<String, AnyError> {
+  let paths =
+    expand_globs(inputs.iter().map(|input| base_dir.join(input)).collect())?;
+  let mut files = FileCollector::new(|_| true)
+    .ignore_git_folder()
+    .ignore_node_modules()
+    .collect_files(Some(&paths))?;
+  files.sort();
+  files.dedup();
+  // the files are streamed into the hash, so large inputs aren't held in
+  // memory at once
+  let mut hasher = ring::digest::Context::new(&ring::digest::SHA256);
+  hasher.update(script.as_bytes());
+  for file in files {
+    let mut reader = std::fs::File::open(&file)
+      .with_context(|| format!("Failed to read {}", file.display()))?;
+    let len = reader
+      .metadata()
+      .with_context(|| format!("Failed to read {}", file.display()))?
+      .len();
+    let relative_path = file.strip_prefix(base_dir).unwrap_or(&file);
+    hasher
+      .update(format!("\0{}\0{}\0", relative_path.display(), len).as_bytes());
+    let read = std::io::copy(&mut reader, &mut HashWriter(&mut hasher))
+      .with_context(|| format!("Failed to read {}", file.display()))?;
+    if read != len {
+      bail!("{} changed while it was being read", file.display());
+    }
+  }
+  Ok(
+    hasher
+      .finish()
+      .as_ref()
+      .iter()
+      .map(|byte| format!("{byte:02x}"))
+      .collect(),
+  )
+}
+
+/// Feeds everything written to it into the hash.
+struct HashWriter<'a>(&'a mut ring::digest::Context);
+
+impl Write for HashWriter<'_> {
+  fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+    self.0.update(buf);
+    Ok(buf.len())
+  }
+
+  fn flush(&mut self) -> std::io::Result<()> {
+    Ok(())
+  }
+}
+
+/// Resolves the task and the tasks it depends on, directly or indirectly, in
+/// the order they are declared in.
+fn resolve_task_graph(
+  tasks_config: &IndexMap<String, TaskDefinition>,
+  task_name: &str,
+  cli_options: &CliOptions,
+  config_file_path: &Path,
+  task_cache: &TaskCache,
+) -> Result<Vec<ResolvedTask>, AnyError> {
+  fn visit<'a>(
+    tasks_config: &'a IndexMap<String, TaskDefinition>,
+    task_name: &'a str,
+    path: &mut Vec<&'a str>,
+    visited: &mut HashSet<&'a str>,
+  ) -> Result<(), AnyError> {
+    if let Some(index) = path.iter().position(|name| *name == task_name) {
+      let mut cycle = path[index..].to_vec();
+      cycle.push(task_name);
+      bail!("Task dependency cycle detected: {}", cycle.join(" -> "));
+    }
+    if !visited.insert(task_name) {
+      return Ok(());
+    }
+    let task = &tasks_config[task_name];
+    path.push(task_name);
+    for dependency in &task.dependencies {
+      if !tasks_config.contains_key(dependency) {
+        bail!("Task '{task_name}' depends on unknown task '{dependency}'.");
+      }
+      visit(tasks_config, dependency, path, visited)?;
+    }
+    path.pop();
+    Ok(())
+  }
+
+  let mut visited = HashSet::new();
+  visit(tasks_config, task_name, &mut Vec::new(), &mut visited)?;
+
+  let base_dir = canonicalize_path(config_file_path.parent().unwrap())?;
+
+  let mut tasks = Vec::with_capacity(visited.len());
+  for (name, task) in tasks_config {
+    if !visited.contains(name.as_str()) {
+      continue;
+    }
+    let command = match &task.command {
+      Some(command) => {
+        // additional arguments only go to the task that was asked for
+        let script = if name == task_name {
+          get_script_with_args(command, cli_options)
+        } else {
+          command.clone()
+        };
+        let seq_list = deno_task_shell::parser::parse(&script)
+          .with_context(|| format!("Error parsing script '{name}'."))?;
+        Some((script, seq_list))
+      }
+      None => None,
+    };
+    let cache = if task.inputs.is_empty() {
+      None
+    } else {
+      Some(TaskCacheEntry {
+        cache: task_cache.clone(),
+        key: format!("{}#{}", config_file_path.display(), name),
+        base_dir: base_dir.clone(),
+        inputs: task.inputs.clone(),
+        outputs: task.outputs.clone(),
+      })
+    };
+    tasks.push(ResolvedTask {
+      name: name.clone(),
+      command,
+      dependencies: task.dependencies.clone(),
+      cache,
+    });
+  }
+  Ok(tasks)
+}
+
+/// Runs every task once its dependencies are done, so tasks that don't
+/// depend on each other run in parallel. When there is more than one command
+/// to run, their output is prefixed with the task name. After a task fails,
+/// no more tasks are started and its exit code is returned.
+async fn run_task_graph(tasks: Vec<ResolvedTask>, cwd: PathBuf) -> i32 {
+  let prefix_output =
+    tasks.iter().filter(|task| task.command.is_some()).count() > 1;
+  let mut pending = tasks;
+  let mut completed = HashSet::new();
+  let mut running = FuturesUnordered::new();
+  let mut exit_code = 0;
+  loop {
+    if exit_code == 0 {
+      let (ready, not_ready): (Vec<_>, Vec<_>) =
+        pending.into_iter().partition(|task| {
+          task
+            .dependencies
+            .iter()
+            .all(|dependency| completed.contains(dependency))
+        });
+      pending = not_ready;
+      for task in ready {
+        running.push(run_task(task, cwd.clone(), prefix_output));
+      }
+    }
+    match running.next().await {
+      Some((name, task_exit_code)) => {
+        completed.insert(name);
+        if exit_code == 0 {
+          exit_code = task_exit_code;
+        }
+      }
+      None => break,
+    }
+  }
+  exit_code
+}
+
+async fn run_task(
+  task: ResolvedTask,
+  cwd: PathBuf,
+  prefix_output: bool,
+) -> (String, i32) {
+  let Some((script, seq_list)) = task.command else {
+    return (task.name, 0);
+  };
+  // the inputs are hashed right before running, because the tasks this task
+  // depends on may have just changed them
+  let input_hash = match &task.cache {
+    Some(cache) => match cache.input_hash(&script).await {
+      Ok(input_hash) => Some(input_hash),
+      Err(err) => {
+        log::warn!(
+          "{} Not caching task '{}': {:#}",
+          colors::yellow("Warning"),
+          task.name,
+          err
+        );
+        None
+      }
+    },
+    None => None,
+  };
+  if let (Some(cache), Some(input_hash)) = (&task.cache, &input_hash) {
+    if cache.outputs_exist()
+      && cache.cache.get_input_hash(&cache.key).as_ref() == Some(input_hash)
+    {
+      log::info!(
+        "{} {} {}",
+        colors::green("Task"),
+        colors::cyan(&task.name),
+        colors::gray("skipped, inputs unchanged"),
+      );
+      return (task.name, 0);
+    }
+  }
+
+  output_task(&task.name, &script);
+  let exit_code = execute_task(&task.name, seq_list, &cwd, prefix_output).await;
+  if exit_code == 0 {
+    if let (Some(cache), Some(input_hash)) = (&task.cache, &input_hash) {
+      cache.cache.set_input_hash(&cache.key, input_hash);
+    }
+  }
+  (task.name, exit_code)
+}
+
+async fn execute_task(
+  task_name: &str,
+  seq_list: SequentialList,
+  cwd: &Path,
+  prefix_output: bool,
+) -> i32 {
+  let env_vars = collect_env_vars();
+  if !prefix_output {
+    return deno_task_shell::execute(
+      seq_list,
+      env_vars,
+      cwd,
+      Default::default(),
+    )
+    .await;
+  }
+
+  let prefix = format!("{} ", colors::cyan(format!("[{task_name}]")));
+  let (stdout_reader, stdout_writer) = deno_task_shell::pipe();
+  let (stderr_reader, stderr_writer) = deno_task_shell::pipe();
+  let stdout_handle = spawn_blocking({
+    let mut writer = PrefixedLineWriter::new(prefix.clone(), std::io::stdout());
+    move || {
+      let _ = stdout_reader.pipe_to(&mut writer);
+      writer.finish();
+    }
+  });
+  let stderr_handle = spawn_blocking({
+    let mut writer = PrefixedLineWriter::new(prefix, std::io::stderr());
+    move || {
+      let _ = stderr_reader.pipe_to(&mut writer);
+      writer.finish();
+    }
+  });
+  let state = ShellState::new(env_vars, cwd, Default::default());
+  let exit_code = deno_task_shell::execute_with_pipes(
+    seq_list,
+    state,
+    ShellPipeReader::stdin(),
+    stdout_writer,
+    stderr_writer,
+  )
+  .await;
+  let _ = stdout_handle.await;
+  let _ = stderr_handle.await;
+  exit_code
+}
+
+/// Writes complete lines with a prefix, so the output of tasks running in
+/// parallel doesn't get interleaved within a line.
+struct PrefixedLineWriter<W: Write> {
+  prefix: String,
+  inner: W,
+  line: Vec<u8>,
+}
+
+impl<W: Write> PrefixedLineWriter<W> {
+  fn new(prefix: String, inner: W) -> Self {
+    Self {
+      prefix,
+      inner,
+      line: Vec::new(),
+    }
+  }
+
+  fn write_line(&mut self) -> std::io::Result<()> {
+    let mut output = Vec::with_capacity(self.prefix.len() + self.line.len());
+    output.extend_from_slice(self.prefix.as_bytes());
+    output.append(&mut self.line);
+    if output.last() != Some(&b'\n') {
+      output.push(b'\n');
+    }
+    self.inner.write_all(&output)
+  }
+
+  /// Writes the last line, if it didn't end with a new line.
+  fn finish(mut self) {
+    if !self.line.is_empty() {
+      let _ = self.write_line();
+    }
+    let _ = self.inner.flush();
+  }
+}
+
+impl<W: Write> Write for PrefixedLineWriter<W> {
+  fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+    for chunk in buf.split_inclusive(|b| *b == b'\n') {
+      self.line.extend_from_slice(chunk);
+      if chunk.ends_with(b"\n") {
+        self.write_line()?;
+      }
+    }
+    Ok(buf.len())
+  }
+
+  fn flush(&mut self) -> std::io::Result<()> {
+    self.inner.flush()
+  }
+}
+
 fn get_script_with_args(script: &str, options: &CliOptions) -> String {
   let additional_args = options
     .argv()
@@ -178,26 +556,36 @@ fn collect_env_vars() -> HashMap<String, String> {
 
 fn print_available_tasks(
   // order can be important, so these use an index map
-  tasks_config: &IndexMap<String, String>,
+  tasks_config: &IndexMap<String, TaskDefinition>,
   package_json_scripts: &IndexMap<String, String>,
 ) {
   eprintln!("{}", colors::green("Available tasks:"));
 
   let mut had_task = false;
-  for (is_deno, (key, value)) in tasks_config.iter().map(|e| (true, e)).chain(
-    package_json_scripts
-      .iter()
-      .filter(|(key, _)| !tasks_config.contains_key(*key))
-      .map(|e| (false, e)),
-  ) {
+  for (key, task) in tasks_config {
+    eprintln!("- {}", colors::cyan(key));
+    if let Some(description) = &task.description {
+      eprintln!("    {}", colors::italic_gray(description));
+    }
+    if let Some(command) = &task.command {
+      eprintln!("    {command}");
+    }
+    if !task.dependencies.is_empty() {
+      eprintln!(
+        "    {}",
+        colors::gray(format!("depends on: {}", task.dependencies.join(", ")))
+      );
+    }
+    had_task = true;
+  }
+  for (key, value) in package_json_scripts
+    .iter()
+    .filter(|(key, _)| !tasks_config.contains_key(*key))
+  {
     eprintln!(
-      "- {}{}",
+      "- {} {}",
       colors::cyan(key),
-      if is_deno {
-        "".to_string()
-      } else {
-        format!(" {}", colors::italic_gray("(package.json)"))
-      }
+      colors::italic_gray("(package.json)")
     );
     eprintln!("    {value}");
     had_task = true;
@@ -5,6 +5,7 @@ use crate::args::FilesConfig;
 use crate::args::Flags;
 use crate::args::TestFlags;
 use crate::args::TestReporterConfig;
+use crate::args::TestShard;
 use crate::colors;
 use crate::display;
 use crate::factory::CliFactory;
@@ -50,6 +51,7 @@ use deno_runtime::fmt_errors::format_js_error;
 use deno_runtime::permissions::Permissions;
 use deno_runtime::permissions::PermissionsContainer;
 use deno_runtime::tokio_util::create_and_run_current_thread;
+use deno_runtime::worker::MainWorker;
 use indexmap::IndexMap;
 use indexmap::IndexSet;
 use log::Level;
@@ -86,6 +88,7 @@ mod reporters;
 pub use fmt::format_test_error;
 use reporters::CompoundTestReporter;
 use reporters::DotTestReporter;
+use reporters::JsonTestReporter;
 use reporters::JunitTestReporter;
 use reporters::PrettyTestReporter;
 use reporters::TapTestReporter;
@@ -171,6 +174,9 @@ pub struct TestDescription {
   pub only: bool,
   pub origin: String,
   pub location: TestLocation,
+  /// How many times the test is re-run after failing, overriding `--retry`.
+  #[serde(default)]
+  pub retry: Option<usize>,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Deserialize)]
@@ -275,6 +281,8 @@ pub enum TestResult {
   Ignored,
   Failed(TestFailure),
   Cancelled,
+  /// Passed after failing. Holds the failures of the earlier attempts.
+  Flaky(Vec<TestFailure>),
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Deserialize)]
@@ -335,6 +343,7 @@ pub struct TestSummary {
   pub filtered_out: usize,
   pub measured: usize,
   pub failures: Vec<(TestDescription, TestFailure)>,
+  pub flaky: Vec<(TestDescription, usize)>,
   pub uncaught_errors: Vec<(String, Box<JsError>)>,
 }
 
@@ -354,6 +363,7 @@ pub struct TestSpecifierOptions {
   pub shuffle: Option<u64>,
   pub filter: TestFilter,
   pub trace_ops: bool,
+  pub retry: usize,
 }
 
 impl TestSummary {
@@ -369,6 +379,7 @@ impl TestSummary {
       filtered_out: 0,
       measured: 0,
       failures: Vec::new(),
+      flaky: Vec::new(),
       uncaught_errors: Vec::new(),
     }
   }
@@ -393,6 +404,7 @@ fn get_test_reporter(options: &TestSpecifiersOptions) -> Box<dyn TestReporter> {
     TestReporterConfig::Tap => Box::new(TapTestReporter::new(
       options.concurrent_jobs > NonZeroUsize::new(1).unwrap(),
     )),
+    TestReporterConfig::Json => Box::new(JsonTestReporter::new()),
   };
 
   if let Some(junit_path) = &options.junit_path {
@@ -511,26 +523,63 @@ pub async fn test_specifier(
     }
 
     let earlier = SystemTime::now();
-    let result = match worker.js_runtime.call_and_await(&function).await {
-      Ok(r) => r,
-      Err(error) => {
-        if error.is::<JsError>() {
-          sender.send(TestEvent::UncaughtError(
-            specifier.to_string(),
-            Box::new(error.downcast::<JsError>().unwrap()),
-          ))?;
-          fail_fast_tracker.add_failure();
-          sender.send(TestEvent::Result(desc.id, TestResult::Cancelled, 0))?;
-          had_uncaught_error = true;
+    let retry = desc.retry.unwrap_or(options.retry);
+    let mut earlier_failures = vec![];
+    let result = loop {
+      // Only the steps of the final attempt are reported, so hold the step
+      // events back while the attempt may still be retried.
+      let may_retry = earlier_failures.len() < retry;
+      if may_retry {
+        set_step_event_buffering(&mut worker, true);
+      }
+      let result = worker.js_runtime.call_and_await(&function).await;
+      let step_events = set_step_event_buffering(&mut worker, false);
+      let result = match result {
+        Ok(r) => r,
+        Err(error) => {
+          for event in step_events {
+            sender.send(event)?;
+          }
+          if error.is::<JsError>() {
+            sender.send(TestEvent::UncaughtError(
+              specifier.to_string(),
+              Box::new(error.downcast::<JsError>().unwrap()),
+            ))?;
+            had_uncaught_error = true;
+            break TestResult::Cancelled;
+          } else {
+            return Err(error);
+          }
+        }
+      };
+      let scope = &mut worker.js_runtime.handle_scope();
+      let result = v8::Local::new(scope, result);
+      let result = serde_v8::from_v8::<TestResult>(scope, result)?;
+      if let TestResult::Failed(failure) = result {
+        if may_retry {
+          earlier_failures.push(failure);
           continue;
-        } else {
-          return Err(error);
         }
+        for event in step_events {
+          sender.send(event)?;
+        }
+        break TestResult::Failed(failure);
+      }
+      for event in step_events {
+        sender.send(event)?;
+      }
+      match result {
+        TestResult::Ok if !earlier_failures.is_empty() => {
+          break TestResult::Flaky(earlier_failures);
+        }
+        result => break result,
       }
     };
-    let scope = &mut worker.js_runtime.handle_scope();
-    let result = v8::Local::new(scope, result);
-    let result = serde_v8::from_v8::<TestResult>(scope, result)?;
+    if matches!(result, TestResult::Cancelled) {
+      fail_fast_tracker.add_failure();
+      sender.send(TestEvent::Result(desc.id, result, 0))?;
+      continue;
+    }
     if matches!(result, TestResult::Failed(_)) {
       fail_fast_tracker.add_failure();
     }
@@ -551,6 +600,22 @@ pub async fn test_specifier(
   Ok(())
 }
 
+/// Starts or stops holding back the step events of the running test, and
+/// returns the events that were held back.
+fn set_step_event_buffering(
+  worker: &mut MainWorker,
+  enabled: bool,
+) -> Vec<TestEvent> {
+  let state_rc = worker.js_runtime.op_state();
+  let mut state = state_rc.borrow_mut();
+  let buffer = state.borrow_mut::<ops::testing::TestStepEventBuffer>();
+  let events = buffer.0.take().unwrap_or_default();
+  if enabled {
+    buffer.0 = Some(Vec::new());
+  }
+  events
+}
+
 fn extract_files_from_regex_blocks(
   specifier: &ModuleSpecifier,
   source: &str,
@@ -1090,6 +1155,21 @@ async fn fetch_specifiers_with_test_mode(
   Ok(specifiers_with_mode)
 }
 
+/// Keeps the modules that belong to `shard`. Modules are sorted first so that
+/// the partitions don't depend on the order they were collected in.
+fn shard_specifiers(
+  mut specifiers_with_mode: Vec<(ModuleSpecifier, TestMode)>,
+  shard: &TestShard,
+) -> Vec<(ModuleSpecifier, TestMode)> {
+  specifiers_with_mode.sort_by(|(a, _), (b, _)| a.cmp(b));
+  specifiers_with_mode
+    .into_iter()
+    .enumerate()
+    .filter(|(position, _)| shard.includes(*position))
+    .map(|(_, specifier_with_mode)| specifier_with_mode)
+    .collect()
+}
+
 pub async fn run_tests(
   flags: Flags,
   test_flags: TestFlags,
@@ -1117,6 +1197,23 @@ pub async fn run_tests(
     return Err(generic_error("No test modules found"));
   }
 
+  let specifiers_with_mode = match test_options.shard {
+    Some(shard) => {
+      let specifiers_with_mode = shard_specifiers(specifiers_with_mode, &shard);
+      if specifiers_with_mode.is_empty() {
+        log::warn!(
+          "{} No test modules in shard {}/{}",
+          colors::yellow("Warning"),
+          shard.index,
+          shard.total
+        );
+        return Ok(());
+      }
+      specifiers_with_mode
+    }
+    None => specifiers_with_mode,
+  };
+
   check_specifiers(
     cli_options,
     file_fetcher,
@@ -1153,6 +1250,7 @@ pub async fn run_tests(
         filter: TestFilter::from_flag(&test_options.filter),
         shuffle: test_options.shuffle,
         trace_ops: test_options.trace_ops,
+        retry: test_options.retry,
       },
     },
   )
@@ -1191,6 +1289,8 @@ pub async fn run_tests_with_watch(
     move |flags, sender, changed_paths| {
       let test_flags = test_flags.clone();
       Ok(async move {
+        let coverage_dir = test_flags.coverage_dir.clone();
+        let coverage_flags = flags.clone();
         let factory = CliFactoryBuilder::new()
           .with_watcher(sender.clone())
           .build_from_flags(flags)
@@ -1288,11 +1388,20 @@ pub async fn run_tests_with_watch(
               filter: TestFilter::from_flag(&test_options.filter),
               shuffle: test_options.shuffle,
               trace_ops: test_options.trace_ops,
+              retry: test_options.retry,
             },
           },
         )
         .await?;
 
+        if let Some(coverage_dir) = coverage_dir {
+          crate::tools::coverage::check_test_coverage(
+            coverage_flags,
+            &coverage_dir,
+          )
+          .await?;
+        }
+
         Ok(())
       })
     },
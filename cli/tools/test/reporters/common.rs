@@ -65,6 +65,14 @@ pub fn format_test_step_for_summary(
   )
 }
 
+pub(super) fn format_retries(retries: usize) -> String {
+  if retries == 1 {
+    "passed after 1 retry".to_string()
+  } else {
+    format!("passed after {retries} retries")
+  }
+}
+
 pub(super) fn report_sigint(
   cwd: &Url,
   tests_pending: &HashSet<usize>,
@@ -155,6 +163,17 @@ pub(super) fn report_summary(
     }
   }
 
+  if !summary.flaky.is_empty() {
+    println!("\n{}\n", colors::yellow("FLAKY"));
+    for (description, retries) in &summary.flaky {
+      println!(
+        "{} {}",
+        format_test_for_summary(cwd, description),
+        colors::gray(format!("({})", format_retries(*retries)))
+      );
+    }
+  }
+
   let status = if summary.has_failed() {
     colors::red("FAILED").to_string()
   } else {
@@ -183,6 +202,10 @@ pub(super) fn report_summary(
   )
   .unwrap();
 
+  if !summary.flaky.is_empty() {
+    write!(summary_result, " | {} flaky", summary.flaky.len()).unwrap();
+  }
+
   let ignored_steps = get_steps_text(summary.ignored_steps);
   if summary.ignored > 0 || !ignored_steps.is_empty() {
     write!(
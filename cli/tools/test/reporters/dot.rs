@@ -55,6 +55,7 @@ impl DotTestReporter {
   fn print_test_result(&mut self, result: &TestResult) {
     let status = match result {
       TestResult::Ok => fmt_ok(),
+      TestResult::Flaky(_) => fmt_flaky(),
       TestResult::Ignored => fmt_ignored(),
       TestResult::Failed(_failure) => fmt_failed(),
       TestResult::Cancelled => fmt_cancelled(),
@@ -68,6 +69,10 @@ fn fmt_ok() -> String {
   colors::gray(".").to_string()
 }
 
+fn fmt_flaky() -> String {
+  colors::yellow(".").to_string()
+}
+
 fn fmt_ignored() -> String {
   colors::cyan(",").to_string()
 }
@@ -105,6 +110,13 @@ impl TestReporter for DotTestReporter {
       TestResult::Ok => {
         self.summary.passed += 1;
       }
+      TestResult::Flaky(failures) => {
+        self.summary.passed += 1;
+        self
+          .summary
+          .flaky
+          .push((description.clone(), failures.len()));
+      }
       TestResult::Ignored => {
         self.summary.ignored += 1;
       }
@@ -169,6 +181,7 @@ impl TestReporter for DotTestReporter {
             only: false,
             origin: desc.origin.clone(),
             location: desc.location.clone(),
+            retry: None,
           },
           failure.clone(),
         ))
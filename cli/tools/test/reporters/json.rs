@@ -0,0 +1,285 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+
+use deno_core::serde_json;
+use serde::Serialize;
+
+use super::fmt::to_relative_path_or_remote_url;
+use super::*;
+
+/// A test reporter that writes every test event to stdout as a single line
+/// of JSON, so that other tools can consume the test run as it happens.
+///
+/// Events are written in the order they happen. Tests are registered while
+/// their module is evaluated, so the `register` events of a module come
+/// before its `plan` event, which in turn comes before any of its `wait`
+/// events.
+pub struct JsonTestReporter {
+  cwd: Url,
+}
+
+#[derive(Serialize)]
+struct JsonLocation {
+  file: String,
+  line: u32,
+  column: u32,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "status", rename_all = "camelCase")]
+enum JsonResult {
+  Ok,
+  Ignored,
+  Failed {
+    message: String,
+  },
+  Cancelled,
+  Flaky {
+    retries: usize,
+    failures: Vec<String>,
+  },
+}
+
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+enum JsonTestEvent<'a> {
+  #[serde(rename_all = "camelCase")]
+  Register {
+    id: usize,
+    name: &'a str,
+    origin: String,
+    location: JsonLocation,
+    ignore: bool,
+    only: bool,
+  },
+  #[serde(rename_all = "camelCase")]
+  Plan {
+    origin: String,
+    total: usize,
+    filtered_out: usize,
+    used_only: bool,
+  },
+  Wait {
+    id: usize,
+  },
+  Output {
+    output: String,
+  },
+  Result {
+    id: usize,
+    #[serde(flatten)]
+    result: JsonResult,
+    elapsed: u64,
+  },
+  /// A test or step that failed because it leaked async ops or resources.
+  Leak {
+    id: usize,
+    kind: &'static str,
+    details: &'a [String],
+  },
+  #[serde(rename_all = "camelCase")]
+  UncaughtError {
+    origin: String,
+    message: String,
+  },
+  #[serde(rename_all = "camelCase")]
+  StepRegister {
+    id: usize,
+    name: &'a str,
+    parent_id: usize,
+    root_id: usize,
+    level: usize,
+    location: JsonLocation,
+  },
+  StepWait {
+    id: usize,
+  },
+  StepResult {
+    id: usize,
+    #[serde(flatten)]
+    result: JsonResult,
+    elapsed: u64,
+  },
+  Summary {
+    elapsed: u128,
+  },
+  #[serde(rename_all = "camelCase")]
+  Sigint {
+    pending_ids: Vec<usize>,
+  },
+}
+
+impl JsonTestReporter {
+  pub fn new() -> JsonTestReporter {
+    JsonTestReporter {
+      cwd: Url::from_directory_path(std::env::current_dir().unwrap()).unwrap(),
+    }
+  }
+
+  fn write_event(&self, event: &JsonTestEvent) {
+    let line =
+      serde_json::to_string(event).expect("failed to serialize test event");
+    let mut stdout = std::io::stdout().lock();
+    writeln!(stdout, "{line}").unwrap();
+    // flush for faster feedback when line buffered
+    stdout.flush().unwrap();
+  }
+
+  fn location(&self, location: &TestLocation) -> JsonLocation {
+    JsonLocation {
+      file: self.relative(&location.file_name),
+      line: location.line_number,
+      column: location.column_number,
+    }
+  }
+
+  fn relative(&self, specifier: &str) -> String {
+    to_relative_path_or_remote_url(&self.cwd, specifier)
+  }
+
+  fn write_leak(&self, id: usize, failure: &TestFailure) {
+    let (kind, details) = match failure {
+      TestFailure::LeakedOps(details, _) => ("ops", details),
+      TestFailure::LeakedResources(details) => ("resources", details),
+      _ => return,
+    };
+    self.write_event(&JsonTestEvent::Leak { id, kind, details });
+  }
+}
+
+impl TestReporter for JsonTestReporter {
+  fn report_register(&mut self, description: &TestDescription) {
+    self.write_event(&JsonTestEvent::Register {
+      id: description.id,
+      name: &description.name,
+      origin: self.relative(&description.origin),
+      location: self.location(&description.location),
+      ignore: description.ignore,
+      only: description.only,
+    });
+  }
+
+  fn report_plan(&mut self, plan: &TestPlan) {
+    self.write_event(&JsonTestEvent::Plan {
+      origin: self.relative(&plan.origin),
+      total: plan.total,
+      filtered_out: plan.filtered_out,
+      used_only: plan.used_only,
+    });
+  }
+
+  fn report_wait(&mut self, description: &TestDescription) {
+    self.write_event(&JsonTestEvent::Wait { id: description.id });
+  }
+
+  fn report_output(&mut self, output: &[u8]) {
+    self.write_event(&JsonTestEvent::Output {
+      output: String::from_utf8_lossy(output).to_string(),
+    });
+  }
+
+  fn report_result(
+    &mut self,
+    description: &TestDescription,
+    result: &TestResult,
+    elapsed: u64,
+  ) {
+    let result = match result {
+      TestResult::Ok => JsonResult::Ok,
+      TestResult::Ignored => JsonResult::Ignored,
+      TestResult::Failed(failure) => {
+        self.write_leak(description.id, failure);
+        JsonResult::Failed {
+          message: failure.to_string(),
+        }
+      }
+      TestResult::Cancelled => JsonResult::Cancelled,
+      TestResult::Flaky(failures) => JsonResult::Flaky {
+        retries: failures.len(),
+        failures: failures.iter().map(|f| f.to_string()).collect(),
+      },
+    };
+    self.write_event(&JsonTestEvent::Result {
+      id: description.id,
+      result,
+      elapsed,
+    });
+  }
+
+  fn report_uncaught_error(&mut self, origin: &str, error: Box<JsError>) {
+    self.write_event(&JsonTestEvent::UncaughtError {
+      origin: self.relative(origin),
+      message: format_test_error(&error),
+    });
+  }
+
+  fn report_step_register(&mut self, description: &TestStepDescription) {
+    self.write_event(&JsonTestEvent::StepRegister {
+      id: description.id,
+      name: &description.name,
+      parent_id: description.parent_id,
+      root_id: description.root_id,
+      level: description.level,
+      location: self.location(&description.location),
+    });
+  }
+
+  fn report_step_wait(&mut self, description: &TestStepDescription) {
+    self.write_event(&JsonTestEvent::StepWait { id: description.id });
+  }
+
+  fn report_step_result(
+    &mut self,
+    desc: &TestStepDescription,
+    result: &TestStepResult,
+    elapsed: u64,
+    _tests: &IndexMap<usize, TestDescription>,
+    _test_steps: &IndexMap<usize, TestStepDescription>,
+  ) {
+    let result = match result {
+      TestStepResult::Ok => JsonResult::Ok,
+      TestStepResult::Ignored => JsonResult::Ignored,
+      TestStepResult::Failed(failure) => {
+        self.write_leak(desc.id, failure);
+        JsonResult::Failed {
+          message: failure.to_string(),
+        }
+      }
+    };
+    self.write_event(&JsonTestEvent::StepResult {
+      id: desc.id,
+      result,
+      elapsed,
+    });
+  }
+
+  fn report_summary(
+    &mut self,
+    elapsed: &Duration,
+    _tests: &IndexMap<usize, TestDescription>,
+    _test_steps: &IndexMap<usize, TestStepDescription>,
+  ) {
+    self.write_event(&JsonTestEvent::Summary {
+      elapsed: elapsed.as_millis(),
+    });
+  }
+
+  fn report_sigint(
+    &mut self,
+    tests_pending: &HashSet<usize>,
+    _tests: &IndexMap<usize, TestDescription>,
+    _test_steps: &IndexMap<usize, TestStepDescription>,
+  ) {
+    let mut pending_ids = tests_pending.iter().copied().collect::<Vec<_>>();
+    pending_ids.sort_unstable();
+    self.write_event(&JsonTestEvent::Sigint { pending_ids });
+  }
+
+  fn flush_report(
+    &mut self,
+    _elapsed: &Duration,
+    _tests: &IndexMap<usize, TestDescription>,
+    _test_steps: &IndexMap<usize, TestStepDescription>,
+  ) -> anyhow::Result<()> {
+    Ok(())
+  }
+}
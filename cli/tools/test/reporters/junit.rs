@@ -19,6 +19,17 @@ impl JunitTestReporter {
   fn convert_status(status: &TestResult) -> quick_junit::TestCaseStatus {
     match status {
       TestResult::Ok => quick_junit::TestCaseStatus::success(),
+      TestResult::Flaky(failures) => quick_junit::TestCaseStatus::Success {
+        flaky_runs: failures
+          .iter()
+          .map(|failure| {
+            let mut rerun =
+              quick_junit::TestRerun::new(quick_junit::NonSuccessKind::Failure);
+            rerun.set_message(failure.to_string());
+            rerun
+          })
+          .collect(),
+      },
       TestResult::Ignored => quick_junit::TestCaseStatus::skipped(),
       TestResult::Failed(failure) => quick_junit::TestCaseStatus::NonSuccess {
         kind: quick_junit::NonSuccessKind::Failure,
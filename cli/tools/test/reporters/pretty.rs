@@ -191,6 +191,13 @@ impl TestReporter for PrettyTestReporter {
       TestResult::Ok => {
         self.summary.passed += 1;
       }
+      TestResult::Flaky(failures) => {
+        self.summary.passed += 1;
+        self
+          .summary
+          .flaky
+          .push((description.clone(), failures.len()));
+      }
       TestResult::Ignored => {
         self.summary.ignored += 1;
       }
@@ -217,15 +224,22 @@ impl TestReporter for PrettyTestReporter {
 
     let status = match result {
       TestResult::Ok => colors::green("ok").to_string(),
+      TestResult::Flaky(_) => colors::yellow("flaky").to_string(),
       TestResult::Ignored => colors::yellow("ignored").to_string(),
       TestResult::Failed(failure) => failure.format_label(),
       TestResult::Cancelled => colors::gray("cancelled").to_string(),
     };
     print!(" {}", status);
-    if let TestResult::Failed(failure) = result {
-      if let Some(inline_summary) = failure.format_inline_summary() {
-        print!(" ({})", inline_summary)
+    match result {
+      TestResult::Failed(failure) => {
+        if let Some(inline_summary) = failure.format_inline_summary() {
+          print!(" ({})", inline_summary)
+        }
+      }
+      TestResult::Flaky(failures) => {
+        print!(" ({})", common::format_retries(failures.len()))
       }
+      _ => {}
     }
     println!(
       " {}",
@@ -287,6 +301,7 @@ impl TestReporter for PrettyTestReporter {
             only: false,
             origin: desc.origin.clone(),
             location: desc.location.clone(),
+            retry: None,
           },
           failure.clone(),
         ))
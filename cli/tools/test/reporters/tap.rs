@@ -65,6 +65,23 @@ impl TapTestReporter {
     println!("{:indent$}  ...", "", indent = indent);
   }
 
+  fn print_flaky_diagnostic(
+    failures: &[TestFailure],
+    location: DiagnosticLocation,
+  ) {
+    let diagnostic = serde_json::to_string(&json!({
+      "message": format!("flaky, {}", common::format_retries(failures.len())),
+      "severity": "comment",
+      "retries": failures.len(),
+      "failures": failures.iter().map(|f| f.to_string()).collect::<Vec<_>>(),
+      "at": location,
+    }))
+    .expect("failed to serialize TAP diagnostic");
+    println!("  ---");
+    println!("  {}", diagnostic);
+    println!("  ...");
+  }
+
   fn print_line(
     indent: usize,
     status: &str,
@@ -159,7 +176,7 @@ impl TestReporter for TapTestReporter {
     }
 
     let (status, directive) = match result {
-      TestResult::Ok => ("ok", ""),
+      TestResult::Ok | TestResult::Flaky(_) => ("ok", ""),
       TestResult::Ignored => ("ok", " # SKIP"),
       TestResult::Failed(_failure) => ("not ok", ""),
       TestResult::Cancelled => ("not ok", ""),
@@ -167,15 +184,18 @@ impl TestReporter for TapTestReporter {
     self.n += 1;
     Self::print_line(0, status, self.n, &description.name, directive);
 
-    if let TestResult::Failed(failure) = result {
-      Self::print_diagnostic(
-        0,
-        failure,
-        DiagnosticLocation {
-          file: to_relative_path_or_remote_url(&self.cwd, &description.origin),
-          line: description.location.line_number,
-        },
-      );
+    let location = DiagnosticLocation {
+      file: to_relative_path_or_remote_url(&self.cwd, &description.origin),
+      line: description.location.line_number,
+    };
+    match result {
+      TestResult::Failed(failure) => {
+        Self::print_diagnostic(0, failure, location);
+      }
+      TestResult::Flaky(failures) => {
+        Self::print_flaky_diagnostic(failures, location);
+      }
+      _ => {}
     }
   }
 
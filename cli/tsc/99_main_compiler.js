@@ -32,6 +32,7 @@ delete Object.prototype.__proto__;
   /** @type {ReadonlySet<string>} */
   const unstableDenoProps = new Set([
     "AtomicOperation",
+    "CacheUsage",
     "CreateHttpClientOptions",
     "DatagramConn",
     "HttpClient",
@@ -44,6 +45,7 @@ delete Object.prototype.__proto__;
     "UnsafeFnPointer",
     "UnixConnectOptions",
     "UnixListenOptions",
+    "cacheUsage",
     "createHttpClient",
     "dlopen",
     "flock",
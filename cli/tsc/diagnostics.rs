@@ -239,6 +239,14 @@ impl Diagnostic {
   fn is_error(&self) -> bool {
     self.category == DiagnosticCategory::Error
   }
+
+  /// The message of the diagnostic, including any chained messages.
+  pub fn message(&self) -> String {
+    match &self.message_chain {
+      Some(message_chain) => message_chain.format_message(0),
+      None => self.message_text.clone().unwrap_or_default(),
+    }
+  }
 }
 
 impl fmt::Display for Diagnostic {
@@ -270,6 +278,10 @@ impl Diagnostics {
   pub fn is_empty(&self) -> bool {
     self.0.is_empty()
   }
+
+  pub fn iter(&self) -> std::slice::Iter<'_, Diagnostic> {
+    self.0.iter()
+  }
 }
 
 impl<'de> Deserialize<'de> for Diagnostics {
@@ -783,6 +783,11 @@ declare namespace Deno {
     /** If at least one test has `only` set to `true`, only run tests that have
      * `only` set to `true` and fail the test suite. */
     only?: boolean;
+    /** Re-run the test up to this many times when it fails. A test that only
+     * passes on a retry is reported as flaky. Overrides the `--retry` flag.
+     *
+     * @default {0} */
+    retry?: number;
     /** Check that the number of async completed operations after the test step
      * is the same as number of dispatched operations. This ensures that the
      * code tested does not start async operations which it then does
@@ -1172,6 +1177,12 @@ declare namespace Deno {
     /** If at least one bench has `only` set to true, only run benches that have
      * `only` set to `true` and fail the bench suite. */
     only?: boolean;
+    /** Also collect V8 heap usage statistics for the bench, in a separate
+     * pass after the timing measurements. Enabled for all benches with the
+     * `--memory` flag.
+     *
+     * @default {false} */
+    memory?: boolean;
     /** Ensure the bench case does not prematurely cause the process to exit,
      * for example via a call to {@linkcode Deno.exit}.
      *
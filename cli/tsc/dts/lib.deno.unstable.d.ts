@@ -1905,6 +1905,35 @@ declare namespace Deno {
       handler: (value: unknown) => Promise<void> | void,
     ): Promise<void>;
 
+    /**
+     * Watch a set of keys for changes. The returned stream first emits the
+     * current entries for all of the given keys, and then emits the entries
+     * again every time a committed write changes at least one of them.
+     *
+     * Each emitted array has one entry per watched key, in the same order as
+     * the `keys` argument. Keys that do not exist are reported with a `null`
+     * value and versionstamp.
+     *
+     * ```ts
+     * const db = await Deno.openKv();
+     * const stream = db.watch([["foo"], ["bar"]]);
+     * for await (const [foo, bar] of stream) {
+     *   console.log(foo.value, bar.value);
+     * }
+     * ```
+     *
+     * Changes made by other handles, workers or processes that opened the same
+     * database file, as well as keys being removed after their `expireIn`
+     * elapsed, are picked up too. Changes made through other connections can
+     * take a short moment to be noticed.
+     *
+     * At most 10 keys can be watched at once. Watching keys is not
+     * supported for remote databases.
+     */
+    watch<T extends readonly unknown[]>(
+      keys: readonly [...{ [K in keyof T]: KvKey }],
+    ): ReadableStream<{ [K in keyof T]: KvEntryMaybe<T[K]> }>;
+
     /**
      * Create a new {@linkcode Deno.AtomicOperation} object which can be used to
      * perform an atomic transaction on the database. This does not perform any
@@ -1936,6 +1965,37 @@ declare namespace Deno {
     /** The value of this unsigned 64-bit integer, represented as a bigint. */
     readonly value: bigint;
   }
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * How many responses a cache of the Cache API stores and their size in
+   * bytes, headers included.
+   *
+   * @category Cache API
+   */
+  export interface CacheUsage {
+    /** The name of the cache, as passed to `caches.open()`. */
+    cacheName: string;
+    /** The number of responses stored in the cache. */
+    entries: number;
+    /** The size of the stored responses in bytes. */
+    size: number;
+  }
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * List the existing caches of the Cache API in creation order, along with
+   * how many responses each of them stores and their size.
+   *
+   * ```ts
+   * for (const { cacheName, size } of await Deno.cacheUsage()) {
+   *   console.log(cacheName, size);
+   * }
+   * ```
+   *
+   * @category Cache API
+   */
+  export function cacheUsage(): Promise<CacheUsage[]>;
 }
 
 /** **UNSTABLE**: New API, yet to be vetted.
@@ -11,6 +11,7 @@
 declare interface WindowEventMap {
   "error": ErrorEvent;
   "unhandledrejection": PromiseRejectionEvent;
+  "storage": StorageEvent;
 }
 
 /** @category Web APIs */
@@ -105,6 +106,7 @@ declare interface Navigator {
   readonly userAgent: string;
   readonly language: string;
   readonly languages: string[];
+  readonly storage: StorageManager;
 }
 
 /** @category Web APIs */
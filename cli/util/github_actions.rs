@@ -0,0 +1,112 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+
+//! Writing diagnostics as GitHub Actions
+//! [workflow commands](https://docs.github.com/en/actions/using-workflow-commands-for-github-actions),
+//! which GitHub shows as annotations on the pull request.
+
+use deno_core::url::Url;
+
+use crate::util::path::specifier_to_file_path;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AnnotationLevel {
+  Error,
+  Warning,
+  Notice,
+}
+
+/// A region of a file. Lines and columns are one based.
+#[derive(Clone, Debug)]
+pub struct AnnotationLocation {
+  pub specifier: Url,
+  pub line: u64,
+  pub column: u64,
+  pub end_line: u64,
+  pub end_column: u64,
+}
+
+/// Formats an annotation command, such as
+/// `::error file=main.ts,line=1,col=5::message`.
+pub fn format_annotation(
+  level: AnnotationLevel,
+  title: &str,
+  message: &str,
+  location: Option<&AnnotationLocation>,
+) -> String {
+  let command = match level {
+    AnnotationLevel::Error => "error",
+    AnnotationLevel::Warning => "warning",
+    AnnotationLevel::Notice => "notice",
+  };
+  let mut properties = Vec::new();
+  if let Some(location) = location {
+    properties.push(format!("file={}", escape_property(&file(location))));
+    properties.push(format!("line={}", location.line));
+    properties.push(format!("endLine={}", location.end_line));
+    // columns are only supported for annotations on a single line
+    if location.line == location.end_line {
+      properties.push(format!("col={}", location.column));
+      properties.push(format!("endColumn={}", location.end_column));
+    }
+  }
+  properties.push(format!("title={}", escape_property(title)));
+
+  format!(
+    "::{command} {}::{}",
+    properties.join(","),
+    escape_data(message)
+  )
+}
+
+/// Files are referenced relative to the current directory, which is the
+/// repository root in a typical workflow.
+fn file(location: &AnnotationLocation) -> String {
+  let Ok(path) = specifier_to_file_path(&location.specifier) else {
+    return location.specifier.to_string();
+  };
+  let cwd = std::env::current_dir().unwrap();
+  let path = path.strip_prefix(&cwd).unwrap_or(&path);
+  path.to_string_lossy().replace('\\', "/")
+}
+
+fn escape_data(text: &str) -> String {
+  text
+    .replace('%', "%25")
+    .replace('\r', "%0D")
+    .replace('\n', "%0A")
+}
+
+fn escape_property(text: &str) -> String {
+  escape_data(text).replace(':', "%3A").replace(',', "%2C")
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn annotation() {
+    let cwd =
+      Url::from_directory_path(std::env::current_dir().unwrap()).unwrap();
+    let location = AnnotationLocation {
+      specifier: cwd.join("src/main.ts").unwrap(),
+      line: 1,
+      column: 5,
+      end_line: 1,
+      end_column: 6,
+    };
+    assert_eq!(
+      format_annotation(
+        AnnotationLevel::Error,
+        "prefer-const",
+        "`a` is never reassigned\nuse const: 100%",
+        Some(&location),
+      ),
+      "::error file=src/main.ts,line=1,endLine=1,col=5,endColumn=6,title=prefer-const::`a` is never reassigned%0Ause const: 100%25"
+    );
+    assert_eq!(
+      format_annotation(AnnotationLevel::Warning, "a, b: c", "message", None),
+      "::warning title=a%2C b%3A c::message"
+    );
+  }
+}
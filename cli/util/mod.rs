@@ -8,10 +8,12 @@ pub mod display;
 pub mod draw_thread;
 pub mod file_watcher;
 pub mod fs;
+pub mod github_actions;
 pub mod glob;
 pub mod logger;
 pub mod path;
 pub mod progress_bar;
+pub mod sarif;
 pub mod sync;
 pub mod text_encoding;
 pub mod time;
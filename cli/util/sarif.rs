@@ -0,0 +1,204 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+
+//! Writing diagnostics as a [SARIF 2.1.0](https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html)
+//! log, the format consumed by code scanning tools.
+
+use std::collections::BTreeMap;
+
+use deno_core::serde_json;
+use deno_core::serde_json::json;
+use deno_core::url::Url;
+use serde::Serialize;
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const SARIF_VERSION: &str = "2.1.0";
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SarifLevel {
+  Error,
+  Warning,
+  Note,
+}
+
+/// A region of a file. Lines and columns are one based.
+#[derive(Clone, Debug)]
+pub struct SarifLocation {
+  pub specifier: Url,
+  pub start_line: u64,
+  pub start_column: u64,
+  pub end_line: u64,
+  pub end_column: u64,
+}
+
+/// A single run of a tool, written as a log with one run.
+pub struct SarifRun {
+  tool_name: String,
+  information_uri: String,
+  cwd: Url,
+  /// Help uris of the rules, by rule id.
+  rules: BTreeMap<String, Option<String>>,
+  results: Vec<serde_json::Value>,
+  notifications: Vec<serde_json::Value>,
+}
+
+impl SarifRun {
+  pub fn new(tool_name: &str, information_uri: &str) -> Self {
+    Self {
+      tool_name: tool_name.to_string(),
+      information_uri: information_uri.to_string(),
+      cwd: Url::from_directory_path(std::env::current_dir().unwrap()).unwrap(),
+      rules: BTreeMap::new(),
+      results: Vec::new(),
+      notifications: Vec::new(),
+    }
+  }
+
+  pub fn add_result(
+    &mut self,
+    rule_id: &str,
+    help_uri: Option<String>,
+    level: SarifLevel,
+    message: &str,
+    location: Option<&SarifLocation>,
+  ) {
+    self.rules.entry(rule_id.to_string()).or_insert(help_uri);
+    let mut result = json!({
+      "ruleId": rule_id,
+      "level": level,
+      "message": { "text": message },
+    });
+    if let Some(location) = location {
+      result["locations"] = json!([{
+        "physicalLocation": {
+          "artifactLocation": { "uri": self.artifact_uri(&location.specifier) },
+          "region": {
+            "startLine": location.start_line,
+            "startColumn": location.start_column,
+            "endLine": location.end_line,
+            "endColumn": location.end_column,
+          },
+        },
+      }]);
+    }
+    self.results.push(result);
+  }
+
+  /// Records an error of the tool itself, such as a file that could not be
+  /// parsed.
+  pub fn add_error(&mut self, message: &str, specifier: Option<&Url>) {
+    let mut notification = json!({
+      "level": SarifLevel::Error,
+      "message": { "text": message },
+    });
+    if let Some(specifier) = specifier {
+      notification["locations"] = json!([{
+        "physicalLocation": {
+          "artifactLocation": { "uri": self.artifact_uri(specifier) },
+        },
+      }]);
+    }
+    self.notifications.push(notification);
+  }
+
+  pub fn to_json(&self) -> String {
+    let rules = self
+      .rules
+      .iter()
+      .map(|(id, help_uri)| match help_uri {
+        Some(help_uri) => json!({ "id": id, "helpUri": help_uri }),
+        None => json!({ "id": id }),
+      })
+      .collect::<Vec<_>>();
+    let log = json!({
+      "$schema": SARIF_SCHEMA,
+      "version": SARIF_VERSION,
+      "runs": [{
+        "tool": {
+          "driver": {
+            "name": self.tool_name,
+            "informationUri": self.information_uri,
+            "version": crate::version::deno(),
+            "rules": rules,
+          },
+        },
+        "invocations": [{
+          "executionSuccessful": self.notifications.is_empty(),
+          "toolExecutionNotifications": self.notifications,
+        }],
+        "results": self.results,
+      }],
+    });
+    serde_json::to_string_pretty(&log).unwrap()
+  }
+
+  /// Files in the current directory are referenced relative to it, which is
+  /// what code scanning expects for files in the repository.
+  fn artifact_uri(&self, specifier: &Url) -> String {
+    if specifier.scheme() == "file" {
+      if let Some(relative) = self.cwd.make_relative(specifier) {
+        if !relative.starts_with("../") {
+          return relative;
+        }
+      }
+    }
+    specifier.to_string()
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn sarif_log() {
+    let cwd =
+      Url::from_directory_path(std::env::current_dir().unwrap()).unwrap();
+    let mut run = SarifRun::new("deno lint", "https://lint.deno.land");
+    run.add_result(
+      "prefer-const",
+      Some("https://lint.deno.land/#prefer-const".to_string()),
+      SarifLevel::Error,
+      "`a` is never reassigned",
+      Some(&SarifLocation {
+        specifier: cwd.join("src/main.ts").unwrap(),
+        start_line: 1,
+        start_column: 5,
+        end_line: 1,
+        end_column: 6,
+      }),
+    );
+    run.add_result(
+      "prefer-const",
+      Some("https://lint.deno.land/#prefer-const".to_string()),
+      SarifLevel::Error,
+      "`b` is never reassigned",
+      Some(&SarifLocation {
+        specifier: Url::parse("https://deno.land/x/mod.ts").unwrap(),
+        start_line: 2,
+        start_column: 5,
+        end_line: 2,
+        end_column: 6,
+      }),
+    );
+    let log: serde_json::Value = serde_json::from_str(&run.to_json()).unwrap();
+    assert_eq!(log["version"], "2.1.0");
+    let run = &log["runs"][0];
+    assert_eq!(
+      run["tool"]["driver"]["rules"],
+      json!([{
+        "id": "prefer-const",
+        "helpUri": "https://lint.deno.land/#prefer-const",
+      }])
+    );
+    assert_eq!(run["invocations"][0]["executionSuccessful"], true);
+    assert_eq!(run["results"][0]["level"], "error");
+    let artifact_uri = |i: usize| {
+      run["results"][i]["locations"][0]["physicalLocation"]["artifactLocation"]
+        ["uri"]
+        .clone()
+    };
+    assert_eq!(artifact_uri(0), "src/main.ts");
+    assert_eq!(artifact_uri(1), "https://deno.land/x/mod.ts");
+  }
+}
@@ -1,5 +1,6 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
 
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::rc::Rc;
 use std::sync::Arc;
@@ -21,6 +22,11 @@ use deno_core::SourceMapGetter;
 use deno_lockfile::Lockfile;
 use deno_runtime::colors;
 use deno_runtime::deno_broadcast_channel::InMemoryBroadcastChannel;
+use deno_runtime::deno_broadcast_channel::SqliteBroadcastChannelRelay;
+use deno_runtime::deno_cache::CacheImpl;
+use deno_runtime::deno_cache::CacheLimits;
+use deno_runtime::deno_cache::CreateCache;
+use deno_runtime::deno_cache::InMemoryCache;
 use deno_runtime::deno_fs;
 use deno_runtime::deno_node;
 use deno_runtime::deno_node::NodeResolution;
@@ -74,14 +80,18 @@ pub trait HasNodeSpecifierChecker: Send + Sync {
 #[derive(Clone)]
 pub struct CliMainWorkerOptions {
   pub argv: Vec<String>,
+  pub broadcast_channel_db_path: Option<PathBuf>,
+  pub cache_storage_limits: CacheLimits,
   pub log_level: WorkerLogLevel,
   pub coverage_dir: Option<String>,
   pub enable_testing_features: bool,
   pub has_node_modules_dir: bool,
+  pub in_memory_cache_storage: bool,
   pub inspect_brk: bool,
   pub inspect_wait: bool,
   pub is_inspecting: bool,
   pub is_npm_main: bool,
+  pub local_storage_quota: Option<usize>,
   pub location: Option<Url>,
   pub maybe_binary_npm_command_name: Option<String>,
   pub origin_data_folder_path: Option<PathBuf>,
@@ -97,6 +107,9 @@ struct SharedWorkerState {
   node_resolver: Arc<NodeResolver>,
   blob_store: Arc<BlobStore>,
   broadcast_channel: InMemoryBroadcastChannel,
+  /// The broadcast channels relaying messages to other processes, by the
+  /// storage key used as their origin.
+  relayed_broadcast_channels: Mutex<HashMap<String, InMemoryBroadcastChannel>>,
   shared_array_buffer_store: SharedArrayBufferStore,
   compiled_wasm_module_store: CompiledWasmModuleStore,
   module_loader_factory: Box<dyn ModuleLoaderFactory>,
@@ -107,7 +120,44 @@ struct SharedWorkerState {
 }
 
 impl SharedWorkerState {
-  // Currently empty
+  /// Gets the broadcast channel of a main worker. When messages are relayed
+  /// to other processes, main workers with the same storage key share a
+  /// channel, and the storage key is used as the origin of its messages.
+  /// Otherwise all workers of this process share one channel. Web workers
+  /// use the channel of the main worker that created them.
+  fn broadcast_channel(
+    &self,
+    maybe_storage_key: Option<&str>,
+  ) -> Result<InMemoryBroadcastChannel, AnyError> {
+    let (Some(db_path), Some(storage_key)) =
+      (&self.options.broadcast_channel_db_path, maybe_storage_key)
+    else {
+      return Ok(self.broadcast_channel.clone());
+    };
+    let mut channels = self.relayed_broadcast_channels.lock();
+    if let Some(channel) = channels.get(storage_key) {
+      return Ok(channel.clone());
+    }
+    let relay = SqliteBroadcastChannelRelay::new(
+      db_path.clone(),
+      storage_key.to_string(),
+    )?;
+    let channel = InMemoryBroadcastChannel::with_relay(Arc::new(relay));
+    channels.insert(storage_key.to_string(), channel.clone());
+    Ok(channel)
+  }
+
+  /// Creates the Cache API backend of a worker when it shouldn't be backed by
+  /// the origin's cache storage directory. Every worker gets its own caches.
+  fn create_cache(&self) -> Option<CreateCache<CacheImpl>> {
+    if !self.options.in_memory_cache_storage {
+      return None;
+    }
+    let limits = self.options.cache_storage_limits;
+    Some(CreateCache(Arc::new(move || {
+      CacheImpl::Memory(InMemoryCache::with_limits(limits))
+    })))
+  }
 }
 
 pub struct CliMainWorker {
@@ -307,6 +357,7 @@ impl CliMainWorkerFactory {
         node_resolver,
         blob_store,
         broadcast_channel: Default::default(),
+        relayed_broadcast_channels: Default::default(),
         shared_array_buffer_store: Default::default(),
         compiled_wasm_module_store: Default::default(),
         module_loader_factory,
@@ -379,12 +430,16 @@ impl CliMainWorkerFactory {
       shared.module_loader_factory.create_source_map_getter();
     let maybe_inspector_server = shared.maybe_inspector_server.clone();
 
-    let create_web_worker_cb =
-      create_web_worker_callback(shared.clone(), stdio.clone());
-
     let maybe_storage_key = shared
       .storage_key_resolver
       .resolve_storage_key(&main_module);
+    let broadcast_channel =
+      shared.broadcast_channel(maybe_storage_key.as_deref())?;
+    let create_web_worker_cb = create_web_worker_callback(
+      shared.clone(),
+      stdio.clone(),
+      broadcast_channel.clone(),
+    );
     let origin_storage_dir = maybe_storage_key.as_ref().map(|key| {
       shared
         .options
@@ -447,9 +502,12 @@ impl CliMainWorkerFactory {
       npm_resolver: Some(shared.npm_resolver.clone()),
       get_error_class_fn: Some(&errors::get_error_class_name),
       cache_storage_dir,
+      cache_storage_limits: shared.options.cache_storage_limits,
+      create_cache: shared.create_cache(),
       origin_storage_dir,
+      local_storage_quota: shared.options.local_storage_quota,
       blob_store: shared.blob_store.clone(),
-      broadcast_channel: shared.broadcast_channel.clone(),
+      broadcast_channel,
       shared_array_buffer_store: Some(shared.shared_array_buffer_store.clone()),
       compiled_wasm_module_store: Some(
         shared.compiled_wasm_module_store.clone(),
@@ -536,6 +594,7 @@ impl CliMainWorkerFactory {
 fn create_web_worker_callback(
   shared: Arc<SharedWorkerState>,
   stdio: deno_runtime::deno_io::Stdio,
+  broadcast_channel: InMemoryBroadcastChannel,
 ) -> Arc<CreateWebWorkerCb> {
   Arc::new(move |args| {
     let maybe_inspector_server = shared.maybe_inspector_server.clone();
@@ -546,8 +605,11 @@ fn create_web_worker_callback(
     );
     let maybe_source_map_getter =
       shared.module_loader_factory.create_source_map_getter();
-    let create_web_worker_cb =
-      create_web_worker_callback(shared.clone(), stdio.clone());
+    let create_web_worker_cb = create_web_worker_callback(
+      shared.clone(),
+      stdio.clone(),
+      broadcast_channel.clone(),
+    );
 
     let extensions = ops::cli_exts(shared.npm_resolver.clone());
 
@@ -603,13 +665,15 @@ fn create_web_worker_callback(
       maybe_inspector_server,
       get_error_class_fn: Some(&errors::get_error_class_name),
       blob_store: shared.blob_store.clone(),
-      broadcast_channel: shared.broadcast_channel.clone(),
+      broadcast_channel: broadcast_channel.clone(),
       shared_array_buffer_store: Some(shared.shared_array_buffer_store.clone()),
       compiled_wasm_module_store: Some(
         shared.compiled_wasm_module_store.clone(),
       ),
       stdio: stdio.clone(),
       cache_storage_dir,
+      cache_storage_limits: shared.options.cache_storage_limits,
+      create_cache: shared.create_cache(),
     };
 
     WebWorker::bootstrap_from_options(
@@ -10,9 +10,15 @@ use tokio::sync::mpsc;
 use uuid::Uuid;
 
 use crate::BroadcastChannel;
+use crate::BroadcastChannelRelay;
 
+/// Broadcasts messages between the workers of this process, and, when created
+/// with a relay, to and from the workers of other processes.
 #[derive(Clone)]
-pub struct InMemoryBroadcastChannel(Arc<Mutex<broadcast::Sender<Message>>>);
+pub struct InMemoryBroadcastChannel {
+  tx: Arc<Mutex<broadcast::Sender<Message>>>,
+  relay: Option<Arc<RelayState>>,
+}
 
 pub struct InMemoryBroadcastChannelResource {
   rx: tokio::sync::Mutex<(
@@ -21,6 +27,27 @@ pub struct InMemoryBroadcastChannelResource {
   )>,
   cancel_tx: mpsc::UnboundedSender<()>,
   uuid: Uuid,
+  _relay_subscription: Option<RelaySubscription>,
+}
+
+/// The relay is only running while there are subscribers, so that a process
+/// that doesn't use `BroadcastChannel` doesn't keep looking for messages.
+struct RelayState {
+  relay: Arc<dyn BroadcastChannelRelay>,
+  subscribers: Mutex<usize>,
+}
+
+/// Keeps the relay running until the resource holding it is dropped.
+struct RelaySubscription(Arc<RelayState>);
+
+impl Drop for RelaySubscription {
+  fn drop(&mut self) {
+    let mut subscribers = self.0.subscribers.lock();
+    *subscribers -= 1;
+    if *subscribers == 0 {
+      self.0.relay.stop();
+    }
+  }
 }
 
 #[derive(Clone, Debug)]
@@ -33,7 +60,40 @@ struct Message {
 impl Default for InMemoryBroadcastChannel {
   fn default() -> Self {
     let (tx, _) = broadcast::channel(256);
-    Self(Arc::new(Mutex::new(tx)))
+    Self {
+      tx: Arc::new(Mutex::new(tx)),
+      relay: None,
+    }
+  }
+}
+
+impl InMemoryBroadcastChannel {
+  pub fn with_relay(relay: Arc<dyn BroadcastChannelRelay>) -> Self {
+    Self {
+      relay: Some(Arc::new(RelayState {
+        relay,
+        subscribers: Mutex::new(0),
+      })),
+      ..Default::default()
+    }
+  }
+
+  fn subscribe_relay(&self) -> Option<RelaySubscription> {
+    let state = self.relay.as_ref()?;
+    let mut subscribers = state.subscribers.lock();
+    if *subscribers == 0 {
+      let tx = self.tx.clone();
+      state.relay.start(Box::new(move |name, data| {
+        // Use a nil sender id so that every local subscriber gets the message.
+        let _ = tx.lock().send(Message {
+          name: Arc::new(name),
+          data: Arc::new(data),
+          uuid: Uuid::nil(),
+        });
+      }));
+    }
+    *subscribers += 1;
+    Some(RelaySubscription(state.clone()))
   }
 }
 
@@ -43,13 +103,14 @@ impl BroadcastChannel for InMemoryBroadcastChannel {
 
   fn subscribe(&self) -> Result<Self::Resource, AnyError> {
     let (cancel_tx, cancel_rx) = mpsc::unbounded_channel();
-    let broadcast_rx = self.0.lock().subscribe();
+    let broadcast_rx = self.tx.lock().subscribe();
     let rx = tokio::sync::Mutex::new((broadcast_rx, cancel_rx));
     let uuid = Uuid::new_v4();
     Ok(Self::Resource {
       rx,
       cancel_tx,
       uuid,
+      _relay_subscription: self.subscribe_relay(),
     })
   }
 
@@ -63,10 +124,16 @@ impl BroadcastChannel for InMemoryBroadcastChannel {
     name: String,
     data: Vec<u8>,
   ) -> Result<(), AnyError> {
+    if let Some(state) = &self.relay {
+      let relay = state.relay.clone();
+      let (name, data) = (name.clone(), data.clone());
+      tokio::task::spawn_blocking(move || relay.publish(&name, &data))
+        .await??;
+    }
     let name = Arc::new(name);
     let data = Arc::new(data);
     let uuid = resource.uuid;
-    self.0.lock().send(Message { name, data, uuid })?;
+    self.tx.lock().send(Message { name, data, uuid })?;
     Ok(())
   }
 
@@ -1,9 +1,11 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
 
 mod in_memory_broadcast_channel;
+mod sqlite_broadcast_channel;
 
 pub use in_memory_broadcast_channel::InMemoryBroadcastChannel;
 pub use in_memory_broadcast_channel::InMemoryBroadcastChannelResource;
+pub use sqlite_broadcast_channel::SqliteBroadcastChannelRelay;
 
 use std::cell::RefCell;
 use std::path::PathBuf;
@@ -40,6 +42,21 @@ pub trait BroadcastChannel: Clone {
 
 pub type Message = (String, Vec<u8>);
 
+/// Relays messages between the channels of this process and the channels of
+/// other processes, e.g. through a database or socket they all have access to.
+pub trait BroadcastChannelRelay: Send + Sync {
+  /// Forwards a message sent by a subscriber in this process.
+  fn publish(&self, name: &str, data: &[u8]) -> Result<(), AnyError>;
+
+  /// Starts delivering messages published by other processes to `deliver`.
+  /// Called when the first channel of this process subscribes.
+  fn start(&self, deliver: Box<dyn Fn(String, Vec<u8>) + Send>);
+
+  /// Stops delivering messages. Called when the last channel of this process
+  /// is closed.
+  fn stop(&self);
+}
+
 struct Unstable(bool); // --unstable
 
 #[op]
@@ -0,0 +1,145 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use deno_core::error::AnyError;
+use deno_core::parking_lot::Mutex;
+use rusqlite::params;
+use rusqlite::Connection;
+use uuid::Uuid;
+
+use crate::BroadcastChannelRelay;
+
+/// How often other processes' messages are looked up.
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+/// Messages older than this are removed from the database.
+const MESSAGE_RETENTION: Duration = Duration::from_secs(60);
+
+/// Relays messages between processes through a SQLite database shared by all
+/// of them. Only messages published with the same origin are delivered, and
+/// they are only looked up while a channel of this process is subscribed.
+pub struct SqliteBroadcastChannelRelay {
+  db_path: PathBuf,
+  origin: String,
+  sender_id: String,
+  conn: Mutex<Connection>,
+  /// Set to stop the thread polling for messages, if one is running.
+  poller_stopped: Mutex<Option<Arc<AtomicBool>>>,
+}
+
+impl SqliteBroadcastChannelRelay {
+  pub fn new(db_path: PathBuf, origin: String) -> Result<Self, AnyError> {
+    let conn = open_connection(&db_path)?;
+    conn.execute(
+      "CREATE TABLE IF NOT EXISTS messages (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        origin TEXT NOT NULL,
+        sender TEXT NOT NULL,
+        name TEXT NOT NULL,
+        data BLOB NOT NULL,
+        created_at INTEGER NOT NULL
+      )",
+      params![],
+    )?;
+    Ok(Self {
+      db_path,
+      origin,
+      sender_id: Uuid::new_v4().to_string(),
+      conn: Mutex::new(conn),
+      poller_stopped: Default::default(),
+    })
+  }
+}
+
+impl Drop for SqliteBroadcastChannelRelay {
+  fn drop(&mut self) {
+    self.stop();
+  }
+}
+
+impl BroadcastChannelRelay for SqliteBroadcastChannelRelay {
+  fn publish(&self, name: &str, data: &[u8]) -> Result<(), AnyError> {
+    let now = unix_time_millis();
+    let conn = self.conn.lock();
+    conn.execute(
+      "INSERT INTO messages (origin, sender, name, data, created_at) VALUES (?1, ?2, ?3, ?4, ?5)",
+      params![self.origin, self.sender_id, name, data, now],
+    )?;
+    conn.execute(
+      "DELETE FROM messages WHERE created_at < ?1",
+      params![now - MESSAGE_RETENTION.as_millis() as i64],
+    )?;
+    Ok(())
+  }
+
+  fn start(&self, deliver: Box<dyn Fn(String, Vec<u8>) + Send>) {
+    let db_path = self.db_path.clone();
+    let origin = self.origin.clone();
+    let sender_id = self.sender_id.clone();
+    let stopped = Arc::new(AtomicBool::new(false));
+    if let Some(previous) = self.poller_stopped.lock().replace(stopped.clone())
+    {
+      previous.store(true, Ordering::Relaxed);
+    }
+    std::thread::spawn(move || {
+      let poll = || -> Result<(), AnyError> {
+        let conn = open_connection(&db_path)?;
+        let mut last_id: i64 = conn.query_row(
+          "SELECT COALESCE(MAX(id), 0) FROM messages",
+          params![],
+          |row| row.get(0),
+        )?;
+        let mut stmt = conn.prepare(
+          "SELECT id, name, data FROM messages WHERE id > ?1 AND origin = ?2 AND sender != ?3 ORDER BY id",
+        )?;
+        while !stopped.load(Ordering::Relaxed) {
+          std::thread::sleep(POLL_INTERVAL);
+          let mut rows = stmt.query(params![last_id, origin, sender_id])?;
+          while let Some(row) = rows.next()? {
+            last_id = row.get(0)?;
+            deliver(row.get(1)?, row.get(2)?);
+          }
+        }
+        Ok(())
+      };
+      // Other processes' messages just stop arriving if the database becomes
+      // unusable, there's nobody to report the error to.
+      let _ = poll();
+    });
+  }
+
+  fn stop(&self) {
+    if let Some(stopped) = self.poller_stopped.lock().take() {
+      stopped.store(true, Ordering::Relaxed);
+    }
+  }
+}
+
+fn open_connection(db_path: &Path) -> Result<Connection, AnyError> {
+  if let Some(parent) = db_path.parent() {
+    std::fs::create_dir_all(parent)?;
+  }
+  let conn = Connection::open(db_path)?;
+  conn.busy_timeout(Duration::from_secs(5))?;
+  conn.execute_batch(
+    "
+    PRAGMA journal_mode=WAL;
+    PRAGMA synchronous=NORMAL;
+    ",
+  )?;
+  Ok(conn)
+}
+
+fn unix_time_millis() -> i64 {
+  SystemTime::now()
+    .duration_since(UNIX_EPOCH)
+    .map(|d| d.as_millis() as i64)
+    .unwrap_or(0)
+}
@@ -3,6 +3,7 @@ const core = globalThis.Deno.core;
 import * as webidl from "ext:deno_webidl/00_webidl.js";
 const primordials = globalThis.__bootstrap.primordials;
 const {
+  ArrayPrototypeMap,
   ArrayPrototypePush,
   ObjectPrototypeIsPrototypeOf,
   StringPrototypeSplit,
@@ -26,6 +27,7 @@ const {
   op_cache_put_finish,
   op_cache_storage_delete,
   op_cache_storage_has,
+  op_cache_storage_list,
   op_cache_storage_open,
 } = core.ensureFastOps();
 class CacheStorage {
@@ -59,6 +61,17 @@ class CacheStorage {
     cacheName = webidl.converters["DOMString"](cacheName, prefix, "Argument 1");
     return await op_cache_storage_delete(cacheName);
   }
+
+  async keys() {
+    webidl.assertBranded(this, CacheStoragePrototype);
+    const caches = await op_cache_storage_list();
+    return ArrayPrototypeMap(caches, (cache) => cache.cacheName);
+  }
+}
+
+/** Exposed as the unstable `Deno.cacheUsage()`. */
+async function cacheUsage() {
+  return await op_cache_storage_list();
 }
 
 const _matchAll = Symbol("[[matchAll]]");
@@ -290,4 +303,4 @@ function cacheStorage() {
   return cacheStorageStorage;
 }
 
-export { Cache, CacheStorage, cacheStorage };
+export { Cache, CacheStorage, cacheStorage, cacheUsage };
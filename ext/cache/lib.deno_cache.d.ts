@@ -16,6 +16,8 @@ declare interface CacheStorage {
   has(cacheName: string): Promise<boolean>;
   /** Delete cache storage for the provided name. */
   delete(cacheName: string): Promise<boolean>;
+  /** List the names of the existing caches, in creation order. */
+  keys(): Promise<string[]>;
 }
 
 /** @category Cache API */
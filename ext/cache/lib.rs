@@ -1,5 +1,6 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
 
+use std::borrow::Cow;
 use std::cell::RefCell;
 use std::path::PathBuf;
 use std::rc::Rc;
@@ -15,12 +16,24 @@ use deno_core::OpState;
 use deno_core::Resource;
 use deno_core::ResourceId;
 
+mod memory;
 mod sqlite;
+pub use memory::InMemoryCache;
 pub use sqlite::SqliteBackedCache;
 
 #[derive(Clone)]
 pub struct CreateCache<C: Cache + 'static>(pub Arc<dyn Fn() -> C>);
 
+/// Size limits of a cache storage. Once a limit is exceeded, the least
+/// recently used entries are evicted until the storage fits again.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CacheLimits {
+  /// Maximum size in bytes of each cache.
+  pub max_cache_bytes: Option<u64>,
+  /// Maximum size in bytes of all caches together.
+  pub max_total_bytes: Option<u64>,
+}
+
 deno_core::extension!(deno_cache,
   deps = [ deno_webidl, deno_web, deno_url, deno_fetch ],
   parameters=[CA: Cache],
@@ -28,6 +41,7 @@ deno_core::extension!(deno_cache,
     op_cache_storage_open<CA>,
     op_cache_storage_has<CA>,
     op_cache_storage_delete<CA>,
+    op_cache_storage_list<CA>,
     op_cache_put<CA>,
     op_cache_put_finish<CA>,
     op_cache_match<CA>,
@@ -88,6 +102,16 @@ pub struct CacheDeleteRequest {
   pub request_url: String,
 }
 
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheStorageInfo {
+  pub cache_name: String,
+  /// Number of responses stored in the cache.
+  pub entries: u64,
+  /// Size in bytes of the responses stored in the cache, headers included.
+  pub size: u64,
+}
+
 #[async_trait(?Send)]
 pub trait Cache: Clone + 'static {
   type CachePutResourceType: Resource;
@@ -95,6 +119,8 @@ pub trait Cache: Clone + 'static {
   async fn storage_open(&self, cache_name: String) -> Result<i64, AnyError>;
   async fn storage_has(&self, cache_name: String) -> Result<bool, AnyError>;
   async fn storage_delete(&self, cache_name: String) -> Result<bool, AnyError>;
+  /// List the caches of the storage in creation order, with their sizes.
+  async fn storage_list(&self) -> Result<Vec<CacheStorageInfo>, AnyError>;
 
   /// Create a put request.
   async fn put_create(
@@ -117,6 +143,127 @@ pub trait Cache: Clone + 'static {
     -> Result<bool, AnyError>;
 }
 
+/// A cache backend selected at runtime, for embedders that can't pick the
+/// [Cache] implementation at compile time.
+#[derive(Clone)]
+pub enum CacheImpl {
+  Sqlite(SqliteBackedCache),
+  Memory(InMemoryCache),
+}
+
+pub enum CacheImplPutResource {
+  Sqlite(Rc<sqlite::CachePutResource>),
+  Memory(Rc<memory::InMemoryCachePutResource>),
+}
+
+impl CacheImplPutResource {
+  async fn write(self: Rc<Self>, data: &[u8]) -> Result<usize, AnyError> {
+    match &*self {
+      Self::Sqlite(resource) => resource.clone().write(data).await,
+      Self::Memory(resource) => resource.clone().write(data).await,
+    }
+  }
+}
+
+impl Resource for CacheImplPutResource {
+  fn name(&self) -> Cow<str> {
+    match self {
+      Self::Sqlite(resource) => resource.name(),
+      Self::Memory(resource) => resource.name(),
+    }
+  }
+
+  deno_core::impl_writable!();
+}
+
+#[async_trait(?Send)]
+impl Cache for CacheImpl {
+  type CachePutResourceType = CacheImplPutResource;
+
+  async fn storage_open(&self, cache_name: String) -> Result<i64, AnyError> {
+    match self {
+      Self::Sqlite(cache) => cache.storage_open(cache_name).await,
+      Self::Memory(cache) => cache.storage_open(cache_name).await,
+    }
+  }
+
+  async fn storage_has(&self, cache_name: String) -> Result<bool, AnyError> {
+    match self {
+      Self::Sqlite(cache) => cache.storage_has(cache_name).await,
+      Self::Memory(cache) => cache.storage_has(cache_name).await,
+    }
+  }
+
+  async fn storage_delete(&self, cache_name: String) -> Result<bool, AnyError> {
+    match self {
+      Self::Sqlite(cache) => cache.storage_delete(cache_name).await,
+      Self::Memory(cache) => cache.storage_delete(cache_name).await,
+    }
+  }
+
+  async fn storage_list(&self) -> Result<Vec<CacheStorageInfo>, AnyError> {
+    match self {
+      Self::Sqlite(cache) => cache.storage_list().await,
+      Self::Memory(cache) => cache.storage_list().await,
+    }
+  }
+
+  async fn put_create(
+    &self,
+    request_response: CachePutRequest,
+  ) -> Result<Option<Rc<CacheImplPutResource>>, AnyError> {
+    let resource = match self {
+      Self::Sqlite(cache) => cache
+        .put_create(request_response)
+        .await?
+        .map(CacheImplPutResource::Sqlite),
+      Self::Memory(cache) => cache
+        .put_create(request_response)
+        .await?
+        .map(CacheImplPutResource::Memory),
+    };
+    Ok(resource.map(Rc::new))
+  }
+
+  async fn put_finish(
+    &self,
+    resource: Rc<CacheImplPutResource>,
+  ) -> Result<(), AnyError> {
+    match (self, &*resource) {
+      (Self::Sqlite(cache), CacheImplPutResource::Sqlite(resource)) => {
+        cache.put_finish(resource.clone()).await
+      }
+      (Self::Memory(cache), CacheImplPutResource::Memory(resource)) => {
+        cache.put_finish(resource.clone()).await
+      }
+      _ => unreachable!("put resource created by another cache backend"),
+    }
+  }
+
+  async fn r#match(
+    &self,
+    request: CacheMatchRequest,
+  ) -> Result<
+    Option<(CacheMatchResponseMeta, Option<Rc<dyn Resource>>)>,
+    AnyError,
+  > {
+    match self {
+      Self::Sqlite(cache) => cache.r#match(request).await,
+      Self::Memory(cache) => cache.r#match(request).await,
+    }
+  }
+
+  async fn delete(
+    &self,
+    request: CacheDeleteRequest,
+  ) -> Result<bool, AnyError> {
+    match self {
+      Self::Sqlite(cache) => cache.delete(request).await,
+      Self::Memory(cache) => cache.delete(request).await,
+    }
+  }
+}
+
 #[op]
 pub async fn op_cache_storage_open<CA>(
   state: Rc<RefCell<OpState>>,
@@ -153,6 +300,17 @@ where
   cache.storage_delete(cache_name).await
 }
 
+#[op]
+pub async fn op_cache_storage_list<CA>(
+  state: Rc<RefCell<OpState>>,
+) -> Result<Vec<CacheStorageInfo>, AnyError>
+where
+  CA: Cache,
+{
+  let cache = get_cache::<CA>(&state)?;
+  cache.storage_list().await
+}
+
 #[op]
 pub async fn op_cache_put<CA>(
   state: Rc<RefCell<OpState>>,
@@ -342,6 +500,32 @@ fn test_get_header() {
   assert_eq!(value, Some(ByteString::from("accept-encoding")));
 }
 
+/// Returns when a response with the given headers stops being fresh, in
+/// seconds since the Unix epoch. `Cache-Control: max-age` takes precedence over
+/// `Expires`, as in https://www.rfc-editor.org/rfc/rfc9111#section-5.3.
+pub(crate) fn get_expiry(
+  response_headers: &[(ByteString, ByteString)],
+  now: u64,
+) -> Option<u64> {
+  if let Some(cache_control) = get_header("cache-control", response_headers) {
+    let cache_control = String::from_utf8_lossy(&cache_control).to_lowercase();
+    for directive in cache_control.split(',') {
+      if let Some(max_age) = directive.trim().strip_prefix("max-age=") {
+        if let Ok(max_age) = max_age.trim_matches('"').parse::<u64>() {
+          return Some(now.saturating_add(max_age));
+        }
+      }
+    }
+  }
+  let expires = get_header("expires", response_headers)?;
+  let expires = String::from_utf8_lossy(&expires);
+  match chrono::DateTime::parse_from_rfc2822(expires.trim()) {
+    Ok(expires) => Some(expires.timestamp().max(0) as u64),
+    // An invalid date represents a time in the past.
+    Err(_) => Some(now),
+  }
+}
+
 /// Serialize headers into bytes.
 pub fn serialize_headers(headers: &[(ByteString, ByteString)]) -> Vec<u8> {
   let mut serialized_headers = Vec::new();
@@ -381,3 +565,26 @@ pub fn deserialize_headers(
   assert_eq!(start, serialized_headers.len());
   headers
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_get_expiry() {
+    let headers = |name: &str, value: &str| {
+      vec![(ByteString::from(name), ByteString::from(value))]
+    };
+    assert_eq!(get_expiry(&[], 100), None);
+    assert_eq!(
+      get_expiry(&headers("cache-control", "public, max-age=60"), 100),
+      Some(160)
+    );
+    assert_eq!(get_expiry(&headers("Cache-Control", "no-cache"), 100), None);
+    assert_eq!(
+      get_expiry(&headers("expires", "Thu, 01 Jan 1970 00:01:40 GMT"), 0),
+      Some(100)
+    );
+    assert_eq!(get_expiry(&headers("expires", "0"), 100), Some(100));
+  }
+}
@@ -0,0 +1,575 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+
+use std::borrow::Cow;
+use std::cell::Cell;
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use async_trait::async_trait;
+use deno_core::error::AnyError;
+use deno_core::parking_lot::Mutex;
+use deno_core::ByteString;
+use deno_core::Resource;
+
+use crate::get_expiry;
+use crate::get_header;
+use crate::serialize_headers;
+use crate::vary_header_matches;
+use crate::Cache;
+use crate::CacheDeleteRequest;
+use crate::CacheLimits;
+use crate::CacheMatchRequest;
+use crate::CacheMatchResponseMeta;
+use crate::CachePutRequest;
+use crate::CacheStorageInfo;
+
+/// A cache storage that only lives in memory and is dropped together with the
+/// last clone of it. Clones share the same caches.
+#[derive(Clone, Default)]
+pub struct InMemoryCache(Arc<Mutex<InMemoryCacheStorage>>);
+
+#[derive(Default)]
+struct InMemoryCacheStorage {
+  limits: CacheLimits,
+  last_cache_id: i64,
+  /// Incremented on every put and match, to order entries by their last use.
+  last_access: u64,
+  /// Caches in creation order.
+  caches: Vec<InMemoryCacheEntries>,
+  /// The size of all caches in bytes.
+  size: u64,
+  /// The cache id and request URL of every entry keyed by its last access,
+  /// least recently used first.
+  lru: BTreeMap<u64, (i64, String)>,
+}
+
+impl InMemoryCacheStorage {
+  fn next_access(&mut self) -> u64 {
+    self.last_access += 1;
+    self.last_access
+  }
+
+  /// Inserts the entry into the cache with the given id, replacing the entry
+  /// of the same request URL. Does nothing if there is no such cache.
+  fn insert_entry(
+    &mut self,
+    cache_id: i64,
+    request_url: String,
+    entry: CacheEntry,
+  ) {
+    let Some(cache) = self.caches.iter_mut().find(|c| c.id == cache_id) else {
+      return;
+    };
+    self
+      .lru
+      .insert(entry.last_access, (cache_id, request_url.clone()));
+    cache.size += entry.size;
+    self.size += entry.size;
+    if let Some(old_entry) = cache.entries.insert(request_url, entry) {
+      cache.size -= old_entry.size;
+      self.size -= old_entry.size;
+      self.lru.remove(&old_entry.last_access);
+    }
+  }
+
+  fn remove_entry(
+    &mut self,
+    cache_id: i64,
+    request_url: &str,
+  ) -> Option<CacheEntry> {
+    let cache = self.caches.iter_mut().find(|c| c.id == cache_id)?;
+    let entry = cache.entries.remove(request_url)?;
+    cache.size -= entry.size;
+    self.size -= entry.size;
+    self.lru.remove(&entry.last_access);
+    Some(entry)
+  }
+
+  /// Removes the least recently used responses of the cache with the given id,
+  /// or of all caches if `None`, until they take at most `max_bytes`.
+  fn evict_least_recently_used(
+    &mut self,
+    cache_id: Option<i64>,
+    max_bytes: u64,
+  ) {
+    loop {
+      let size = match cache_id {
+        Some(cache_id) => match self.caches.iter().find(|c| c.id == cache_id) {
+          Some(cache) => cache.size,
+          None => return,
+        },
+        None => self.size,
+      };
+      if size <= max_bytes {
+        return;
+      }
+      let Some((id, request_url)) = self
+        .lru
+        .values()
+        .find(|(id, _)| cache_id.is_none() || cache_id == Some(*id))
+        .cloned()
+      else {
+        return;
+      };
+      self.remove_entry(id, &request_url);
+    }
+  }
+}
+
+struct InMemoryCacheEntries {
+  id: i64,
+  name: String,
+  /// Entries keyed by request URL.
+  entries: HashMap<String, CacheEntry>,
+  /// The size of the entries in bytes.
+  size: u64,
+}
+
+#[derive(Clone)]
+struct CacheEntry {
+  request_headers: Vec<(ByteString, ByteString)>,
+  response_headers: Vec<(ByteString, ByteString)>,
+  response_status: u16,
+  response_status_text: String,
+  response_body: Option<Arc<[u8]>>,
+  /// When the response stops being fresh, in seconds since the Unix epoch.
+  expires_at: Option<u64>,
+  last_access: u64,
+  /// The size of the headers and the body in bytes.
+  size: u64,
+}
+
+impl CacheEntry {
+  fn new(
+    put: CachePutRequest,
+    response_body: Option<Arc<[u8]>>,
+    expires_at: Option<u64>,
+    last_access: u64,
+  ) -> Self {
+    let size = (serialize_headers(&put.request_headers).len()
+      + serialize_headers(&put.response_headers).len()
+      + response_body.as_ref().map(|b| b.len()).unwrap_or(0))
+      as u64;
+    Self {
+      request_headers: put.request_headers,
+      response_headers: put.response_headers,
+      response_status: put.response_status,
+      response_status_text: put.response_status_text,
+      response_body,
+      expires_at,
+      last_access,
+      size,
+    }
+  }
+}
+
+impl InMemoryCache {
+  /// Creates an empty cache storage that evicts the least recently used
+  /// responses once it exceeds `limits`.
+  pub fn with_limits(limits: CacheLimits) -> Self {
+    Self(Arc::new(Mutex::new(InMemoryCacheStorage {
+      limits,
+      ..Default::default()
+    })))
+  }
+
+  fn insert(
+    &self,
+    put: CachePutRequest,
+    response_body: Option<Arc<[u8]>>,
+  ) -> Result<(), AnyError> {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)?;
+    let expires_at = get_expiry(&put.response_headers, now.as_secs());
+    let mut storage = self.0.lock();
+    let last_access = storage.next_access();
+    let cache_id = put.cache_id;
+    let request_url = put.request_url.clone();
+    // does nothing if the cache was deleted while the body was being written
+    storage.insert_entry(
+      cache_id,
+      request_url,
+      CacheEntry::new(put, response_body, expires_at, last_access),
+    );
+    let limits = storage.limits;
+    if let Some(max_cache_bytes) = limits.max_cache_bytes {
+      storage.evict_least_recently_used(Some(cache_id), max_cache_bytes);
+    }
+    if let Some(max_total_bytes) = limits.max_total_bytes {
+      storage.evict_least_recently_used(None, max_total_bytes);
+    }
+    Ok(())
+  }
+}
+
+#[async_trait(?Send)]
+impl Cache for InMemoryCache {
+  type CachePutResourceType = InMemoryCachePutResource;
+
+  async fn storage_open(&self, cache_name: String) -> Result<i64, AnyError> {
+    let mut storage = self.0.lock();
+    if let Some(cache) = storage.caches.iter().find(|c| c.name == cache_name) {
+      return Ok(cache.id);
+    }
+    storage.last_cache_id += 1;
+    let id = storage.last_cache_id;
+    storage.caches.push(InMemoryCacheEntries {
+      id,
+      name: cache_name,
+      entries: HashMap::new(),
+      size: 0,
+    });
+    Ok(id)
+  }
+
+  async fn storage_has(&self, cache_name: String) -> Result<bool, AnyError> {
+    let storage = self.0.lock();
+    Ok(storage.caches.iter().any(|c| c.name == cache_name))
+  }
+
+  async fn storage_delete(&self, cache_name: String) -> Result<bool, AnyError> {
+    let mut storage = self.0.lock();
+    let storage = &mut *storage;
+    let Some(index) = storage.caches.iter().position(|c| c.name == cache_name)
+    else {
+      return Ok(false);
+    };
+    let cache = storage.caches.remove(index);
+    storage.size -= cache.size;
+    for entry in cache.entries.values() {
+      storage.lru.remove(&entry.last_access);
+    }
+    Ok(true)
+  }
+
+  async fn storage_list(&self) -> Result<Vec<CacheStorageInfo>, AnyError> {
+    let storage = self.0.lock();
+    Ok(
+      storage
+        .caches
+        .iter()
+        .map(|cache| CacheStorageInfo {
+          cache_name: cache.name.clone(),
+          entries: cache.entries.len() as u64,
+          size: cache.size,
+        })
+        .collect(),
+    )
+  }
+
+  async fn put_create(
+    &self,
+    request_response: CachePutRequest,
+  ) -> Result<Option<Rc<InMemoryCachePutResource>>, AnyError> {
+    if request_response.response_has_body {
+      Ok(Some(Rc::new(InMemoryCachePutResource {
+        cache: self.clone(),
+        put_request: request_response,
+        body: Default::default(),
+      })))
+    } else {
+      self.insert(request_response, None)?;
+      Ok(None)
+    }
+  }
+
+  async fn put_finish(
+    &self,
+    resource: Rc<InMemoryCachePutResource>,
+  ) -> Result<(), AnyError> {
+    let body = resource.body.take();
+    resource
+      .cache
+      .insert(resource.put_request.clone(), Some(body.into()))
+  }
+
+  async fn r#match(
+    &self,
+    request: CacheMatchRequest,
+  ) -> Result<
+    Option<(CacheMatchResponseMeta, Option<Rc<dyn Resource>>)>,
+    AnyError,
+  > {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)?;
+    let mut storage = self.0.lock();
+    let storage = &mut *storage;
+    let last_access = storage.next_access();
+    let Some(cache) =
+      storage.caches.iter_mut().find(|c| c.id == request.cache_id)
+    else {
+      return Ok(None);
+    };
+    let Some(entry) = cache.entries.get_mut(&request.request_url) else {
+      return Ok(None);
+    };
+    // Stale responses are evicted instead of being served.
+    if matches!(entry.expires_at, Some(expires_at) if expires_at <= now.as_secs())
+    {
+      storage.remove_entry(request.cache_id, &request.request_url);
+      return Ok(None);
+    }
+    // From https://w3c.github.io/ServiceWorker/#request-matches-cached-item-algorithm
+    // If there's Vary header in the response, ensure all the
+    // headers of the cached request match the query request.
+    if let Some(vary_header) = get_header("vary", &entry.response_headers) {
+      if !vary_header_matches(
+        &vary_header,
+        &request.request_headers,
+        &entry.request_headers,
+      ) {
+        return Ok(None);
+      }
+    }
+    if let Some(lru_entry) = storage.lru.remove(&entry.last_access) {
+      storage.lru.insert(last_access, lru_entry);
+    }
+    entry.last_access = last_access;
+    let meta = CacheMatchResponseMeta {
+      response_status: entry.response_status,
+      response_status_text: entry.response_status_text.clone(),
+      request_headers: entry.request_headers.clone(),
+      response_headers: entry.response_headers.clone(),
+    };
+    let body = entry.response_body.clone().map(|body| {
+      Rc::new(InMemoryCacheResponseResource {
+        body,
+        position: Cell::new(0),
+      }) as Rc<dyn Resource>
+    });
+    Ok(Some((meta, body)))
+  }
+
+  async fn delete(
+    &self,
+    request: CacheDeleteRequest,
+  ) -> Result<bool, AnyError> {
+    let mut storage = self.0.lock();
+    let removed = storage.remove_entry(request.cache_id, &request.request_url);
+    Ok(removed.is_some())
+  }
+}
+
+pub struct InMemoryCachePutResource {
+  cache: InMemoryCache,
+  put_request: CachePutRequest,
+  body: RefCell<Vec<u8>>,
+}
+
+impl InMemoryCachePutResource {
+  pub(crate) async fn write(
+    self: Rc<Self>,
+    data: &[u8],
+  ) -> Result<usize, AnyError> {
+    self.body.borrow_mut().extend_from_slice(data);
+    Ok(data.len())
+  }
+}
+
+impl Resource for InMemoryCachePutResource {
+  fn name(&self) -> Cow<str> {
+    "InMemoryCachePutResource".into()
+  }
+
+  deno_core::impl_writable!();
+}
+
+pub struct InMemoryCacheResponseResource {
+  body: Arc<[u8]>,
+  position: Cell<usize>,
+}
+
+impl InMemoryCacheResponseResource {
+  async fn read(self: Rc<Self>, data: &mut [u8]) -> Result<usize, AnyError> {
+    let position = self.position.get();
+    let remaining = &self.body[position..];
+    let nread = remaining.len().min(data.len());
+    data[..nread].copy_from_slice(&remaining[..nread]);
+    self.position.set(position + nread);
+    Ok(nread)
+  }
+}
+
+impl Resource for InMemoryCacheResponseResource {
+  deno_core::impl_readable_byob!();
+
+  fn name(&self) -> Cow<str> {
+    "InMemoryCacheResponseResource".into()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_in_memory_cache() {
+    use deno_core::futures::executor::block_on;
+
+    let cache = InMemoryCache::default();
+    let put = |cache_id: i64, request_url: &str, vary: Option<&str>| {
+      let mut response_headers = Vec::new();
+      if let Some(vary) = vary {
+        response_headers
+          .push((ByteString::from("vary"), ByteString::from(vary)));
+      }
+      CachePutRequest {
+        cache_id,
+        request_url: request_url.to_string(),
+        request_headers: vec![(
+          ByteString::from("accept-encoding"),
+          ByteString::from("gzip"),
+        )],
+        response_headers,
+        response_has_body: false,
+        response_status: 200,
+        response_status_text: "OK".to_string(),
+      }
+    };
+    let matches = |cache_id: i64, request_url: &str, accept_encoding: &str| {
+      let request = CacheMatchRequest {
+        cache_id,
+        request_url: request_url.to_string(),
+        request_headers: vec![(
+          ByteString::from("accept-encoding"),
+          ByteString::from(accept_encoding),
+        )],
+      };
+      block_on(cache.r#match(request)).unwrap().is_some()
+    };
+
+    let id_a = block_on(cache.storage_open("a".to_string())).unwrap();
+    let id_b = block_on(cache.storage_open("b".to_string())).unwrap();
+    assert_ne!(id_a, id_b);
+    assert_eq!(block_on(cache.storage_open("a".to_string())).unwrap(), id_a);
+
+    cache
+      .insert(put(id_a, "https://deno.land/", None), None)
+      .unwrap();
+    cache
+      .insert(
+        put(id_b, "https://deno.land/vary", Some("accept-encoding")),
+        None,
+      )
+      .unwrap();
+    assert!(matches(id_a, "https://deno.land/", "br"));
+    assert!(!matches(id_b, "https://deno.land/", "gzip"));
+    assert!(matches(id_b, "https://deno.land/vary", "gzip"));
+    assert!(!matches(id_b, "https://deno.land/vary", "br"));
+
+    let caches = block_on(cache.storage_list()).unwrap();
+    assert_eq!(
+      caches
+        .iter()
+        .map(|c| c.cache_name.as_str())
+        .collect::<Vec<_>>(),
+      vec!["a", "b"]
+    );
+    assert_eq!(caches[0].entries, 1);
+
+    assert!(block_on(cache.storage_delete("a".to_string())).unwrap());
+    assert!(!block_on(cache.storage_has("a".to_string())).unwrap());
+    assert!(!matches(id_a, "https://deno.land/", "br"));
+  }
+
+  #[test]
+  fn test_in_memory_cache_expiry() {
+    use deno_core::futures::executor::block_on;
+
+    let cache = InMemoryCache::default();
+    let cache_id = block_on(cache.storage_open("a".to_string())).unwrap();
+    let put = |request_url: &str, cache_control: &str| CachePutRequest {
+      cache_id,
+      request_url: request_url.to_string(),
+      request_headers: vec![],
+      response_headers: vec![(
+        ByteString::from("cache-control"),
+        ByteString::from(cache_control),
+      )],
+      response_has_body: false,
+      response_status: 200,
+      response_status_text: "OK".to_string(),
+    };
+    let matches = |request_url: &str| {
+      let request = CacheMatchRequest {
+        cache_id,
+        request_url: request_url.to_string(),
+        request_headers: vec![],
+      };
+      block_on(cache.r#match(request)).unwrap().is_some()
+    };
+
+    cache
+      .insert(put("https://deno.land/stale", "max-age=0"), None)
+      .unwrap();
+    cache
+      .insert(put("https://deno.land/fresh", "max-age=3600"), None)
+      .unwrap();
+    assert!(!matches("https://deno.land/stale"));
+    assert!(matches("https://deno.land/fresh"));
+    // the stale response is evicted rather than kept around
+    let caches = block_on(cache.storage_list()).unwrap();
+    assert_eq!(caches[0].entries, 1);
+  }
+
+  #[test]
+  fn test_in_memory_cache_limits() {
+    use deno_core::futures::executor::block_on;
+
+    let cache = InMemoryCache::with_limits(CacheLimits {
+      max_cache_bytes: Some(25),
+      max_total_bytes: Some(35),
+    });
+    let id_a = block_on(cache.storage_open("a".to_string())).unwrap();
+    let id_b = block_on(cache.storage_open("b".to_string())).unwrap();
+    let put = |cache_id: i64, request_url: &str| CachePutRequest {
+      cache_id,
+      request_url: request_url.to_string(),
+      request_headers: vec![],
+      response_headers: vec![],
+      response_has_body: true,
+      response_status: 200,
+      response_status_text: "OK".to_string(),
+    };
+    let body = || Some(Arc::from(&[0u8; 10][..]));
+    let matches = |cache_id: i64, request_url: &str| {
+      let request = CacheMatchRequest {
+        cache_id,
+        request_url: request_url.to_string(),
+        request_headers: vec![],
+      };
+      block_on(cache.r#match(request)).unwrap().is_some()
+    };
+
+    cache.insert(put(id_a, "https://a.com/1"), body()).unwrap();
+    cache.insert(put(id_a, "https://a.com/2"), body()).unwrap();
+    // using the first response makes the second one the least recently used
+    assert!(matches(id_a, "https://a.com/1"));
+    cache.insert(put(id_a, "https://a.com/3"), body()).unwrap();
+    assert!(matches(id_a, "https://a.com/1"));
+    assert!(!matches(id_a, "https://a.com/2"));
+    assert!(matches(id_a, "https://a.com/3"));
+
+    // over the total limit, the least recently used response of any cache goes
+    cache.insert(put(id_b, "https://b.com/1"), body()).unwrap();
+    cache.insert(put(id_b, "https://b.com/2"), body()).unwrap();
+    assert!(!matches(id_a, "https://a.com/1"));
+    assert!(matches(id_a, "https://a.com/3"));
+    assert!(matches(id_b, "https://b.com/1"));
+    assert!(matches(id_b, "https://b.com/2"));
+
+    // replaced responses and deleted caches no longer count
+    cache.insert(put(id_b, "https://b.com/1"), body()).unwrap();
+    let sizes = block_on(cache.storage_list())
+      .unwrap()
+      .into_iter()
+      .map(|info| info.size)
+      .collect::<Vec<_>>();
+    assert_eq!(sizes, vec![10, 20]);
+    assert!(block_on(cache.storage_delete("b".to_string())).unwrap());
+    assert_eq!(cache.0.lock().size, 10);
+  }
+}
@@ -1,6 +1,7 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
 
 use std::borrow::Cow;
+use std::path::Path;
 use std::path::PathBuf;
 use std::rc::Rc;
 use std::sync::Arc;
@@ -22,23 +23,31 @@ use tokio::io::AsyncReadExt;
 use tokio::io::AsyncWriteExt;
 
 use crate::deserialize_headers;
+use crate::get_expiry;
 use crate::get_header;
 use crate::serialize_headers;
 use crate::vary_header_matches;
 use crate::Cache;
 use crate::CacheDeleteRequest;
+use crate::CacheLimits;
 use crate::CacheMatchRequest;
 use crate::CacheMatchResponseMeta;
 use crate::CachePutRequest;
+use crate::CacheStorageInfo;
 
 #[derive(Clone)]
 pub struct SqliteBackedCache {
   pub connection: Arc<Mutex<Connection>>,
   pub cache_storage_dir: PathBuf,
+  pub limits: CacheLimits,
 }
 
 impl SqliteBackedCache {
   pub fn new(cache_storage_dir: PathBuf) -> Self {
+    Self::with_limits(cache_storage_dir, CacheLimits::default())
+  }
+
+  pub fn with_limits(cache_storage_dir: PathBuf, limits: CacheLimits) -> Self {
     {
       std::fs::create_dir_all(&cache_storage_dir)
         .expect("failed to create cache dir");
@@ -77,6 +86,9 @@ impl SqliteBackedCache {
                     response_status_text   TEXT,
                     response_body_key      TEXT,
                     last_inserted_at       INTEGER UNSIGNED NOT NULL,
+                    last_accessed_at       INTEGER UNSIGNED NOT NULL DEFAULT 0,
+                    expires_at             INTEGER UNSIGNED,
+                    response_size          INTEGER UNSIGNED NOT NULL DEFAULT 0,
                     FOREIGN KEY (cache_id) REFERENCES cache_storage(id) ON DELETE CASCADE,
 
                     UNIQUE (cache_id, request_url)
@@ -84,9 +96,12 @@ impl SqliteBackedCache {
           (),
         )
         .expect("failed to create request_response_list table");
+      add_missing_columns(&connection)
+        .expect("failed to migrate request_response_list table");
       SqliteBackedCache {
         connection: Arc::new(Mutex::new(connection)),
         cache_storage_dir,
+        limits,
       }
     }
   }
@@ -116,7 +131,7 @@ impl Cache for SqliteBackedCache {
           Ok(id)
         },
       )?;
-      let responses_dir = get_responses_dir(cache_storage_dir, cache_id);
+      let responses_dir = get_responses_dir(&cache_storage_dir, cache_id);
       std::fs::create_dir_all(responses_dir)?;
       Ok::<i64, AnyError>(cache_id)
     })
@@ -159,6 +174,10 @@ impl Cache for SqliteBackedCache {
         )
         .optional()?;
       if let Some(cache_id) = maybe_cache_id {
+        db.execute(
+          "DELETE FROM request_response_list WHERE cache_id = ?1",
+          params![cache_id],
+        )?;
         let cache_dir = cache_storage_dir.join(cache_id.to_string());
         if cache_dir.exists() {
           std::fs::remove_dir_all(cache_dir)?;
@@ -169,12 +188,37 @@ impl Cache for SqliteBackedCache {
     .await?
   }
 
+  async fn storage_list(&self) -> Result<Vec<CacheStorageInfo>, AnyError> {
+    let db = self.connection.clone();
+    spawn_blocking(move || {
+      let db = db.lock();
+      let mut stmt = db.prepare(
+        "SELECT cache_storage.cache_name, COUNT(request_response_list.id),
+                COALESCE(SUM(request_response_list.response_size), 0)
+             FROM cache_storage
+             LEFT JOIN request_response_list
+               ON request_response_list.cache_id = cache_storage.id
+             GROUP BY cache_storage.id
+             ORDER BY cache_storage.id",
+      )?;
+      let caches = stmt
+        .query_map((), |row| {
+          Ok(CacheStorageInfo {
+            cache_name: row.get(0)?,
+            entries: row.get(1)?,
+            size: row.get(2)?,
+          })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+      Ok::<Vec<CacheStorageInfo>, AnyError>(caches)
+    })
+    .await?
+  }
+
   async fn put_create(
     &self,
     request_response: CachePutRequest,
   ) -> Result<Option<Rc<CachePutResource>>, AnyError> {
-    let db = self.connection.clone();
-    let cache_storage_dir = self.cache_storage_dir.clone();
     let now = SystemTime::now().duration_since(UNIX_EPOCH)?;
     let response_body_key = if request_response.response_has_body {
       Some(hash(&format!(
@@ -188,18 +232,18 @@ impl Cache for SqliteBackedCache {
 
     if let Some(body_key) = response_body_key {
       let responses_dir =
-        get_responses_dir(cache_storage_dir, request_response.cache_id);
+        get_responses_dir(&self.cache_storage_dir, request_response.cache_id);
       let response_path = responses_dir.join(&body_key);
       let file = tokio::fs::File::create(response_path).await?;
       Ok(Some(Rc::new(CachePutResource {
         file: AsyncRefCell::new(file),
-        db,
+        cache: self.clone(),
         put_request: request_response,
         response_body_key: body_key,
         start_time: now.as_secs(),
       })))
     } else {
-      insert_cache_asset(db, request_response, None).await?;
+      insert_cache_asset(self.clone(), request_response, None, 0).await?;
       Ok(None)
     }
   }
@@ -223,7 +267,7 @@ impl Cache for SqliteBackedCache {
     let query_result = spawn_blocking(move || {
       let db = db.lock();
       let result = db.query_row(
-        "SELECT response_body_key, response_headers, response_status, response_status_text, request_headers
+        "SELECT response_body_key, response_headers, response_status, response_status_text, request_headers, id, expires_at
              FROM request_response_list
              WHERE cache_id = ?1 AND request_url = ?2",
         (request.cache_id, &request.request_url),
@@ -233,12 +277,35 @@ impl Cache for SqliteBackedCache {
           let response_status: u16 = row.get(2)?;
           let response_status_text: String = row.get(3)?;
           let request_headers: Vec<u8> = row.get(4)?;
+          let id: i64 = row.get(5)?;
+          let expires_at: Option<u64> = row.get(6)?;
           let response_headers: Vec<(ByteString, ByteString)> = deserialize_headers(&response_headers);
           let request_headers: Vec<(ByteString, ByteString)> = deserialize_headers(&request_headers);
-          Ok((CacheMatchResponseMeta {request_headers, response_headers,response_status,response_status_text}, response_body_key))
+          Ok((CacheMatchResponseMeta {request_headers, response_headers,response_status,response_status_text}, response_body_key, id, expires_at))
         },
-      );
-      result.optional()
+      ).optional()?;
+      let Some((cache_meta, response_body_key, id, expires_at)) = result else {
+        return Ok(None);
+      };
+      // Stale responses are evicted instead of being served.
+      let now = SystemTime::now().duration_since(UNIX_EPOCH)?;
+      if matches!(expires_at, Some(expires_at) if expires_at <= now.as_secs()) {
+        db.execute(
+          "DELETE FROM request_response_list WHERE id = ?1",
+          params![id],
+        )?;
+        remove_response_body(
+          &cache_storage_dir,
+          request.cache_id,
+          response_body_key.as_deref(),
+        );
+        return Ok(None);
+      }
+      db.execute(
+        "UPDATE request_response_list SET last_accessed_at = ?1 WHERE id = ?2",
+        params![now.as_millis() as u64, id],
+      )?;
+      Ok::<_, AnyError>(Some((cache_meta, response_body_key)))
     })
     .await??;
 
@@ -259,7 +326,7 @@ impl Cache for SqliteBackedCache {
           }
         }
         let response_path =
-          get_responses_dir(cache_storage_dir, request.cache_id)
+          get_responses_dir(&self.cache_storage_dir, request.cache_id)
             .join(response_body_key);
         let file = tokio::fs::File::open(response_path).await?;
         return Ok(Some((
@@ -279,55 +346,203 @@ impl Cache for SqliteBackedCache {
     request: CacheDeleteRequest,
   ) -> Result<bool, AnyError> {
     let db = self.connection.clone();
+    let cache_storage_dir = self.cache_storage_dir.clone();
     spawn_blocking(move || {
-      // TODO(@satyarohith): remove the response body from disk if one exists
       let db = db.lock();
-      let rows_effected = db.execute(
-        "DELETE FROM request_response_list WHERE cache_id = ?1 AND request_url = ?2",
-        (request.cache_id, &request.request_url),
-      )?;
-      Ok::<bool, AnyError>(rows_effected > 0)
+      let maybe_response_body_key = db
+        .query_row(
+          "DELETE FROM request_response_list WHERE cache_id = ?1 AND request_url = ?2
+             RETURNING response_body_key",
+          (request.cache_id, &request.request_url),
+          |row| {
+            let response_body_key: Option<String> = row.get(0)?;
+            Ok(response_body_key)
+          },
+        )
+        .optional()?;
+      if let Some(response_body_key) = &maybe_response_body_key {
+        remove_response_body(
+          &cache_storage_dir,
+          request.cache_id,
+          response_body_key.as_deref(),
+        );
+      }
+      Ok::<bool, AnyError>(maybe_response_body_key.is_some())
     })
     .await?
   }
 }
 
 async fn insert_cache_asset(
-  db: Arc<Mutex<rusqlite::Connection>>,
+  cache: SqliteBackedCache,
   put: CachePutRequest,
   response_body_key: Option<String>,
+  response_body_size: u64,
 ) -> Result<Option<String>, deno_core::anyhow::Error> {
   spawn_blocking(move || {
-    let maybe_response_body = {
-      let db = db.lock();
-      db.query_row(
-        "INSERT OR REPLACE INTO request_response_list
-             (cache_id, request_url, request_headers, response_headers,
-              response_body_key, response_status, response_status_text, last_inserted_at)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
-             RETURNING response_body_key",
-        (
-          put.cache_id,
-          put.request_url,
-          serialize_headers(&put.request_headers),
-          serialize_headers(&put.response_headers),
-          response_body_key,
-          put.response_status,
-          put.response_status_text,
-          SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs(),
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)?;
+    let request_headers = serialize_headers(&put.request_headers);
+    let response_headers = serialize_headers(&put.response_headers);
+    let response_size = response_body_size
+      + (request_headers.len() + response_headers.len()) as u64;
+    let expires_at = get_expiry(&put.response_headers, now.as_secs());
+    let db = cache.connection.lock();
+    // The body of the response being replaced, if any, isn't reachable
+    // anymore once the new one is inserted.
+    let replaced_response_body_key: Option<String> = db
+      .query_row(
+        "SELECT response_body_key FROM request_response_list
+             WHERE cache_id = ?1 AND request_url = ?2",
+        (put.cache_id, &put.request_url),
+        |row| row.get(0),
+      )
+      .optional()?
+      .flatten();
+    let maybe_response_body = db.query_row(
+      "INSERT OR REPLACE INTO request_response_list
+           (cache_id, request_url, request_headers, response_headers,
+            response_body_key, response_status, response_status_text, last_inserted_at,
+            last_accessed_at, expires_at, response_size)
+           VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)
+           RETURNING response_body_key",
+      (
+        put.cache_id,
+        &put.request_url,
+        request_headers,
+        response_headers,
+        response_body_key,
+        put.response_status,
+        put.response_status_text,
+        now.as_secs(),
+        now.as_millis() as u64,
+        expires_at,
+        response_size,
+      ),
+      |row| {
+        let response_body_key: Option<String> = row.get(0)?;
+        Ok(response_body_key)
+      },
+    )?;
+    if replaced_response_body_key != maybe_response_body {
+      remove_response_body(
+        &cache.cache_storage_dir,
+        put.cache_id,
+        replaced_response_body_key.as_deref(),
+      );
+    }
+    if let Some(max_cache_bytes) = cache.limits.max_cache_bytes {
+      evict_least_recently_used(
+        &db,
+        &cache.cache_storage_dir,
+        Some(put.cache_id),
+        max_cache_bytes,
+      )?;
+    }
+    if let Some(max_total_bytes) = cache.limits.max_total_bytes {
+      evict_least_recently_used(
+        &db,
+        &cache.cache_storage_dir,
+        None,
+        max_total_bytes,
+      )?;
+    }
+    Ok::<Option<String>, AnyError>(maybe_response_body)
+  })
+  .await?
+}
+
+/// Removes the least recently used responses of the cache with the given id,
+/// or of all caches if `None`, until they take at most `max_bytes`.
+fn evict_least_recently_used(
+  db: &Connection,
+  cache_storage_dir: &Path,
+  cache_id: Option<i64>,
+  max_bytes: u64,
+) -> Result<(), AnyError> {
+  let size: u64 = db.query_row(
+    "SELECT COALESCE(SUM(response_size), 0) FROM request_response_list
+         WHERE ?1 IS NULL OR cache_id = ?1",
+    params![cache_id],
+    |row| row.get(0),
+  )?;
+  let Some(mut excess) = size.checked_sub(max_bytes).filter(|e| *e > 0) else {
+    return Ok(());
+  };
+  let mut evicted = Vec::new();
+  {
+    let mut stmt = db.prepare(
+      "SELECT id, cache_id, response_body_key, response_size
+           FROM request_response_list
+           WHERE ?1 IS NULL OR cache_id = ?1
+           ORDER BY last_accessed_at, id",
+    )?;
+    let mut rows = stmt.query(params![cache_id])?;
+    while excess > 0 {
+      let Some(row) = rows.next()? else {
+        break;
+      };
+      let id: i64 = row.get(0)?;
+      let cache_id: i64 = row.get(1)?;
+      let response_body_key: Option<String> = row.get(2)?;
+      let response_size: u64 = row.get(3)?;
+      evicted.push((id, cache_id, response_body_key));
+      excess = excess.saturating_sub(response_size);
+    }
+  }
+  for (id, cache_id, response_body_key) in evicted {
+    db.execute(
+      "DELETE FROM request_response_list WHERE id = ?1",
+      params![id],
+    )?;
+    remove_response_body(
+      cache_storage_dir,
+      cache_id,
+      response_body_key.as_deref(),
+    );
+  }
+  Ok(())
+}
+
+/// Adds the columns introduced after the first release of the cache to
+/// databases created by older versions.
+fn add_missing_columns(connection: &Connection) -> Result<(), AnyError> {
+  let mut stmt =
+    connection.prepare("PRAGMA table_info(request_response_list)")?;
+  let columns = stmt
+    .query_map((), |row| row.get::<_, String>(1))?
+    .collect::<Result<Vec<_>, _>>()?;
+  for (name, definition) in [
+    ("last_accessed_at", "INTEGER UNSIGNED NOT NULL DEFAULT 0"),
+    ("expires_at", "INTEGER UNSIGNED"),
+    ("response_size", "INTEGER UNSIGNED NOT NULL DEFAULT 0"),
+  ] {
+    if !columns.iter().any(|column| column == name) {
+      connection.execute(
+        &format!(
+          "ALTER TABLE request_response_list ADD COLUMN {name} {definition}"
         ),
-        |row| {
-          let response_body_key: Option<String> = row.get(0)?;
-          Ok(response_body_key)
-        },
-      )?
-    };
-      Ok::<Option<String>, AnyError>(maybe_response_body)
-  }).await?
+        (),
+      )?;
+    }
+  }
+  Ok(())
+}
+
+fn remove_response_body(
+  cache_storage_dir: &Path,
+  cache_id: i64,
+  response_body_key: Option<&str>,
+) {
+  if let Some(response_body_key) = response_body_key {
+    let response_path =
+      get_responses_dir(cache_storage_dir, cache_id).join(response_body_key);
+    // the body might already be gone if the cache dir was removed
+    let _ = std::fs::remove_file(response_path);
+  }
 }
 
 #[inline]
-fn get_responses_dir(cache_storage_dir: PathBuf, cache_id: i64) -> PathBuf {
+fn get_responses_dir(cache_storage_dir: &Path, cache_id: i64) -> PathBuf {
   cache_storage_dir
     .join(cache_id.to_string())
     .join("responses")
@@ -340,7 +555,7 @@ impl deno_core::Resource for SqliteBackedCache {
 }
 
 pub struct CachePutResource {
-  pub db: Arc<Mutex<rusqlite::Connection>>,
+  pub cache: SqliteBackedCache,
   pub put_request: CachePutRequest,
   pub response_body_key: String,
   pub file: AsyncRefCell<tokio::fs::File>,
@@ -348,7 +563,10 @@ pub struct CachePutResource {
 }
 
 impl CachePutResource {
-  async fn write(self: Rc<Self>, data: &[u8]) -> Result<usize, AnyError> {
+  pub(crate) async fn write(
+    self: Rc<Self>,
+    data: &[u8],
+  ) -> Result<usize, AnyError> {
     let resource = deno_core::RcRef::map(&self, |r| &r.file);
     let mut file = resource.borrow_mut().await;
     file.write_all(data).await?;
@@ -360,10 +578,12 @@ impl CachePutResource {
     let mut file = resource.borrow_mut().await;
     file.flush().await?;
     file.sync_all().await?;
+    let response_body_size = file.metadata().await?.len();
     let maybe_body_key = insert_cache_asset(
-      self.db.clone(),
+      self.cache.clone(),
       self.put_request.clone(),
       Some(self.response_body_key.clone()),
+      response_body_size,
     )
     .await?;
     match maybe_body_key {
@@ -419,3 +639,54 @@ pub fn hash(token: &str) -> String {
   use sha2::Digest;
   format!("{:x}", sha2::Sha256::digest(token.as_bytes()))
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_evict_least_recently_used() {
+    let dir = tempfile::tempdir().unwrap();
+    let cache = SqliteBackedCache::new(dir.path().to_path_buf());
+    let db = cache.connection.lock();
+    db.execute(
+      "INSERT INTO cache_storage (cache_name) VALUES ('a'), ('b')",
+      (),
+    )
+    .unwrap();
+    for (cache_id, request_url, last_accessed_at) in [
+      (1, "https://a.com/1", 3),
+      (1, "https://a.com/2", 1),
+      (2, "https://b.com/1", 2),
+    ] {
+      db.execute(
+        "INSERT INTO request_response_list
+             (cache_id, request_url, request_headers, response_headers,
+              response_status, last_inserted_at, last_accessed_at, response_size)
+             VALUES (?1, ?2, x'', x'', 200, 0, ?3, 10)",
+        params![cache_id, request_url, last_accessed_at],
+      )
+      .unwrap();
+    }
+    let request_urls = |db: &Connection| -> Vec<String> {
+      let mut stmt = db
+        .prepare("SELECT request_url FROM request_response_list ORDER BY id")
+        .unwrap();
+      let request_urls = stmt
+        .query_map((), |row| row.get(0))
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+      request_urls
+    };
+
+    evict_least_recently_used(&db, dir.path(), Some(1), 10).unwrap();
+    assert_eq!(
+      request_urls(&db),
+      vec!["https://a.com/1", "https://b.com/1"]
+    );
+
+    evict_least_recently_used(&db, dir.path(), None, 10).unwrap();
+    assert_eq!(request_urls(&db), vec!["https://a.com/1"]);
+  }
+}
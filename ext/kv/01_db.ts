@@ -2,6 +2,8 @@
 
 // @ts-ignore internal api
 const {
+  ArrayPrototypeMap,
+  ArrayPrototypeSlice,
   AsyncGeneratorPrototype,
   BigIntPrototypeToString,
   ObjectFreeze,
@@ -12,6 +14,7 @@ const {
   SymbolToStringTag,
   Uint8ArrayPrototype,
 } = globalThis.__bootstrap.primordials;
+import { ReadableStream } from "ext:deno_web/06_streams.js";
 const core = Deno.core;
 const ops = core.ops;
 
@@ -57,6 +60,13 @@ type RawValue = {
   value: bigint;
 };
 
+type RawWatchKeyOutput = {
+  kind: "changed";
+  entry: RawKvEntry | null;
+} | {
+  kind: "unchanged";
+};
+
 const kvSymbol = Symbol("KvRid");
 
 class Kv {
@@ -308,6 +318,41 @@ class Kv {
     finishMessageOps.clear();
   }
 
+  watch(keys: Deno.KvKey[]) {
+    const lastEntries: Deno.KvEntryMaybe<unknown>[] = ArrayPrototypeMap(
+      keys,
+      (key: Deno.KvKey) => ({ key, value: null, versionstamp: null }),
+    );
+    let rid: number | undefined;
+    return new ReadableStream({
+      start: async () => {
+        rid = await core.opAsync("op_kv_watch", this.#rid, keys);
+      },
+      pull: async (controller) => {
+        const updates: RawWatchKeyOutput[] | null = await core.opAsync(
+          "op_kv_watch_next",
+          rid,
+        );
+        if (updates === null) {
+          core.tryClose(rid);
+          controller.close();
+          return;
+        }
+        for (let i = 0; i < updates.length; i++) {
+          const update = updates[i];
+          if (update.kind === "unchanged") continue;
+          lastEntries[i] = update.entry === null
+            ? { key: keys[i], value: null, versionstamp: null }
+            : deserializeValue(update.entry);
+        }
+        controller.enqueue(ArrayPrototypeSlice(lastEntries));
+      },
+      cancel() {
+        if (rid !== undefined) core.tryClose(rid);
+      },
+    });
+  }
+
   close() {
     core.close(this.#rid);
     this.#closed = true;
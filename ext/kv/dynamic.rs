@@ -14,6 +14,7 @@ use crate::QueueMessageHandle;
 use crate::ReadRange;
 use crate::ReadRangeOutput;
 use crate::SnapshotReadOptions;
+use crate::WatchStream;
 use async_trait::async_trait;
 use deno_core::error::type_error;
 use deno_core::error::AnyError;
@@ -134,6 +135,12 @@ pub trait DynamicDb {
     state: Rc<RefCell<OpState>>,
   ) -> Result<Box<dyn QueueMessageHandle>, AnyError>;
 
+  async fn dyn_watch(
+    &self,
+    state: Rc<RefCell<OpState>>,
+    keys: Vec<Vec<u8>>,
+  ) -> Result<WatchStream, AnyError>;
+
   fn dyn_close(&self);
 }
 
@@ -165,6 +172,14 @@ impl Database for Box<dyn DynamicDb> {
     (**self).dyn_dequeue_next_message(state).await
   }
 
+  async fn watch(
+    &self,
+    state: Rc<RefCell<OpState>>,
+    keys: Vec<Vec<u8>>,
+  ) -> Result<WatchStream, AnyError> {
+    (**self).dyn_watch(state, keys).await
+  }
+
   fn close(&self) {
     (**self).dyn_close()
   }
@@ -200,6 +215,14 @@ where
     Ok(Box::new(self.dequeue_next_message(state).await?))
   }
 
+  async fn dyn_watch(
+    &self,
+    state: Rc<RefCell<OpState>>,
+    keys: Vec<Vec<u8>>,
+  ) -> Result<WatchStream, AnyError> {
+    self.watch(state, keys).await
+  }
+
   fn dyn_close(&self) {
     self.close()
   }
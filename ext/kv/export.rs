@@ -0,0 +1,206 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+
+//! A portable, newline-delimited JSON format for the contents of a KV
+//! database. Every line holds one [ExportedEntry].
+
+use std::cell::RefCell;
+use std::io::BufRead;
+use std::io::Write;
+use std::rc::Rc;
+
+use chrono::Utc;
+use deno_core::anyhow::Context;
+use deno_core::error::type_error;
+use deno_core::error::AnyError;
+use deno_core::futures;
+use deno_core::OpState;
+use serde::Deserialize;
+use serde::Serialize;
+use tokio::sync::mpsc;
+
+use crate::codec::decode_key;
+use crate::sqlite::SqliteDb;
+use crate::AtomicWrite;
+use crate::Database;
+use crate::KvEntry;
+use crate::KvMutation;
+use crate::MutationKind;
+use crate::Value;
+
+const EXPORT_BATCH_SIZE: usize = 500;
+const IMPORT_BATCH_SIZE: usize = 100;
+const IMPORT_BATCH_MAX_BYTES: usize = 512 * 1024;
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportedEntry {
+  /// The key, as encoded by [crate::codec::encode_key], in URL-safe base64.
+  pub key: String,
+  pub value: ExportedValue,
+  /// The versionstamp of the entry in the source database, in hex. It is
+  /// informational only: imported entries get a new versionstamp.
+  pub versionstamp: String,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub expire_at: Option<u64>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(tag = "kind", content = "data", rename_all = "snake_case")]
+pub enum ExportedValue {
+  /// Base64 encoded V8 serialized value.
+  V8(String),
+  /// Base64 encoded bytes.
+  Bytes(String),
+  /// Decimal string, to avoid precision loss in JSON.
+  U64(String),
+}
+
+impl From<KvEntry> for ExportedEntry {
+  fn from(entry: KvEntry) -> Self {
+    let value = match entry.value {
+      Value::V8(buf) => ExportedValue::V8(base64::encode(buf)),
+      Value::Bytes(buf) => ExportedValue::Bytes(base64::encode(buf)),
+      Value::U64(n) => ExportedValue::U64(n.to_string()),
+    };
+    ExportedEntry {
+      key: base64::encode_config(entry.key, base64::URL_SAFE),
+      value,
+      versionstamp: hex::encode(entry.versionstamp),
+      expire_at: entry.expire_at,
+    }
+  }
+}
+
+impl TryFrom<ExportedEntry> for KvMutation {
+  type Error = AnyError;
+  fn try_from(entry: ExportedEntry) -> Result<Self, AnyError> {
+    let key = base64::decode_config(&entry.key, base64::URL_SAFE)
+      .map_err(|_| type_error("invalid key encoding"))?;
+    if key.is_empty() {
+      return Err(type_error("key cannot be empty"));
+    }
+    // Make sure the key is well-formed before writing it.
+    decode_key(&key)?;
+    let value = match entry.value {
+      ExportedValue::V8(data) => Value::V8(
+        base64::decode(data).map_err(|_| type_error("invalid value"))?,
+      ),
+      ExportedValue::Bytes(data) => Value::Bytes(
+        base64::decode(data).map_err(|_| type_error("invalid value"))?,
+      ),
+      ExportedValue::U64(n) => {
+        Value::U64(n.parse().map_err(|_| type_error("invalid u64 value"))?)
+      }
+    };
+    Ok(KvMutation {
+      key,
+      kind: MutationKind::Set(value),
+      expire_at: entry.expire_at,
+    })
+  }
+}
+
+/// Writes all live entries of `db` to `writer`, in key order. Returns the
+/// number of exported entries.
+///
+/// The entries are read from a single consistent snapshot of the database, so
+/// the export reflects its state at one point in time even if it is written to
+/// while the export runs. Only SQLite databases can be exported: the remote
+/// protocol does not report expiration times, so they would be lost.
+pub async fn export_database(
+  db: &SqliteDb,
+  writer: &mut impl Write,
+) -> Result<usize, AnyError> {
+  let (entries_tx, mut entries_rx) = mpsc::channel(1);
+  let read = db.read_snapshot(EXPORT_BATCH_SIZE, entries_tx);
+  let write = async move {
+    let mut count = 0;
+    let now = Utc::now().timestamp_millis() as u64;
+    while let Some(entries) = entries_rx.recv().await {
+      for entry in entries {
+        if entry.expire_at.is_some_and(|expire_at| expire_at <= now) {
+          continue;
+        }
+        serde_json::to_writer(&mut *writer, &ExportedEntry::from(entry))?;
+        writer.write_all(b"\n")?;
+        count += 1;
+      }
+    }
+    writer.flush()?;
+    Ok::<_, AnyError>(count)
+  };
+  let (read_result, write_result) = futures::join!(read, write);
+  // A write error stops the read early, so report it first.
+  let count = write_result?;
+  read_result?;
+  Ok(count)
+}
+
+/// Reads entries in the export format from `reader` and writes them to `db`.
+/// Entries that have already expired are skipped. Returns the number of
+/// imported entries.
+///
+/// `supports_expiration` must be false for backends that drop the expiration
+/// of written entries (remote databases). Importing an entry that expires
+/// into such a database fails rather than making the entry permanent.
+pub async fn import_database<DB: Database>(
+  db: &DB,
+  state: Rc<RefCell<OpState>>,
+  reader: impl BufRead,
+  supports_expiration: bool,
+) -> Result<usize, AnyError> {
+  let mut batch = Vec::with_capacity(IMPORT_BATCH_SIZE);
+  let mut batch_bytes = 0;
+  let mut count = 0;
+  let now = Utc::now().timestamp_millis() as u64;
+  for (i, line) in reader.lines().enumerate() {
+    let line = line?;
+    if line.trim().is_empty() {
+      continue;
+    }
+    let entry: ExportedEntry = serde_json::from_str(&line)
+      .with_context(|| format!("Invalid entry on line {}", i + 1))?;
+    if let Some(expire_at) = entry.expire_at {
+      if expire_at <= now {
+        continue;
+      }
+      if !supports_expiration {
+        return Err(type_error(format!(
+          "Entry on line {} has an expiration time, which this database does not support",
+          i + 1
+        )));
+      }
+    }
+    let mutation = KvMutation::try_from(entry)
+      .with_context(|| format!("Invalid entry on line {}", i + 1))?;
+    batch_bytes += mutation.key.len() + line.len();
+    batch.push(mutation);
+    count += 1;
+
+    if batch.len() >= IMPORT_BATCH_SIZE || batch_bytes >= IMPORT_BATCH_MAX_BYTES
+    {
+      write_batch(db, state.clone(), std::mem::take(&mut batch)).await?;
+      batch_bytes = 0;
+    }
+  }
+  if !batch.is_empty() {
+    write_batch(db, state, batch).await?;
+  }
+  Ok(count)
+}
+
+async fn write_batch<DB: Database>(
+  db: &DB,
+  state: Rc<RefCell<OpState>>,
+  mutations: Vec<KvMutation>,
+) -> Result<(), AnyError> {
+  let write = AtomicWrite {
+    checks: vec![],
+    mutations,
+    enqueues: vec![],
+  };
+  match db.atomic_write(state, write).await? {
+    Some(_) => Ok(()),
+    None => Err(type_error("Failed to write imported entries")),
+  }
+}
@@ -0,0 +1,190 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+
+//! Secondary indexes that are maintained by the database itself.
+//!
+//! An index covers all entries whose key starts with a given prefix. For each
+//! covered entry, the index extractor derives the indexed key parts from the
+//! entry's value. The database then stores an index entry under
+//! `[...index_prefix, ...indexed_parts]` for unique indexes, and under
+//! `[...index_prefix, ...indexed_parts, ...primary_key]` for non-unique
+//! indexes. The value of an index entry is the encoded primary key, as
+//! [Value::Bytes].
+//!
+//! Index entries are written in the same commit as the primary mutation, and
+//! share the expiration of the primary entry.
+
+use std::sync::Arc;
+
+use deno_core::error::type_error;
+use deno_core::error::AnyError;
+
+use crate::codec::decode_key;
+use crate::codec::encode_key;
+use crate::Key;
+use crate::KeyPart;
+use crate::Value;
+
+/// Derives the indexed key parts from the primary key and value of an entry.
+/// Returning `None` leaves the entry out of the index.
+///
+/// The extractor runs inside the database transaction, possibly on another
+/// thread, and must be deterministic: it is also used to find the index entry
+/// of the previous value when an entry is changed or deleted.
+pub type IndexExtractor =
+  Arc<dyn Fn(&Key, &Value) -> Option<Vec<KeyPart>> + Send + Sync>;
+
+/// The definition of a secondary index.
+#[derive(Clone)]
+pub struct KvIndex {
+  /// The name of the index.
+  pub name: String,
+  primary_prefix: Vec<u8>,
+  index_prefix: Key,
+  unique: bool,
+  extract: IndexExtractor,
+}
+
+impl KvIndex {
+  /// Creates an index over all entries under `primary_prefix`, stored under
+  /// `index_prefix`. A unique index rejects commits that would map two
+  /// primary keys to the same indexed key parts.
+  pub fn new(
+    name: impl Into<String>,
+    primary_prefix: Key,
+    index_prefix: Key,
+    unique: bool,
+    extract: IndexExtractor,
+  ) -> Result<Self, AnyError> {
+    let primary_prefix = encode_key(&primary_prefix)?;
+    if primary_prefix.is_empty() || index_prefix.0.is_empty() {
+      return Err(type_error("Index prefixes cannot be empty"));
+    }
+    Ok(Self {
+      name: name.into(),
+      primary_prefix,
+      index_prefix,
+      unique,
+      extract,
+    })
+  }
+
+  pub fn is_unique(&self) -> bool {
+    self.unique
+  }
+
+  /// Whether the entry with the encoded key `key` is covered by this index.
+  pub fn covers(&self, key: &[u8]) -> bool {
+    key.len() > self.primary_prefix.len()
+      && key.starts_with(&self.primary_prefix)
+  }
+
+  /// Returns the encoded key of the index entry for the given primary entry,
+  /// or `None` if the entry is not indexed.
+  pub fn entry_key(
+    &self,
+    primary_key: &[u8],
+    value: &Value,
+  ) -> Result<Option<Vec<u8>>, AnyError> {
+    let decoded_key = decode_key(primary_key)?;
+    let Some(parts) = (self.extract)(&decoded_key, value) else {
+      return Ok(None);
+    };
+    let mut key = self.index_prefix.clone();
+    key.0.extend(parts);
+    let mut key = encode_key(&key)?;
+    if !self.unique {
+      // Encoded key parts are self-delimiting, so appending the encoded
+      // primary key yields a valid key.
+      key.extend_from_slice(primary_key);
+    }
+    Ok(Some(key))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::sync::Arc;
+
+  use crate::codec::decode_key;
+  use crate::codec::encode_key;
+  use crate::Key;
+  use crate::KeyPart;
+  use crate::Value;
+
+  use super::KvIndex;
+
+  fn key(parts: &[&str]) -> Key {
+    Key(
+      parts
+        .iter()
+        .map(|part| KeyPart::String(part.to_string()))
+        .collect(),
+    )
+  }
+
+  fn by_bytes_value(unique: bool) -> KvIndex {
+    KvIndex::new(
+      "users_by_email",
+      key(&["users"]),
+      key(&["users_by_email"]),
+      unique,
+      Arc::new(|_, value| match value {
+        Value::Bytes(email) => Some(vec![KeyPart::String(
+          String::from_utf8(email.clone()).ok()?,
+        )]),
+        _ => None,
+      }),
+    )
+    .unwrap()
+  }
+
+  #[test]
+  fn covers() {
+    let index = by_bytes_value(true);
+    assert!(index.covers(&encode_key(&key(&["users", "a"])).unwrap()));
+    assert!(!index.covers(&encode_key(&key(&["users"])).unwrap()));
+    assert!(!index.covers(&encode_key(&key(&["usersx", "a"])).unwrap()));
+    assert!(!index.covers(&encode_key(&key(&["posts", "a"])).unwrap()));
+  }
+
+  #[test]
+  fn unique_entry_key() {
+    let index = by_bytes_value(true);
+    let primary = encode_key(&key(&["users", "alice"])).unwrap();
+    let entry_key = index
+      .entry_key(&primary, &Value::Bytes(b"alice@example.com".to_vec()))
+      .unwrap()
+      .unwrap();
+    assert_eq!(
+      decode_key(&entry_key).unwrap(),
+      key(&["users_by_email", "alice@example.com"])
+    );
+    assert!(index.entry_key(&primary, &Value::U64(1)).unwrap().is_none());
+  }
+
+  #[test]
+  fn non_unique_entry_key() {
+    let index = by_bytes_value(false);
+    let primary = encode_key(&key(&["users", "alice"])).unwrap();
+    let entry_key = index
+      .entry_key(&primary, &Value::Bytes(b"alice@example.com".to_vec()))
+      .unwrap()
+      .unwrap();
+    assert_eq!(
+      decode_key(&entry_key).unwrap(),
+      key(&["users_by_email", "alice@example.com", "users", "alice"])
+    );
+  }
+
+  #[test]
+  fn empty_prefix() {
+    assert!(KvIndex::new(
+      "invalid",
+      Key(vec![]),
+      key(&["index"]),
+      true,
+      Arc::new(|_, _| None),
+    )
+    .is_err());
+  }
+}
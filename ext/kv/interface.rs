@@ -3,10 +3,12 @@
 use std::cell::RefCell;
 use std::cmp::Ordering;
 use std::num::NonZeroU32;
+use std::pin::Pin;
 use std::rc::Rc;
 
 use async_trait::async_trait;
 use deno_core::error::AnyError;
+use deno_core::futures::Stream;
 use deno_core::OpState;
 use num_bigint::BigInt;
 
@@ -45,6 +47,12 @@ pub trait Database {
     state: Rc<RefCell<OpState>>,
   ) -> Result<Self::QMH, AnyError>;
 
+  async fn watch(
+    &self,
+    state: Rc<RefCell<OpState>>,
+    keys: Vec<Vec<u8>>,
+  ) -> Result<WatchStream, AnyError>;
+
   fn close(&self);
 }
 
@@ -166,15 +174,38 @@ pub struct ReadRangeOutput {
   pub entries: Vec<KvEntry>,
 }
 
+/// A stream of notifications for a set of watched keys, as returned by
+/// [Database::watch].
+///
+/// The first item of the stream contains the current state of every watched
+/// key. Every following item is produced after a committed write changed at
+/// least one of the watched keys. Each item holds one [WatchKeyOutput] per
+/// watched key, in the order the keys were passed to [Database::watch].
+pub type WatchStream =
+  Pin<Box<dyn Stream<Item = Result<Vec<WatchKeyOutput>, AnyError>>>>;
+
+/// The state of a single watched key in a [WatchStream] item.
+pub enum WatchKeyOutput {
+  /// The key was changed since the previous item. `entry` is `None` if the
+  /// key does not exist (anymore).
+  Changed { entry: Option<KvEntry> },
+  /// The key was not changed since the previous item.
+  Unchanged,
+}
+
 /// A versionstamp is a 10 byte array that is used to represent the version of
 /// a key in the database.
 type Versionstamp = [u8; 10];
 
 /// A key-value entry with a versionstamp.
+///
+/// `expire_at` is the time (in milliseconds since the Unix epoch) after which
+/// the entry is deleted, if the backend reports it.
 pub struct KvEntry {
   pub key: Vec<u8>,
   pub value: Value,
   pub versionstamp: Versionstamp,
+  pub expire_at: Option<u64>,
 }
 
 /// A serialized value for a KV pair as stored in the database. All values
@@ -2,6 +2,8 @@
 
 pub mod codec;
 pub mod dynamic;
+pub mod export;
+pub mod index;
 mod interface;
 mod proto;
 pub mod remote;
@@ -18,12 +20,17 @@ use codec::encode_key;
 use deno_core::anyhow::Context;
 use deno_core::error::type_error;
 use deno_core::error::AnyError;
+use deno_core::futures::StreamExt;
 use deno_core::op;
 use deno_core::serde_v8::AnyValue;
 use deno_core::serde_v8::BigInt;
+use deno_core::AsyncRefCell;
 use deno_core::ByteString;
+use deno_core::CancelFuture;
+use deno_core::CancelHandle;
 use deno_core::JsBuffer;
 use deno_core::OpState;
+use deno_core::RcRef;
 use deno_core::Resource;
 use deno_core::ResourceId;
 use deno_core::ToJsBuffer;
@@ -42,6 +49,7 @@ const MAX_CHECKS: usize = 10;
 const MAX_MUTATIONS: usize = 1000;
 const MAX_TOTAL_MUTATION_SIZE_BYTES: usize = 800 * 1024;
 const MAX_TOTAL_KEY_SIZE_BYTES: usize = 80 * 1024;
+const MAX_WATCHED_KEYS: usize = 10;
 
 struct UnstableChecker {
   pub unstable: bool,
@@ -60,7 +68,7 @@ impl UnstableChecker {
 }
 
 deno_core::extension!(deno_kv,
-  deps = [ deno_console ],
+  deps = [ deno_console, deno_web ],
   parameters = [ DBH: DatabaseHandler ],
   ops = [
     op_kv_database_open<DBH>,
@@ -69,6 +77,8 @@ deno_core::extension!(deno_kv,
     op_kv_encode_cursor,
     op_kv_dequeue_next_message<DBH>,
     op_kv_finish_dequeued_message<DBH>,
+    op_kv_watch<DBH>,
+    op_kv_watch_next,
   ],
   esm = [ "01_db.ts" ],
   options = {
@@ -361,6 +371,104 @@ where
   handle.finish(success).await
 }
 
+struct DatabaseWatcherResource {
+  stream: AsyncRefCell<WatchStream>,
+  cancel: CancelHandle,
+}
+
+impl Resource for DatabaseWatcherResource {
+  fn name(&self) -> Cow<str> {
+    "databaseWatcher".into()
+  }
+
+  fn close(self: Rc<Self>) {
+    self.cancel.cancel()
+  }
+}
+
+#[op]
+async fn op_kv_watch<DBH>(
+  state: Rc<RefCell<OpState>>,
+  rid: ResourceId,
+  keys: Vec<KvKey>,
+) -> Result<ResourceId, AnyError>
+where
+  DBH: DatabaseHandler + 'static,
+{
+  let db = {
+    let state = state.borrow();
+    let resource =
+      state.resource_table.get::<DatabaseResource<DBH::DB>>(rid)?;
+    resource.db.clone()
+  };
+
+  if keys.len() > MAX_WATCHED_KEYS {
+    return Err(type_error(format!(
+      "too many keys (max {})",
+      MAX_WATCHED_KEYS
+    )));
+  }
+
+  let keys = keys
+    .into_iter()
+    .map(encode_v8_key)
+    .collect::<std::io::Result<Vec<_>>>()?;
+  for key in &keys {
+    check_read_key_size(key)?;
+  }
+
+  let stream = db.watch(state.clone(), keys).await?;
+  let rid = state
+    .borrow_mut()
+    .resource_table
+    .add(DatabaseWatcherResource {
+      stream: AsyncRefCell::new(stream),
+      cancel: CancelHandle::new(),
+    });
+  Ok(rid)
+}
+
+#[derive(Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+enum ToV8WatchKeyOutput {
+  Changed { entry: Option<ToV8KvEntry> },
+  Unchanged,
+}
+
+impl TryFrom<WatchKeyOutput> for ToV8WatchKeyOutput {
+  type Error = AnyError;
+  fn try_from(output: WatchKeyOutput) -> Result<Self, AnyError> {
+    Ok(match output {
+      WatchKeyOutput::Changed { entry } => ToV8WatchKeyOutput::Changed {
+        entry: entry.map(TryInto::try_into).transpose()?,
+      },
+      WatchKeyOutput::Unchanged => ToV8WatchKeyOutput::Unchanged,
+    })
+  }
+}
+
+#[op]
+async fn op_kv_watch_next(
+  state: Rc<RefCell<OpState>>,
+  rid: ResourceId,
+) -> Result<Option<Vec<ToV8WatchKeyOutput>>, AnyError> {
+  let resource = {
+    let state = state.borrow();
+    state.resource_table.get::<DatabaseWatcherResource>(rid)?
+  };
+  let mut stream = RcRef::map(&resource, |r| &r.stream).borrow_mut().await;
+  let cancel = RcRef::map(&resource, |r| &r.cancel);
+  let Ok(Some(outputs)) = stream.next().or_cancel(cancel).await else {
+    // The watcher was closed or the database went away.
+    return Ok(None);
+  };
+  let outputs = outputs?
+    .into_iter()
+    .map(TryInto::try_into)
+    .collect::<Result<Vec<_>, AnyError>>()?;
+  Ok(Some(outputs))
+}
+
 type V8KvCheck = (KvKey, Option<ByteString>);
 
 impl TryFrom<V8KvCheck> for KvCheck {
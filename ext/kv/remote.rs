@@ -1,10 +1,9 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
 
 use std::cell::RefCell;
-use std::fmt;
-use std::io::Write;
 use std::marker::PhantomData;
 use std::rc::Rc;
+use std::rc::Weak;
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -19,6 +18,7 @@ use crate::QueueMessageHandle;
 use crate::ReadRange;
 use crate::ReadRangeOutput;
 use crate::SnapshotReadOptions;
+use crate::WatchStream;
 use anyhow::Context;
 use async_trait::async_trait;
 use chrono::DateTime;
@@ -27,14 +27,12 @@ use deno_core::error::type_error;
 use deno_core::error::AnyError;
 use deno_core::futures::TryFutureExt;
 use deno_core::unsync::JoinHandle;
+use deno_core::CancelFuture;
+use deno_core::CancelHandle;
 use deno_core::OpState;
 use prost::Message;
 use rand::Rng;
 use serde::Deserialize;
-use termcolor::Ansi;
-use termcolor::Color;
-use termcolor::ColorSpec;
-use termcolor::WriteColor;
 use tokio::sync::watch;
 use url::Url;
 use uuid::Uuid;
@@ -126,35 +124,70 @@ impl<P: RemoteDbHandlerPermissions> DatabaseHandler for RemoteDbHandler<P> {
 
     let db = RemoteDb {
       client: reqwest::Client::new(),
-      refresher,
+      refresher: Rc::new(refresher),
+      dequeue_cancel: Rc::new(CancelHandle::new()),
       _p: PhantomData,
     };
     Ok(db)
   }
 }
 
+/// How long to wait before polling the remote queue again after it reported
+/// that no message is ready for delivery.
+const QUEUE_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
 pub struct RemoteDb<P: RemoteDbHandlerPermissions + 'static> {
   client: reqwest::Client,
-  refresher: MetadataRefresher,
+  refresher: Rc<MetadataRefresher>,
+  dequeue_cancel: Rc<CancelHandle>,
   _p: std::marker::PhantomData<P>,
 }
 
-pub struct DummyQueueMessageHandle {}
+pub struct RemoteQueueMessageHandle<P: RemoteDbHandlerPermissions + 'static> {
+  state: Weak<RefCell<OpState>>,
+  client: reqwest::Client,
+  refresher: Rc<MetadataRefresher>,
+  id: String,
+  payload: Option<Vec<u8>>,
+  _p: std::marker::PhantomData<P>,
+}
 
 #[async_trait(?Send)]
-impl QueueMessageHandle for DummyQueueMessageHandle {
+impl<P: RemoteDbHandlerPermissions> QueueMessageHandle
+  for RemoteQueueMessageHandle<P>
+{
   async fn take_payload(&mut self) -> Result<Vec<u8>, AnyError> {
-    unimplemented!()
+    self
+      .payload
+      .take()
+      .ok_or_else(|| type_error("Payload already consumed"))
   }
 
-  async fn finish(&self, _success: bool) -> Result<(), AnyError> {
-    unimplemented!()
+  async fn finish(&self, success: bool) -> Result<(), AnyError> {
+    let Some(state) = self.state.upgrade() else {
+      // The isolate is shutting down. The server will redeliver the message
+      // once its delivery deadline has passed.
+      return Ok(());
+    };
+    let req = pb::QueueFinish {
+      id: self.id.clone(),
+      success,
+    };
+    let _: pb::QueueFinishOutput = call_remote::<P, _, _>(
+      &state,
+      &self.refresher,
+      &self.client,
+      "queue_finish",
+      &req,
+    )
+    .await?;
+    Ok(())
   }
 }
 
 #[async_trait(?Send)]
 impl<P: RemoteDbHandlerPermissions> Database for RemoteDb<P> {
-  type QMH = DummyQueueMessageHandle;
+  type QMH = RemoteQueueMessageHandle<P>;
 
   async fn snapshot_read(
     &self,
@@ -201,6 +234,8 @@ impl<P: RemoteDbHandlerPermissions> Database for RemoteDb<P> {
                 key: e.key,
                 value: decode_value(e.value, encoding)?,
                 versionstamp: <[u8; 10]>::try_from(&e.versionstamp[..])?,
+                // The remote protocol does not report expiration times.
+                expire_at: None,
               })
             })
             .collect::<Result<_, AnyError>>()?,
@@ -215,10 +250,7 @@ impl<P: RemoteDbHandlerPermissions> Database for RemoteDb<P> {
     state: Rc<RefCell<OpState>>,
     write: AtomicWrite,
   ) -> Result<Option<CommitResult>, AnyError> {
-    if !write.enqueues.is_empty() {
-      return Err(type_error("Enqueue operations are not supported yet."));
-    }
-
+    let now = Utc::now().timestamp_millis();
     let req = pb::AtomicWrite {
       kv_checks: write
         .checks
@@ -235,7 +267,18 @@ impl<P: RemoteDbHandlerPermissions> Database for RemoteDb<P> {
         .into_iter()
         .map(|x| encode_mutation(x.key, x.kind))
         .collect(),
-      enqueues: vec![],
+      enqueues: write
+        .enqueues
+        .into_iter()
+        .map(|x| {
+          Ok(pb::Enqueue {
+            payload: x.payload,
+            deadline_ms: now + i64::try_from(x.delay_ms)?,
+            kv_keys_if_undelivered: x.keys_if_undelivered,
+            backoff_schedule: x.backoff_schedule.unwrap_or_default(),
+          })
+        })
+        .collect::<anyhow::Result<_>>()?,
     };
 
     let res: pb::AtomicWriteOutput = call_remote::<P, _, _>(
@@ -276,28 +319,52 @@ impl<P: RemoteDbHandlerPermissions> Database for RemoteDb<P> {
 
   async fn dequeue_next_message(
     &self,
-    _state: Rc<RefCell<OpState>>,
+    state: Rc<RefCell<OpState>>,
   ) -> Result<Self::QMH, AnyError> {
-    let msg = "Deno.Kv.listenQueue is not supported for remote KV databases";
-    eprintln!("{}", yellow(msg));
-    deno_core::futures::future::pending().await
+    let poll = async {
+      loop {
+        let res: pb::QueueDequeueOutput = call_remote::<P, _, _>(
+          &state,
+          &self.refresher,
+          &self.client,
+          "queue_dequeue",
+          &pb::QueueDequeue {},
+        )
+        .await?;
+        if let Some(message) = res.message {
+          return Ok::<_, AnyError>(message);
+        }
+        tokio::time::sleep(QUEUE_POLL_INTERVAL).await;
+      }
+    };
+    let Ok(message) = poll.or_cancel(self.dequeue_cancel.clone()).await else {
+      return Err(type_error("Database closed"));
+    };
+    let message = message?;
+
+    Ok(RemoteQueueMessageHandle {
+      state: Rc::downgrade(&state),
+      client: self.client.clone(),
+      refresher: self.refresher.clone(),
+      id: message.id,
+      payload: Some(message.payload),
+      _p: PhantomData,
+    })
   }
 
-  fn close(&self) {}
-}
+  async fn watch(
+    &self,
+    _state: Rc<RefCell<OpState>>,
+    _keys: Vec<Vec<u8>>,
+  ) -> Result<WatchStream, AnyError> {
+    Err(type_error(
+      "Deno.Kv.watch is not supported for remote KV databases",
+    ))
+  }
 
-fn yellow<S: AsRef<str>>(s: S) -> impl fmt::Display {
-  if std::env::var_os("NO_COLOR").is_some() {
-    return String::from(s.as_ref());
+  fn close(&self) {
+    self.dequeue_cancel.cancel();
   }
-  let mut style_spec = ColorSpec::new();
-  style_spec.set_fg(Some(Color::Yellow));
-  let mut v = Vec::new();
-  let mut ansi_writer = Ansi::new(&mut v);
-  ansi_writer.set_color(&style_spec).unwrap();
-  ansi_writer.write_all(s.as_ref().as_bytes()).unwrap();
-  ansi_writer.reset().unwrap();
-  String::from_utf8_lossy(&v).into_owned()
 }
 
 fn decode_value(
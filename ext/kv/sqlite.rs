@@ -19,6 +19,7 @@ use deno_core::error::type_error;
 use deno_core::error::AnyError;
 use deno_core::futures;
 use deno_core::futures::FutureExt;
+use deno_core::futures::StreamExt;
 use deno_core::unsync::spawn;
 use deno_core::unsync::spawn_blocking;
 use deno_core::AsyncRefCell;
@@ -28,6 +29,7 @@ use rusqlite::params;
 use rusqlite::OpenFlags;
 use rusqlite::OptionalExtension;
 use rusqlite::Transaction;
+use tokio::sync::broadcast;
 use tokio::sync::mpsc;
 use tokio::sync::watch;
 use tokio::sync::OnceCell;
@@ -35,6 +37,7 @@ use tokio::sync::OwnedSemaphorePermit;
 use tokio::sync::Semaphore;
 use uuid::Uuid;
 
+use crate::index::KvIndex;
 use crate::AtomicWrite;
 use crate::CommitResult;
 use crate::Database;
@@ -46,17 +49,24 @@ use crate::ReadRange;
 use crate::ReadRangeOutput;
 use crate::SnapshotReadOptions;
 use crate::Value;
+use crate::WatchKeyOutput;
+use crate::WatchStream;
 
 const STATEMENT_INC_AND_GET_DATA_VERSION: &str =
   "update data_version set version = version + 1 where k = 0 returning version";
+const STATEMENT_PRAGMA_DATA_VERSION: &str = "pragma data_version";
 const STATEMENT_KV_RANGE_SCAN: &str =
-  "select k, v, v_encoding, version from kv where k >= ? and k < ? order by k asc limit ?";
+  "select k, v, v_encoding, version, expiration_ms from kv where k >= ? and k < ? order by k asc limit ?";
 const STATEMENT_KV_RANGE_SCAN_REVERSE: &str =
-  "select k, v, v_encoding, version from kv where k >= ? and k < ? order by k desc limit ?";
+  "select k, v, v_encoding, version, expiration_ms from kv where k >= ? and k < ? order by k desc limit ?";
+const STATEMENT_KV_FULL_SCAN: &str =
+  "select k, v, v_encoding, version, expiration_ms from kv order by k asc";
 const STATEMENT_KV_POINT_GET_VALUE_ONLY: &str =
   "select v, v_encoding from kv where k = ?";
 const STATEMENT_KV_POINT_GET_VERSION_ONLY: &str =
   "select version from kv where k = ?";
+const STATEMENT_KV_POINT_GET: &str =
+  "select v, v_encoding, version, expiration_ms from kv where k = ?";
 const STATEMENT_KV_POINT_SET: &str =
   "insert into kv (k, v, v_encoding, version, expiration_ms) values (:k, :v, :v_encoding, :version, :expiration_ms) on conflict(k) do update set v = :v, v_encoding = :v_encoding, version = :version, expiration_ms = :expiration_ms";
 const STATEMENT_KV_POINT_DELETE: &str = "delete from kv where k = ?";
@@ -123,6 +133,8 @@ create index kv_expiration_ms_idx on kv (expiration_ms);
 ];
 
 const DISPATCH_CONCURRENCY_LIMIT: usize = 100;
+const WATCH_CHANNEL_CAPACITY: usize = 1024;
+const WATCH_POLL_INTERVAL: Duration = Duration::from_millis(200);
 const DEFAULT_BACKOFF_SCHEDULE: [u32; 5] = [100, 1000, 5000, 30000, 60000];
 
 const ERROR_USING_CLOSED_DATABASE: &str = "Attempted to use a closed database";
@@ -165,6 +177,7 @@ impl WeakProtectedConn {
 
 pub struct SqliteDbHandler<P: SqliteDbHandlerPermissions + 'static> {
   pub default_storage_dir: Option<PathBuf>,
+  indexes: Arc<[KvIndex]>,
   _permissions: PhantomData<P>,
 }
 
@@ -177,9 +190,17 @@ impl<P: SqliteDbHandlerPermissions> SqliteDbHandler<P> {
   pub fn new(default_storage_dir: Option<PathBuf>) -> Self {
     Self {
       default_storage_dir,
+      indexes: Arc::new([]),
       _permissions: PhantomData,
     }
   }
+
+  /// Sets the secondary indexes that are maintained by every atomic write
+  /// to databases opened by this handler.
+  pub fn with_indexes(mut self, indexes: Vec<KvIndex>) -> Self {
+    self.indexes = indexes.into();
+    self
+  }
 }
 
 #[async_trait(?Send)]
@@ -272,20 +293,29 @@ impl<P: SqliteDbHandlerPermissions> DatabaseHandler for SqliteDbHandler<P> {
     })
     .await?;
 
-    let expiration_watcher = spawn(watch_expiration(conn.clone()));
+    let (changes_tx, _) = broadcast::channel(WATCH_CHANNEL_CAPACITY);
+    let expiration_watcher =
+      spawn(watch_expiration(conn.clone(), changes_tx.clone()));
 
     Ok(SqliteDb {
       conn,
       queue: OnceCell::new(),
       expiration_watcher,
+      changes_tx,
+      indexes: self.indexes.clone(),
     })
   }
 }
 
+/// The keys mutated by a single committed atomic write.
+type ChangedKeys = Arc<[Vec<u8>]>;
+
 pub struct SqliteDb {
   conn: ProtectedConn,
   queue: OnceCell<SqliteQueue>,
   expiration_watcher: deno_core::unsync::JoinHandle<()>,
+  changes_tx: broadcast::Sender<ChangedKeys>,
+  indexes: Arc<[KvIndex]>,
 }
 
 impl Drop for SqliteDb {
@@ -357,6 +387,55 @@ impl SqliteDb {
     .await
     .unwrap()
   }
+
+  /// Reads every entry of the database, in key order, from a single read
+  /// transaction. The result is one consistent snapshot even if other
+  /// connections write to the database in the meantime. The entries are sent
+  /// to `entries_tx` in batches of at most `batch_size`; reading stops early if
+  /// the receiver is dropped.
+  pub async fn read_snapshot(
+    &self,
+    batch_size: usize,
+    entries_tx: mpsc::Sender<Vec<KvEntry>>,
+  ) -> Result<(), AnyError> {
+    // Not retried: a retry would send the entries read so far a second time.
+    Self::run_tx_inner(self.conn.clone(), move |tx| {
+      let mut stmt = tx.prepare(STATEMENT_KV_FULL_SCAN)?;
+      let mut rows = stmt.query([])?;
+      let mut batch = Vec::with_capacity(batch_size);
+      while let Some(row) = rows.next()? {
+        batch.push(entry_from_row(row)?);
+        if batch.len() >= batch_size {
+          let batch = std::mem::take(&mut batch);
+          if entries_tx.blocking_send(batch).is_err() {
+            return Ok(());
+          }
+        }
+      }
+      if !batch.is_empty() {
+        let _ = entries_tx.blocking_send(batch);
+      }
+      Ok(())
+    })
+    .await
+  }
+}
+
+fn entry_from_row(row: &rusqlite::Row) -> rusqlite::Result<KvEntry> {
+  let key: Vec<u8> = row.get(0)?;
+  let value: Vec<u8> = row.get(1)?;
+  let encoding: i64 = row.get(2)?;
+
+  let value = decode_value(value, encoding);
+
+  let version: i64 = row.get(3)?;
+  let expiration_ms: i64 = row.get(4)?;
+  Ok(KvEntry {
+    key,
+    value,
+    versionstamp: version_to_versionstamp(version),
+    expire_at: u64::try_from(expiration_ms).ok(),
+  })
 }
 
 pub struct DequeuedMessage {
@@ -698,7 +777,147 @@ impl SqliteQueue {
   }
 }
 
-async fn watch_expiration(db: ProtectedConn) {
+struct SqliteWatcher {
+  conn: WeakProtectedConn,
+  keys: Arc<[Vec<u8>]>,
+  changes_rx: broadcast::Receiver<ChangedKeys>,
+  last_versionstamps: Option<Vec<Option<[u8; 10]>>>,
+  last_data_version: i64,
+}
+
+impl SqliteWatcher {
+  /// Waits for the next change to any of the watched keys. Returns `None` once
+  /// the database has been closed.
+  ///
+  /// Writes made through this database handle, including expired keys being
+  /// removed, are picked up as soon as they are committed. Writes made by
+  /// other connections to the same database file (other handles, workers or
+  /// processes) are detected by polling the sqlite data version.
+  async fn next(&mut self) -> Result<Option<Vec<WatchKeyOutput>>, AnyError> {
+    loop {
+      if self.last_versionstamps.is_some() {
+        tokio::select! {
+          res = self.changes_rx.recv() => match res {
+            Ok(changed_keys) => {
+              if !changed_keys.iter().any(|key| self.keys.contains(key)) {
+                continue;
+              }
+            }
+            // Some notifications were dropped; re-read all keys to be safe.
+            Err(broadcast::error::RecvError::Lagged(_)) => {}
+            Err(broadcast::error::RecvError::Closed) => return Ok(None),
+          },
+          _ = tokio::time::sleep(WATCH_POLL_INTERVAL) => {
+            let Some(conn) = self.conn.upgrade() else {
+              return Ok(None);
+            };
+            let data_version = SqliteDb::run_tx(conn, |tx| {
+              let data_version: i64 = tx
+                .prepare_cached(STATEMENT_PRAGMA_DATA_VERSION)?
+                .query_row([], |row| row.get(0))?;
+              Ok(data_version)
+            })
+            .await;
+            match end_on_closed_database(data_version)? {
+              Some(data_version) if data_version != self.last_data_version => {}
+              Some(_) => continue,
+              None => return Ok(None),
+            }
+          }
+        }
+      }
+
+      let Some(conn) = self.conn.upgrade() else {
+        return Ok(None);
+      };
+      let keys = self.keys.clone();
+      let entries = SqliteDb::run_tx(conn, move |tx| {
+        let data_version: i64 = tx
+          .prepare_cached(STATEMENT_PRAGMA_DATA_VERSION)?
+          .query_row([], |row| row.get(0))?;
+        let mut entries = Vec::with_capacity(keys.len());
+        for key in keys.iter() {
+          let entry = tx
+            .prepare_cached(STATEMENT_KV_POINT_GET)?
+            .query_row([key.as_slice()], |row| {
+              let value: Vec<u8> = row.get(0)?;
+              let encoding: i64 = row.get(1)?;
+              let version: i64 = row.get(2)?;
+              let expiration_ms: i64 = row.get(3)?;
+              Ok(KvEntry {
+                key: key.clone(),
+                value: decode_value(value, encoding),
+                versionstamp: version_to_versionstamp(version),
+                expire_at: u64::try_from(expiration_ms).ok(),
+              })
+            })
+            .optional()?;
+          entries.push(entry);
+        }
+        Ok((data_version, entries))
+      })
+      .await;
+      let Some((data_version, entries)) = end_on_closed_database(entries)?
+      else {
+        return Ok(None);
+      };
+      self.last_data_version = data_version;
+
+      let versionstamps = entries
+        .iter()
+        .map(|entry| entry.as_ref().map(|entry| entry.versionstamp))
+        .collect::<Vec<_>>();
+      let outputs = match &self.last_versionstamps {
+        Some(last_versionstamps) => entries
+          .into_iter()
+          .zip(last_versionstamps.iter().zip(versionstamps.iter()))
+          .map(|(entry, (last, current))| {
+            if last == current {
+              WatchKeyOutput::Unchanged
+            } else {
+              WatchKeyOutput::Changed { entry }
+            }
+          })
+          .collect::<Vec<_>>(),
+        None => entries
+          .into_iter()
+          .map(|entry| WatchKeyOutput::Changed { entry })
+          .collect::<Vec<_>>(),
+      };
+      if outputs
+        .iter()
+        .all(|output| matches!(output, WatchKeyOutput::Unchanged))
+      {
+        continue;
+      }
+
+      self.last_versionstamps = Some(versionstamps);
+      return Ok(Some(outputs));
+    }
+  }
+}
+
+/// Turns the error for a database that was closed while a watcher was
+/// waiting into `None`, so that the watch stream ends cleanly.
+fn end_on_closed_database<T>(
+  result: Result<T, AnyError>,
+) -> Result<Option<T>, AnyError> {
+  match result {
+    Ok(x) => Ok(Some(x)),
+    Err(e)
+      if get_custom_error_class(&e) == Some("TypeError")
+        && e.to_string() == ERROR_USING_CLOSED_DATABASE =>
+    {
+      Ok(None)
+    }
+    Err(e) => Err(e),
+  }
+}
+
+async fn watch_expiration(
+  db: ProtectedConn,
+  changes_tx: broadcast::Sender<ChangedKeys>,
+) {
   loop {
     // Scan for expired keys
     let res = SqliteDb::run_tx(db.clone(), move |tx| {
@@ -706,16 +925,24 @@ async fn watch_expiration(db: ProtectedConn) {
         .duration_since(SystemTime::UNIX_EPOCH)
         .unwrap()
         .as_millis() as u64;
-      tx.prepare_cached(
-        "delete from kv where expiration_ms >= 0 and expiration_ms <= ?",
-      )?
-      .execute(params![now])?;
+      let deleted_keys = tx
+        .prepare_cached(
+          "delete from kv where expiration_ms >= 0 and expiration_ms <= ? returning k",
+        )?
+        .query_map(params![now], |row| row.get(0))?
+        .collect::<Result<Vec<Vec<u8>>, _>>()?;
       tx.commit()?;
-      Ok(())
+      Ok(deleted_keys)
     })
     .await;
-    if let Err(e) = res {
-      eprintln!("kv: Error in expiration watcher: {}", e);
+    match res {
+      Ok(deleted_keys) => {
+        if !deleted_keys.is_empty() {
+          // An error only means that there are no active watchers.
+          let _ = changes_tx.send(deleted_keys.into());
+        }
+      }
+      Err(e) => eprintln!("kv: Error in expiration watcher: {}", e),
     }
     let sleep_duration =
       Duration::from_secs_f64(60.0 + rand::thread_rng().gen_range(0.0..30.0));
@@ -749,20 +976,7 @@ impl Database for SqliteDb {
               request.end.as_slice(),
               request.limit.get(),
             ),
-            |row| {
-              let key: Vec<u8> = row.get(0)?;
-              let value: Vec<u8> = row.get(1)?;
-              let encoding: i64 = row.get(2)?;
-
-              let value = decode_value(value, encoding);
-
-              let version: i64 = row.get(3)?;
-              Ok(KvEntry {
-                key,
-                value,
-                versionstamp: version_to_versionstamp(version),
-              })
-            },
+            entry_from_row,
           )?
           .collect::<Result<Vec<_>, rusqlite::Error>>()?;
         responses.push(ReadRangeOutput { entries });
@@ -779,7 +993,8 @@ impl Database for SqliteDb {
     write: AtomicWrite,
   ) -> Result<Option<CommitResult>, AnyError> {
     let write = Arc::new(write);
-    let (has_enqueues, commit_result) =
+    let indexes = self.indexes.clone();
+    let (has_enqueues, changed_keys, commit_result) =
       Self::run_tx(self.conn.clone(), move |tx| {
         for check in &write.checks {
           let real_versionstamp = tx
@@ -788,7 +1003,7 @@ impl Database for SqliteDb {
             .optional()?
             .map(version_to_versionstamp);
           if real_versionstamp != check.versionstamp {
-            return Ok((false, None));
+            return Ok((false, vec![], None));
           }
         }
 
@@ -796,7 +1011,15 @@ impl Database for SqliteDb {
           .prepare_cached(STATEMENT_INC_AND_GET_DATA_VERSION)?
           .query_row([], |row| row.get(0))?;
 
+        let mut changed_keys = Vec::with_capacity(write.mutations.len());
         for mutation in &write.mutations {
+          let indexed = indexes.iter().any(|index| index.covers(&mutation.key));
+          let old_value = if indexed {
+            get_value(&tx, &mutation.key)?
+          } else {
+            None
+          };
+
           match &mutation.kind {
             MutationKind::Set(value) => {
               let (value, encoding) = encode_value(value);
@@ -850,6 +1073,32 @@ impl Database for SqliteDb {
               )?;
             }
           }
+          changed_keys.push(mutation.key.clone());
+
+          if indexed {
+            // Index entries take the expiration that the mutation left on the
+            // primary entry, whatever the kind of the mutation.
+            let (new_value, expire_at) =
+              match get_value_and_expiration(&tx, &mutation.key)? {
+                Some((value, expire_at)) => (Some(value), expire_at),
+                None => (None, None),
+              };
+            for index in indexes.iter().filter(|i| i.covers(&mutation.key)) {
+              let ok = update_index(
+                &tx,
+                index,
+                &mutation.key,
+                old_value.as_ref(),
+                new_value.as_ref(),
+                version,
+                expire_at,
+                &mut changed_keys,
+              )?;
+              if !ok {
+                return Ok((false, vec![], None));
+              }
+            }
+          }
         }
 
         let now = SystemTime::now()
@@ -886,6 +1135,7 @@ impl Database for SqliteDb {
 
         Ok((
           has_enqueues,
+          changed_keys,
           Some(CommitResult {
             versionstamp: new_versionstamp,
           }),
@@ -893,6 +1143,11 @@ impl Database for SqliteDb {
       })
       .await?;
 
+    if commit_result.is_some() && !changed_keys.is_empty() {
+      // An error only means that there are no active watchers.
+      let _ = self.changes_tx.send(changed_keys.into());
+    }
+
     if has_enqueues {
       if let Some(queue) = self.queue.get() {
         queue.wake().await?;
@@ -913,6 +1168,30 @@ impl Database for SqliteDb {
     Ok(handle)
   }
 
+  async fn watch(
+    &self,
+    _state: Rc<RefCell<OpState>>,
+    keys: Vec<Vec<u8>>,
+  ) -> Result<WatchStream, AnyError> {
+    let watcher = SqliteWatcher {
+      conn: self.conn.downgrade(),
+      keys: keys.into(),
+      changes_rx: self.changes_tx.subscribe(),
+      last_versionstamps: None,
+      last_data_version: 0,
+    };
+    let stream = futures::stream::unfold(Some(watcher), |watcher| async move {
+      let mut watcher = watcher?;
+      match watcher.next().await {
+        Ok(Some(outputs)) => Some((Ok(outputs), Some(watcher))),
+        Ok(None) => None,
+        // Yield the error, then end the stream.
+        Err(e) => Some((Err(e), None)),
+      }
+    });
+    Ok(stream.boxed_local())
+  }
+
   fn close(&self) {
     if let Some(queue) = self.queue.get() {
       queue.shutdown();
@@ -931,6 +1210,95 @@ impl Database for SqliteDb {
   }
 }
 
+fn get_value(tx: &Transaction, key: &[u8]) -> Result<Option<Value>, AnyError> {
+  let value = tx
+    .prepare_cached(STATEMENT_KV_POINT_GET_VALUE_ONLY)?
+    .query_row([key], |row| {
+      let value: Vec<u8> = row.get(0)?;
+      let encoding: i64 = row.get(1)?;
+      Ok(decode_value(value, encoding))
+    })
+    .optional()?;
+  Ok(value)
+}
+
+fn get_value_and_expiration(
+  tx: &Transaction,
+  key: &[u8],
+) -> Result<Option<(Value, Option<u64>)>, AnyError> {
+  let entry = tx
+    .prepare_cached(STATEMENT_KV_POINT_GET)?
+    .query_row([key], |row| {
+      let value: Vec<u8> = row.get(0)?;
+      let encoding: i64 = row.get(1)?;
+      let expiration_ms: i64 = row.get(3)?;
+      Ok((
+        decode_value(value, encoding),
+        u64::try_from(expiration_ms).ok(),
+      ))
+    })
+    .optional()?;
+  Ok(entry)
+}
+
+/// Moves the entry of `index` for the primary entry `key` from the one derived
+/// from `old_value` to the one derived from `new_value`. Returns `false` if
+/// the new index entry would violate the uniqueness of the index.
+#[allow(clippy::too_many_arguments)]
+fn update_index(
+  tx: &Transaction,
+  index: &KvIndex,
+  key: &[u8],
+  old_value: Option<&Value>,
+  new_value: Option<&Value>,
+  new_version: i64,
+  expire_at: Option<u64>,
+  changed_keys: &mut Vec<Vec<u8>>,
+) -> Result<bool, AnyError> {
+  let old_entry_key = match old_value {
+    Some(value) => index.entry_key(key, value)?,
+    None => None,
+  };
+  let new_entry_key = match new_value {
+    Some(value) => index.entry_key(key, value)?,
+    None => None,
+  };
+
+  if let Some(old_entry_key) = &old_entry_key {
+    if new_entry_key.as_ref() != Some(old_entry_key) {
+      tx.prepare_cached(STATEMENT_KV_POINT_DELETE)?
+        .execute([old_entry_key])?;
+      changed_keys.push(old_entry_key.clone());
+    }
+  }
+
+  if let Some(new_entry_key) = new_entry_key {
+    if index.is_unique() {
+      let owner = get_value(tx, &new_entry_key)?;
+      let is_owner =
+        matches!(&owner, Some(Value::Bytes(owner)) if owner == key);
+      if owner.is_some() && !is_owner {
+        return Ok(false);
+      }
+    }
+    let (value, encoding) = encode_value(&Value::Bytes(key.to_vec()));
+    let changed =
+      tx.prepare_cached(STATEMENT_KV_POINT_SET)?.execute(params![
+        new_entry_key,
+        value,
+        &encoding,
+        &new_version,
+        expire_at
+          .and_then(|x| i64::try_from(x).ok())
+          .unwrap_or(-1i64)
+      ])?;
+    assert_eq!(changed, 1);
+    changed_keys.push(new_entry_key);
+  }
+
+  Ok(true)
+}
+
 /// Mutates a LE64 value in the database, defaulting to setting it to the
 /// operand if it doesn't exist.
 fn mutate_le64(
@@ -1013,3 +1381,276 @@ fn encode_value(value: &crate::Value) -> (Cow<'_, [u8]>, i64) {
     }
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use std::num::NonZeroU32;
+  use std::path::Path;
+  use std::sync::Arc;
+
+  use deno_core::error::AnyError;
+  use deno_core::JsRuntime;
+
+  use crate::codec::encode_key;
+  use crate::index::KvIndex;
+  use crate::AtomicWrite;
+  use crate::Consistency;
+  use crate::Database;
+  use crate::DatabaseHandler;
+  use crate::Key;
+  use crate::KeyPart;
+  use crate::KvEntry;
+  use crate::KvMutation;
+  use crate::MutationKind;
+  use crate::ReadRange;
+  use crate::SnapshotReadOptions;
+  use crate::Value;
+
+  use super::SqliteDb;
+  use super::SqliteDbHandler;
+  use super::SqliteDbHandlerPermissions;
+
+  struct TestPermissions;
+
+  impl SqliteDbHandlerPermissions for TestPermissions {
+    fn check_read(
+      &mut self,
+      _p: &Path,
+      _api_name: &str,
+    ) -> Result<(), AnyError> {
+      Ok(())
+    }
+
+    fn check_write(
+      &mut self,
+      _p: &Path,
+      _api_name: &str,
+    ) -> Result<(), AnyError> {
+      Ok(())
+    }
+  }
+
+  fn key(parts: &[&str]) -> Vec<u8> {
+    encode_key(&Key(
+      parts
+        .iter()
+        .map(|part| KeyPart::String(part.to_string()))
+        .collect(),
+    ))
+    .unwrap()
+  }
+
+  /// Indexes `["users", ...]` entries by their bytes value, and by the
+  /// number of their U64 value.
+  fn indexes() -> Vec<KvIndex> {
+    let users = Key(vec![KeyPart::String("users".to_string())]);
+    vec![
+      KvIndex::new(
+        "users_by_email",
+        users.clone(),
+        Key(vec![KeyPart::String("users_by_email".to_string())]),
+        true,
+        Arc::new(|_, value| match value {
+          Value::Bytes(email) => Some(vec![KeyPart::String(
+            String::from_utf8(email.clone()).ok()?,
+          )]),
+          _ => None,
+        }),
+      )
+      .unwrap(),
+      KvIndex::new(
+        "users_by_score",
+        users,
+        Key(vec![KeyPart::String("users_by_score".to_string())]),
+        false,
+        Arc::new(|_, value| match value {
+          Value::U64(score) => Some(vec![KeyPart::Int((*score).into())]),
+          _ => None,
+        }),
+      )
+      .unwrap(),
+    ]
+  }
+
+  fn set(key: Vec<u8>, value: Value, expire_at: Option<u64>) -> AtomicWrite {
+    AtomicWrite {
+      checks: vec![],
+      mutations: vec![KvMutation {
+        key,
+        kind: MutationKind::Set(value),
+        expire_at,
+      }],
+      enqueues: vec![],
+    }
+  }
+
+  fn mutate(key: Vec<u8>, kind: MutationKind) -> AtomicWrite {
+    AtomicWrite {
+      checks: vec![],
+      mutations: vec![KvMutation {
+        key,
+        kind,
+        expire_at: None,
+      }],
+      enqueues: vec![],
+    }
+  }
+
+  async fn write(
+    runtime: &mut JsRuntime,
+    db: &SqliteDb,
+    write: AtomicWrite,
+  ) -> bool {
+    db.atomic_write(runtime.op_state(), write)
+      .await
+      .unwrap()
+      .is_some()
+  }
+
+  async fn entries(
+    runtime: &mut JsRuntime,
+    db: &SqliteDb,
+    prefix: &[&str],
+  ) -> Vec<KvEntry> {
+    let start = key(prefix);
+    let mut end = start.clone();
+    end.push(0xff);
+    let mut output = db
+      .snapshot_read(
+        runtime.op_state(),
+        vec![ReadRange {
+          start,
+          end,
+          limit: NonZeroU32::new(100).unwrap(),
+          reverse: false,
+        }],
+        SnapshotReadOptions {
+          consistency: Consistency::Strong,
+        },
+      )
+      .await
+      .unwrap();
+    output.remove(0).entries
+  }
+
+  fn primary_key_of(entry: &KvEntry) -> &[u8] {
+    match &entry.value {
+      Value::Bytes(primary_key) => primary_key,
+      _ => panic!("index entries hold the primary key as bytes"),
+    }
+  }
+
+  async fn open_db(runtime: &mut JsRuntime) -> SqliteDb {
+    SqliteDbHandler::<TestPermissions>::new(None)
+      .with_indexes(indexes())
+      .open(runtime.op_state(), Some(":memory:".to_string()))
+      .await
+      .unwrap()
+  }
+
+  #[tokio::test]
+  async fn atomic_write_maintains_indexes() {
+    let mut runtime = JsRuntime::new(Default::default());
+    let db = open_db(&mut runtime).await;
+    let alice = key(&["users", "alice"]);
+
+    // insert
+    let email = Value::Bytes(b"alice@example.com".to_vec());
+    assert!(write(&mut runtime, &db, set(alice.clone(), email, None)).await);
+    let index = entries(&mut runtime, &db, &["users_by_email"]).await;
+    assert_eq!(index.len(), 1);
+    assert_eq!(index[0].key, key(&["users_by_email", "alice@example.com"]));
+    assert_eq!(primary_key_of(&index[0]), &alice[..]);
+
+    // replace
+    let email = Value::Bytes(b"alice@deno.land".to_vec());
+    assert!(write(&mut runtime, &db, set(alice.clone(), email, None)).await);
+    let index = entries(&mut runtime, &db, &["users_by_email"]).await;
+    assert_eq!(index.len(), 1);
+    assert_eq!(index[0].key, key(&["users_by_email", "alice@deno.land"]));
+
+    // a value that the index doesn't cover drops the index entry
+    let score = Value::U64(3);
+    assert!(write(&mut runtime, &db, set(alice.clone(), score, None)).await);
+    assert!(entries(&mut runtime, &db, &["users_by_email"])
+      .await
+      .is_empty());
+    let index = entries(&mut runtime, &db, &["users_by_score"]).await;
+    assert_eq!(index.len(), 1);
+    assert!(index[0].key.ends_with(&alice));
+
+    // delete
+    assert!(
+      write(
+        &mut runtime,
+        &db,
+        mutate(alice.clone(), MutationKind::Delete)
+      )
+      .await
+    );
+    assert!(entries(&mut runtime, &db, &["users_by_score"])
+      .await
+      .is_empty());
+    assert!(entries(&mut runtime, &db, &["users"]).await.is_empty());
+  }
+
+  #[tokio::test]
+  async fn atomic_write_rejects_unique_index_violation() {
+    let mut runtime = JsRuntime::new(Default::default());
+    let db = open_db(&mut runtime).await;
+    let alice = key(&["users", "alice"]);
+    let bob = key(&["users", "bob"]);
+    let email = || Value::Bytes(b"shared@example.com".to_vec());
+
+    assert!(write(&mut runtime, &db, set(alice.clone(), email(), None)).await);
+    assert!(!write(&mut runtime, &db, set(bob.clone(), email(), None)).await);
+
+    // the rejected commit left neither the primary nor the index entry
+    let users = entries(&mut runtime, &db, &["users"]).await;
+    assert_eq!(users.len(), 1);
+    assert_eq!(users[0].key, alice);
+    let index = entries(&mut runtime, &db, &["users_by_email"]).await;
+    assert_eq!(index.len(), 1);
+    assert_eq!(primary_key_of(&index[0]), &alice[..]);
+
+    // rewriting the same value is not a violation
+    assert!(write(&mut runtime, &db, set(alice, email(), None)).await);
+  }
+
+  #[tokio::test]
+  async fn atomic_write_index_entries_share_expiration() {
+    let mut runtime = JsRuntime::new(Default::default());
+    let db = open_db(&mut runtime).await;
+    let alice = key(&["users", "alice"]);
+    let expire_at = Some(u64::MAX >> 2);
+
+    assert!(
+      write(
+        &mut runtime,
+        &db,
+        set(alice.clone(), Value::U64(1), expire_at)
+      )
+      .await
+    );
+    let index = entries(&mut runtime, &db, &["users_by_score"]).await;
+    assert_eq!(index.len(), 1);
+    assert_eq!(index[0].expire_at, expire_at);
+
+    // a sum rewrites the primary entry, and its index entry with it
+    let sum = mutate(alice.clone(), MutationKind::Sum(Value::U64(2)));
+    assert!(write(&mut runtime, &db, sum).await);
+    let users = entries(&mut runtime, &db, &["users"]).await;
+    let index = entries(&mut runtime, &db, &["users_by_score"]).await;
+    assert_eq!(index.len(), 1);
+    assert_eq!(index[0].key, {
+      let mut index_key = encode_key(&Key(vec![
+        KeyPart::String("users_by_score".to_string()),
+        KeyPart::Int(3u64.into()),
+      ]))
+      .unwrap();
+      index_key.extend_from_slice(&alice);
+      index_key
+    });
+    assert_eq!(index[0].expire_at, users[0].expire_at);
+  }
+}
@@ -6,6 +6,7 @@
 import { EventTarget } from "ext:deno_web/02_event.js";
 const primordials = globalThis.__bootstrap.primordials;
 const {
+  SafeArrayIterator,
   Symbol,
   SymbolToStringTag,
   TypeError,
@@ -13,6 +14,14 @@ const {
 
 const illegalConstructorKey = Symbol("illegalConstructorKey");
 
+// Set by `deno_webstorage`, which only looks up changes of the local storage
+// made by others while the window has `storage` listeners.
+let storageListenerAddedCallback = null;
+
+function setStorageListenerAddedCallback(callback) {
+  storageListenerAddedCallback = callback;
+}
+
 class Window extends EventTarget {
   constructor(key = null) {
     if (key !== illegalConstructorKey) {
@@ -24,6 +33,13 @@ class Window extends EventTarget {
   get [SymbolToStringTag]() {
     return "Window";
   }
+
+  addEventListener(...args) {
+    super.addEventListener(...new SafeArrayIterator(args));
+    if (args[0] === "storage" && storageListenerAddedCallback !== null) {
+      storageListenerAddedCallback();
+    }
+  }
 }
 
 class WorkerGlobalScope extends EventTarget {
@@ -76,6 +92,7 @@ const workerGlobalScopeConstructorDescriptor = {
 export {
   DedicatedWorkerGlobalScope,
   dedicatedWorkerGlobalScopeConstructorDescriptor,
+  setStorageListenerAddedCallback,
   Window,
   windowConstructorDescriptor,
   WorkerGlobalScope,
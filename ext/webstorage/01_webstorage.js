@@ -5,6 +5,13 @@
 const core = globalThis.Deno.core;
 const ops = core.ops;
 import * as webidl from "ext:deno_webidl/00_webidl.js";
+import {
+  Event,
+  listenerCount,
+  setIsTrusted,
+} from "ext:deno_web/02_event.js";
+import { setStorageListenerAddedCallback } from "ext:deno_web/04_global_interfaces.js";
+import { createFilteredInspectProxy } from "ext:deno_console/01_console.js";
 const primordials = globalThis.__bootstrap.primordials;
 const {
   SafeArrayIterator,
@@ -13,11 +20,16 @@ const {
   ObjectDefineProperty,
   ObjectFromEntries,
   ObjectEntries,
+  ObjectPrototypeIsPrototypeOf,
+  PromiseReject,
+  PromiseResolve,
   ReflectGet,
   ReflectHas,
   Proxy,
 } = primordials;
 
+const promiseIdSymbol = SymbolFor("Deno.core.internalPromiseId");
+
 const _persistent = Symbol("[[persistent]]");
 
 class Storage {
@@ -156,6 +168,122 @@ function createStorage(persistent) {
   return proxy;
 }
 
+class StorageEvent extends Event {
+  #key = null;
+  #oldValue = null;
+  #newValue = null;
+  #url = "";
+  #storageArea = null;
+
+  get key() {
+    return this.#key;
+  }
+  get oldValue() {
+    return this.#oldValue;
+  }
+  get newValue() {
+    return this.#newValue;
+  }
+  get url() {
+    return this.#url;
+  }
+  get storageArea() {
+    return this.#storageArea;
+  }
+
+  constructor(type, eventInitDict = {}) {
+    super(type, eventInitDict);
+    webidl.requiredArguments(
+      arguments.length,
+      1,
+      "Failed to construct 'StorageEvent'",
+    );
+    const { key, oldValue, newValue, url, storageArea } = eventInitDict;
+    this.#key = key ?? null;
+    this.#oldValue = oldValue ?? null;
+    this.#newValue = newValue ?? null;
+    this.#url = url ?? "";
+    this.#storageArea = storageArea ?? null;
+  }
+
+  [SymbolFor("Deno.privateCustomInspect")](inspect) {
+    return inspect(createFilteredInspectProxy({
+      object: this,
+      evaluate: ObjectPrototypeIsPrototypeOf(StorageEvent.prototype, this),
+      keys: [
+        "type",
+        "key",
+        "oldValue",
+        "newValue",
+        "url",
+        "storageArea",
+      ],
+    }));
+  }
+}
+
+let isWatchingLocalStorage = false;
+
+// Changes made by other workers and processes sharing the same origin storage
+// are looked up while the window has `storage` listeners. The polling op is
+// unref'd so that it doesn't keep the event loop alive.
+async function watchLocalStorage() {
+  if (isWatchingLocalStorage) {
+    return;
+  }
+  isWatchingLocalStorage = true;
+  try {
+    // only changes made from now on are dispatched
+    ops.op_webstorage_watch_changes();
+    const storageArea = localStorage();
+    while (listenerCount(globalThis, "storage") > 0) {
+      const promise = core.opAsync("op_webstorage_poll_changes");
+      core.unrefOp(promise[promiseIdSymbol]);
+      const changes = await promise;
+      for (let i = 0; i < changes.length; ++i) {
+        const { key, oldValue, newValue } = changes[i];
+        const event = new StorageEvent("storage", {
+          key,
+          oldValue,
+          newValue,
+          storageArea,
+        });
+        setIsTrusted(event, true);
+        globalThis.dispatchEvent(event);
+      }
+    }
+  } catch {
+    // local storage isn't available in this context
+  } finally {
+    isWatchingLocalStorage = false;
+  }
+}
+
+setStorageListenerAddedCallback(watchLocalStorage);
+
+class StorageManager {
+  constructor() {
+    webidl.illegalConstructor();
+  }
+
+  /**
+   * Reports the size of the local storage of this origin and its quota, in
+   * bytes.
+   */
+  estimate() {
+    webidl.assertBranded(this, StorageManagerPrototype);
+    try {
+      return PromiseResolve(ops.op_webstorage_estimate());
+    } catch (err) {
+      return PromiseReject(err);
+    }
+  }
+}
+
+const StorageManagerPrototype = StorageManager.prototype;
+
+const storageManager = webidl.createBranded(StorageManager);
+
 let localStorageStorage;
 function localStorage() {
   if (!localStorageStorage) {
@@ -172,4 +300,11 @@ function sessionStorage() {
   return sessionStorageStorage;
 }
 
-export { localStorage, sessionStorage, Storage };
+export {
+  localStorage,
+  sessionStorage,
+  Storage,
+  StorageEvent,
+  StorageManager,
+  storageManager,
+};
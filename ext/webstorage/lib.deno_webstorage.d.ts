@@ -46,3 +46,55 @@ declare var Storage: {
   readonly prototype: Storage;
   new (): never;
 };
+
+/** @category Web Storage API */
+declare interface StorageEventInit extends EventInit {
+  key?: string | null;
+  oldValue?: string | null;
+  newValue?: string | null;
+  url?: string;
+  storageArea?: Storage | null;
+}
+
+/** Dispatched on the global scope when `localStorage` is changed by another
+ * worker or process sharing the same origin storage.
+ *
+ * @category Web Storage API
+ */
+declare interface StorageEvent extends Event {
+  /** The key that was changed, or `null` if the storage was cleared. */
+  readonly key: string | null;
+  /** The value of the key before the change. */
+  readonly oldValue: string | null;
+  /** The value of the key after the change, or `null` if it was removed. */
+  readonly newValue: string | null;
+  readonly url: string;
+  /** The storage that was changed. */
+  readonly storageArea: Storage | null;
+}
+
+/** @category Web Storage API */
+declare var StorageEvent: {
+  readonly prototype: StorageEvent;
+  new (type: string, eventInitDict?: StorageEventInit): StorageEvent;
+};
+
+/** @category Web Storage API */
+declare interface StorageEstimate {
+  /** Number of bytes used by the local storage of this origin. */
+  usage: number;
+  /** Maximum number of bytes the local storage of this origin can use. */
+  quota: number;
+}
+
+/** @category Web Storage API */
+declare interface StorageManager {
+  /** Reports the usage and quota of the local storage of this origin. */
+  estimate(): Promise<StorageEstimate>;
+}
+
+/** @category Web Storage API */
+declare var StorageManager: {
+  readonly prototype: StorageManager;
+  new (): never;
+};
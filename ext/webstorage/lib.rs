@@ -2,8 +2,13 @@
 
 // NOTE to all: use **cached** prepared statements when interfacing with SQLite.
 
+use std::cell::RefCell;
 use std::fmt;
 use std::path::PathBuf;
+use std::rc::Rc;
+use std::time::Duration;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
 
 use deno_core::error::AnyError;
 use deno_core::op;
@@ -11,16 +16,30 @@ use deno_core::OpState;
 use rusqlite::params;
 use rusqlite::Connection;
 use rusqlite::OptionalExtension;
+use serde::Serialize;
+use uuid::Uuid;
 
 pub use rusqlite;
 
 #[derive(Clone)]
 struct OriginStorageDir(PathBuf);
 
-const MAX_STORAGE_BYTES: usize = 10 * 1024 * 1024;
+/// The quota of each storage area used when the embedder doesn't set one.
+pub const DEFAULT_MAX_STORAGE_BYTES: usize = 10 * 1024 * 1024;
+
+struct MaxStorageBytes(usize);
+
+/// How often the `changes` table is looked up for changes made by other
+/// workers or processes sharing the same origin storage.
+const CHANGES_POLL_INTERVAL: Duration = Duration::from_millis(100);
+/// Changes older than this are removed from the `changes` table.
+const CHANGES_RETENTION: Duration = Duration::from_secs(60);
+/// Expired changes are removed once every this many changes, rather than
+/// every time a change is recorded.
+const CHANGES_PRUNE_INTERVAL: i64 = 100;
 
 deno_core::extension!(deno_webstorage,
-  deps = [ deno_webidl ],
+  deps = [ deno_webidl, deno_web ],
   ops = [
     op_webstorage_length,
     op_webstorage_key,
@@ -29,15 +48,22 @@ deno_core::extension!(deno_webstorage,
     op_webstorage_remove,
     op_webstorage_clear,
     op_webstorage_iterate_keys,
+    op_webstorage_estimate,
+    op_webstorage_watch_changes,
+    op_webstorage_poll_changes,
   ],
   esm = [ "01_webstorage.js" ],
   options = {
-    origin_storage_dir: Option<PathBuf>
+    origin_storage_dir: Option<PathBuf>,
+    max_storage_bytes: Option<usize>,
   },
   state = |state, options| {
     if let Some(origin_storage_dir) = options.origin_storage_dir {
       state.put(OriginStorageDir(origin_storage_dir));
     }
+    state.put(MaxStorageBytes(
+      options.max_storage_bytes.unwrap_or(DEFAULT_MAX_STORAGE_BYTES),
+    ));
   },
 );
 
@@ -45,7 +71,15 @@ pub fn get_declaration() -> PathBuf {
   PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("lib.deno_webstorage.d.ts")
 }
 
-struct LocalStorage(Connection);
+struct LocalStorage {
+  conn: Connection,
+  /// Identifies the changes made through this connection so that they aren't
+  /// reported back as `storage` events.
+  source_id: String,
+  /// The id of the last row of the `changes` table that was looked at.
+  last_change_id: i64,
+}
+
 struct SessionStorage(Connection);
 
 fn get_webstorage(
@@ -79,11 +113,31 @@ fn get_webstorage(
           "CREATE TABLE IF NOT EXISTS data (key VARCHAR UNIQUE, value VARCHAR)",
         )?;
         stmt.execute(params![])?;
+        let mut stmt = conn.prepare_cached(
+          "CREATE TABLE IF NOT EXISTS changes (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            source VARCHAR NOT NULL,
+            key VARCHAR,
+            old_value VARCHAR,
+            new_value VARCHAR,
+            created_at INTEGER NOT NULL
+          )",
+        )?;
+        stmt.execute(params![])?;
+        let mut stmt = conn.prepare_cached(
+          "CREATE INDEX IF NOT EXISTS changes_created_at ON changes (created_at)",
+        )?;
+        stmt.execute(params![])?;
       }
-      state.put(LocalStorage(conn));
+      let last_change_id = latest_change_id(&conn)?;
+      state.put(LocalStorage {
+        conn,
+        source_id: Uuid::new_v4().to_string(),
+        last_change_id,
+      });
     }
 
-    &state.borrow::<LocalStorage>().0
+    &state.borrow::<LocalStorage>().conn
   } else {
     if state.try_borrow::<SessionStorage>().is_none() {
       let conn = Connection::open_in_memory()?;
@@ -134,8 +188,8 @@ pub fn op_webstorage_key(
 }
 
 #[inline]
-fn size_check(input: usize) -> Result<(), AnyError> {
-  if input >= MAX_STORAGE_BYTES {
+fn size_check(max_storage_bytes: usize, input: usize) -> Result<(), AnyError> {
+  if input >= max_storage_bytes {
     return Err(
       deno_web::DomExceptionQuotaExceededError::new(
         "Exceeded maximum storage size",
@@ -147,6 +201,70 @@ fn size_check(input: usize) -> Result<(), AnyError> {
   Ok(())
 }
 
+/// The size of the storage, including the values kept in the `changes` table
+/// for `storage` events.
+fn storage_usage(conn: &Connection) -> Result<usize, AnyError> {
+  let mut stmt = conn.prepare_cached(
+    "SELECT COALESCE(SUM(pgsize), 0) FROM dbstat WHERE name IN ('data', 'changes', 'changes_created_at')",
+  )?;
+  let size: i64 = stmt.query_row(params![], |row| row.get(0))?;
+  Ok(size as usize)
+}
+
+fn get_value(conn: &Connection, key: &str) -> Result<Option<String>, AnyError> {
+  let mut stmt = conn.prepare_cached("SELECT value FROM data WHERE key = ?")?;
+  let val = stmt.query_row(params![key], |row| row.get(0)).optional()?;
+  Ok(val)
+}
+
+fn latest_change_id(conn: &Connection) -> Result<i64, AnyError> {
+  let mut stmt =
+    conn.prepare_cached("SELECT COALESCE(MAX(id), 0) FROM changes")?;
+  Ok(stmt.query_row(params![], |row| row.get(0))?)
+}
+
+/// Records a change of the local storage so that other workers and processes
+/// sharing it can dispatch a `storage` event. A `None` key means the storage
+/// was cleared.
+fn record_change(
+  conn: &Connection,
+  source_id: &str,
+  key: Option<&str>,
+  old_value: Option<&str>,
+  new_value: Option<&str>,
+) -> Result<(), AnyError> {
+  let now = SystemTime::now()
+    .duration_since(UNIX_EPOCH)
+    .map(|d| d.as_millis() as i64)
+    .unwrap_or(0);
+  let mut stmt = conn.prepare_cached(
+    "INSERT INTO changes (source, key, old_value, new_value, created_at) VALUES (?, ?, ?, ?, ?)",
+  )?;
+  stmt.execute(params![source_id, key, old_value, new_value, now])?;
+  if conn.last_insert_rowid() % CHANGES_PRUNE_INTERVAL == 0 {
+    let mut stmt =
+      conn.prepare_cached("DELETE FROM changes WHERE created_at < ?")?;
+    stmt.execute(params![now - CHANGES_RETENTION.as_millis() as i64])?;
+  }
+  Ok(())
+}
+
+/// Like `get_webstorage`, but also returns the id that changes made through
+/// the connection are recorded with. The changes of the session storage
+/// aren't recorded.
+fn get_webstorage_with_source(
+  state: &mut OpState,
+  persistent: bool,
+) -> Result<(&Connection, Option<&str>), AnyError> {
+  get_webstorage(state, persistent)?;
+  if persistent {
+    let local_storage = state.borrow::<LocalStorage>();
+    Ok((&local_storage.conn, Some(&local_storage.source_id)))
+  } else {
+    Ok((&state.borrow::<SessionStorage>().0, None))
+  }
+}
+
 #[op]
 pub fn op_webstorage_set(
   state: &mut OpState,
@@ -154,19 +272,31 @@ pub fn op_webstorage_set(
   value: &str,
   persistent: bool,
 ) -> Result<(), AnyError> {
-  let conn = get_webstorage(state, persistent)?;
-
-  size_check(key.len() + value.len())?;
-
-  let mut stmt = conn
-    .prepare_cached("SELECT SUM(pgsize) FROM dbstat WHERE name = 'data'")?;
-  let size: u32 = stmt.query_row(params![], |row| row.get(0))?;
-
-  size_check(size as usize)?;
-
-  let mut stmt = conn
-    .prepare_cached("INSERT OR REPLACE INTO data (key, value) VALUES (?, ?)")?;
-  stmt.execute(params![key, value])?;
+  let max_storage_bytes = state.borrow::<MaxStorageBytes>().0;
+  let (conn, source) = get_webstorage_with_source(state, persistent)?;
+
+  size_check(max_storage_bytes, key.len() + value.len())?;
+  size_check(max_storage_bytes, storage_usage(conn)?)?;
+
+  // the old value is read in the same transaction, so that it is the value
+  // this change replaced even if others write to the storage concurrently
+  let tx = conn.unchecked_transaction()?;
+  let old_value = match source {
+    Some(_) => get_value(&tx, key)?,
+    None => None,
+  };
+  {
+    let mut stmt = tx.prepare_cached(
+      "INSERT OR REPLACE INTO data (key, value) VALUES (?, ?)",
+    )?;
+    stmt.execute(params![key, value])?;
+  }
+  if let Some(source) = source {
+    if old_value.as_deref() != Some(value) {
+      record_change(&tx, source, Some(key), old_value.as_deref(), Some(value))?;
+    }
+  }
+  tx.commit()?;
 
   Ok(())
 }
@@ -179,12 +309,7 @@ pub fn op_webstorage_get(
 ) -> Result<Option<String>, AnyError> {
   let conn = get_webstorage(state, persistent)?;
 
-  let mut stmt = conn.prepare_cached("SELECT value FROM data WHERE key = ?")?;
-  let val = stmt
-    .query_row(params![key_name], |row| row.get(0))
-    .optional()?;
-
-  Ok(val)
+  get_value(conn, &key_name)
 }
 
 #[op]
@@ -193,10 +318,21 @@ pub fn op_webstorage_remove(
   key_name: &str,
   persistent: bool,
 ) -> Result<(), AnyError> {
-  let conn = get_webstorage(state, persistent)?;
+  let (conn, source) = get_webstorage_with_source(state, persistent)?;
 
-  let mut stmt = conn.prepare_cached("DELETE FROM data WHERE key = ?")?;
-  stmt.execute(params![key_name])?;
+  let tx = conn.unchecked_transaction()?;
+  let old_value = match source {
+    Some(_) => get_value(&tx, key_name)?,
+    None => None,
+  };
+  {
+    let mut stmt = tx.prepare_cached("DELETE FROM data WHERE key = ?")?;
+    stmt.execute(params![key_name])?;
+  }
+  if let (Some(source), Some(old_value)) = (source, &old_value) {
+    record_change(&tx, source, Some(key_name), Some(old_value), None)?;
+  }
+  tx.commit()?;
 
   Ok(())
 }
@@ -206,10 +342,19 @@ pub fn op_webstorage_clear(
   state: &mut OpState,
   persistent: bool,
 ) -> Result<(), AnyError> {
-  let conn = get_webstorage(state, persistent)?;
+  let (conn, source) = get_webstorage_with_source(state, persistent)?;
 
-  let mut stmt = conn.prepare_cached("DELETE FROM data")?;
-  stmt.execute(params![])?;
+  let tx = conn.unchecked_transaction()?;
+  let removed = {
+    let mut stmt = tx.prepare_cached("DELETE FROM data")?;
+    stmt.execute(params![])?
+  };
+  if let Some(source) = source {
+    if removed > 0 {
+      record_change(&tx, source, None, None, None)?;
+    }
+  }
+  tx.commit()?;
 
   Ok(())
 }
@@ -230,6 +375,80 @@ pub fn op_webstorage_iterate_keys(
   Ok(keys)
 }
 
+#[derive(Serialize)]
+pub struct StorageEstimate {
+  usage: usize,
+  quota: usize,
+}
+
+#[op]
+pub fn op_webstorage_estimate(
+  state: &mut OpState,
+) -> Result<StorageEstimate, AnyError> {
+  let conn = get_webstorage(state, true)?;
+  let usage = storage_usage(conn)?;
+
+  Ok(StorageEstimate {
+    usage,
+    quota: state.borrow::<MaxStorageBytes>().0,
+  })
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageChange {
+  key: Option<String>,
+  old_value: Option<String>,
+  new_value: Option<String>,
+}
+
+/// Starts looking for changes of the local storage made by others from the
+/// latest change on, so that only changes made while there are `storage`
+/// listeners are dispatched.
+#[op]
+pub fn op_webstorage_watch_changes(
+  state: &mut OpState,
+) -> Result<(), AnyError> {
+  get_webstorage(state, true)?;
+  let local_storage = state.borrow_mut::<LocalStorage>();
+  local_storage.last_change_id = latest_change_id(&local_storage.conn)?;
+  Ok(())
+}
+
+/// Waits for a moment and returns the changes that other workers or
+/// processes made to the local storage since the last call, oldest first.
+#[op]
+pub async fn op_webstorage_poll_changes(
+  state: Rc<RefCell<OpState>>,
+) -> Result<Vec<StorageChange>, AnyError> {
+  tokio::time::sleep(CHANGES_POLL_INTERVAL).await;
+
+  let mut state = state.borrow_mut();
+  get_webstorage(&mut state, true)?;
+  let local_storage = state.borrow_mut::<LocalStorage>();
+
+  let mut stmt = local_storage.conn.prepare_cached(
+    "SELECT id, source, key, old_value, new_value FROM changes WHERE id > ? ORDER BY id",
+  )?;
+  let mut changes = Vec::new();
+  let mut last_change_id = local_storage.last_change_id;
+  let mut rows = stmt.query(params![last_change_id])?;
+  while let Some(row) = rows.next()? {
+    last_change_id = row.get(0)?;
+    // changes made through this connection aren't reported back
+    if row.get::<_, String>(1)? == local_storage.source_id {
+      continue;
+    }
+    changes.push(StorageChange {
+      key: row.get(2)?,
+      old_value: row.get(3)?,
+      new_value: row.get(4)?,
+    });
+  }
+  local_storage.last_change_id = last_change_id;
+  Ok(changes)
+}
+
 #[derive(Debug)]
 pub struct DomExceptionNotSupportedError {
   pub msg: String,
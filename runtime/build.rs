@@ -11,7 +11,7 @@ use std::path::PathBuf;
 ))]
 mod startup_snapshot {
   use super::*;
-  use deno_cache::SqliteBackedCache;
+  use deno_cache::CacheImpl;
   use deno_core::error::AnyError;
   use deno_core::snapshot_util::*;
   use deno_core::Extension;
@@ -214,13 +214,13 @@ mod startup_snapshot {
       deno_fetch::deno_fetch::init_ops_and_esm::<Permissions>(
         Default::default(),
       ),
-      deno_cache::deno_cache::init_ops_and_esm::<SqliteBackedCache>(None),
+      deno_cache::deno_cache::init_ops_and_esm::<CacheImpl>(None),
       deno_websocket::deno_websocket::init_ops_and_esm::<Permissions>(
         "".to_owned(),
         None,
         None,
       ),
-      deno_webstorage::deno_webstorage::init_ops_and_esm(None),
+      deno_webstorage::deno_webstorage::init_ops_and_esm(None, None),
       deno_crypto::deno_crypto::init_ops_and_esm(None),
       deno_broadcast_channel::deno_broadcast_channel::init_ops_and_esm(
         deno_broadcast_channel::InMemoryBroadcastChannel::default(),
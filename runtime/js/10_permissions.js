@@ -10,15 +10,22 @@ const {
   ArrayPrototypeIncludes,
   ArrayPrototypeMap,
   ArrayPrototypeSlice,
+  Error,
   MapPrototypeGet,
   MapPrototypeHas,
   MapPrototypeSet,
   FunctionPrototypeCall,
+  ObjectDefineProperty,
+  ObjectKeys,
   PromiseResolve,
   PromiseReject,
+  ReflectApply,
   ReflectHas,
+  RegExpPrototypeExec,
   SafeArrayIterator,
   SafeMap,
+  StringPrototypeSplit,
+  StringPrototypeStartsWith,
   Symbol,
   SymbolFor,
   TypeError,
@@ -291,4 +298,84 @@ function serializePermissions(permissions) {
   return permissions;
 }
 
-export { Permissions, permissions, PermissionStatus, serializePermissions };
+/** How many JS frames to inspect when looking for the caller of an op. */
+const AUDIT_STACK_FRAME_LIMIT = 32;
+const STACK_LOCATION_PATTERN = /\(?([^\s()]+:\d+:\d+)\)?$/;
+let isCapturingCaller = false;
+
+/**
+ * Returns the location of the innermost JS frame that isn't part of the
+ * runtime itself, falling back to the innermost frame.
+ * @returns {string | null}
+ */
+function callerLocation() {
+  const stackTraceLimit = Error.stackTraceLimit;
+  Error.stackTraceLimit = AUDIT_STACK_FRAME_LIMIT;
+  const stack = new Error().stack;
+  Error.stackTraceLimit = stackTraceLimit;
+  if (typeof stack !== "string") {
+    return null;
+  }
+  let innermost = null;
+  const lines = StringPrototypeSplit(stack, "\n");
+  for (let i = 1; i < lines.length; ++i) {
+    const match = RegExpPrototypeExec(STACK_LOCATION_PATTERN, lines[i]);
+    if (match === null) {
+      continue;
+    }
+    const location = match[1];
+    if (
+      !StringPrototypeStartsWith(location, "ext:") &&
+      !StringPrototypeStartsWith(location, "node:")
+    ) {
+      return location;
+    }
+    innermost ??= location;
+  }
+  return innermost;
+}
+
+/**
+ * Wraps every op so that the location of the JS code calling it is recorded
+ * with the permission checks it makes. Capturing a stack trace on each op
+ * call is slow, so this is only done when `--audit-permissions` is set.
+ */
+function auditPermissionChecks() {
+  const setCaller = ops.op_audit_permissions_set_caller;
+  for (const opTable of new SafeArrayIterator([ops, core.asyncOps])) {
+    for (const name of new SafeArrayIterator(ObjectKeys(opTable))) {
+      const op = opTable[name];
+      if (typeof op !== "function" || op === setCaller) {
+        continue;
+      }
+      const wrapped = function () {
+        // formatting the stack trace calls ops itself
+        if (isCapturingCaller) {
+          return ReflectApply(op, this, arguments);
+        }
+        isCapturingCaller = true;
+        try {
+          setCaller(callerLocation());
+        } finally {
+          isCapturingCaller = false;
+        }
+        try {
+          return ReflectApply(op, this, arguments);
+        } finally {
+          setCaller(null);
+        }
+      };
+      // async op stubs are generated from the number of arguments
+      ObjectDefineProperty(wrapped, "length", { value: op.length });
+      opTable[name] = wrapped;
+    }
+  }
+}
+
+export {
+  auditPermissionChecks,
+  Permissions,
+  permissions,
+  PermissionStatus,
+  serializePermissions,
+};
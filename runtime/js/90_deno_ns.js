@@ -23,6 +23,7 @@ import * as tty from "ext:runtime/40_tty.js";
 // TODO(bartlomieju): this is funky we have two `http` imports
 import * as httpRuntime from "ext:runtime/40_http.js";
 import * as kv from "ext:deno_kv/01_db.ts";
+import * as cache from "ext:deno_cache/01_cache.js";
 
 const denoNs = {
   metrics: core.metrics,
@@ -178,6 +179,7 @@ const denoNsUnstable = {
   Kv: kv.Kv,
   KvU64: kv.KvU64,
   KvListIterator: kv.KvListIterator,
+  cacheUsage: cache.cacheUsage,
 };
 
 export { denoNs, denoNsUnstable };
@@ -202,6 +202,14 @@ ObjectDefineProperties(Navigator.prototype, {
       return [language];
     },
   },
+  storage: {
+    configurable: true,
+    enumerable: true,
+    get() {
+      webidl.assertBranded(this, NavigatorPrototype);
+      return webStorage.storageManager;
+    },
+  },
 });
 const NavigatorPrototype = Navigator.prototype;
 
@@ -267,6 +275,8 @@ const mainRuntimeGlobalProperties = {
   localStorage: util.getterOnly(webStorage.localStorage),
   sessionStorage: util.getterOnly(webStorage.sessionStorage),
   Storage: util.nonEnumerable(webStorage.Storage),
+  StorageEvent: util.nonEnumerable(webStorage.StorageEvent),
+  StorageManager: util.nonEnumerable(webStorage.StorageManager),
 };
 
 const workerRuntimeGlobalProperties = {
@@ -59,6 +59,7 @@ import * as fetch from "ext:deno_fetch/26_fetch.js";
 import * as messagePort from "ext:deno_web/13_message_port.js";
 import { denoNs, denoNsUnstable } from "ext:runtime/90_deno_ns.js";
 import { errors } from "ext:runtime/01_errors.js";
+import { auditPermissionChecks } from "ext:runtime/10_permissions.js";
 import * as webidl from "ext:deno_webidl/00_webidl.js";
 import DOMException from "ext:deno_web/01_dom_exception.js";
 import {
@@ -470,6 +471,10 @@ function bootstrapMainRuntime(runtimeOptions) {
   delete globalThis.nodeBootstrap;
   hasBootstrapped = true;
 
+  if (ops.op_audit_permissions_enabled()) {
+    auditPermissionChecks();
+  }
+
   // If the `--location` flag isn't set, make `globalThis.location` `undefined` and
   // writable, so that they can mock it themselves if they like. If the flag was
   // set, define `globalThis.location`, using the provided value.
@@ -595,6 +600,10 @@ function bootstrapWorkerRuntime(
   delete globalThis.nodeBootstrap;
   hasBootstrapped = true;
 
+  if (ops.op_audit_permissions_enabled()) {
+    auditPermissionChecks();
+  }
+
   if (unstableFlag) {
     ObjectDefineProperties(globalThis, unstableWindowOrWorkerGlobalScope);
   }
@@ -1,17 +1,20 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
 
+use crate::permissions::is_audit_enabled;
 use crate::permissions::parse_sys_kind;
+use crate::permissions::set_caller_location;
+use crate::permissions::NetDescriptor;
 use crate::permissions::PermissionState;
 use crate::permissions::PermissionsContainer;
 use deno_core::error::custom_error;
 use deno_core::error::uri_error;
 use deno_core::error::AnyError;
 use deno_core::op;
-use deno_core::url;
 use deno_core::OpState;
 use serde::Deserialize;
 use serde::Serialize;
 use std::path::Path;
+use std::str::FromStr;
 
 deno_core::extension!(
   deno_permissions,
@@ -19,6 +22,8 @@ deno_core::extension!(
     op_query_permission,
     op_revoke_permission,
     op_request_permission,
+    op_audit_permissions_enabled,
+    op_audit_permissions_set_caller,
   ],
 );
 
@@ -61,7 +66,7 @@ pub fn op_query_permission(
   let perm = match args.name.as_ref() {
     "read" => permissions.read.query(path.map(Path::new)),
     "write" => permissions.write.query(path.map(Path::new)),
-    "net" => permissions.net.query(
+    "net" => permissions.net.query_pattern(
       match args.host.as_deref() {
         None => None,
         Some(h) => Some(parse_host(h)?),
@@ -95,7 +100,7 @@ pub fn op_revoke_permission(
   let perm = match args.name.as_ref() {
     "read" => permissions.read.revoke(path.map(Path::new)),
     "write" => permissions.write.revoke(path.map(Path::new)),
-    "net" => permissions.net.revoke(
+    "net" => permissions.net.revoke_pattern(
       match args.host.as_deref() {
         None => None,
         Some(h) => Some(parse_host(h)?),
@@ -129,7 +134,7 @@ pub fn op_request_permission(
   let perm = match args.name.as_ref() {
     "read" => permissions.read.request(path.map(Path::new)),
     "write" => permissions.write.request(path.map(Path::new)),
-    "net" => permissions.net.request(
+    "net" => permissions.net.request_pattern(
       match args.host.as_deref() {
         None => None,
         Some(h) => Some(parse_host(h)?),
@@ -153,12 +158,16 @@ pub fn op_request_permission(
   Ok(PermissionStatus::from(perm))
 }
 
-fn parse_host(host_str: &str) -> Result<(String, Option<u16>), AnyError> {
-  let url = url::Url::parse(&format!("http://{host_str}/"))
-    .map_err(|_| uri_error("Invalid host"))?;
-  if url.path() != "/" {
-    return Err(uri_error("Invalid host"));
-  }
-  let hostname = url.host_str().unwrap();
-  Ok((hostname.to_string(), url.port()))
+fn parse_host(host_str: &str) -> Result<NetDescriptor, AnyError> {
+  NetDescriptor::from_str(host_str).map_err(|_| uri_error("Invalid host"))
+}
+
+#[op(fast)]
+pub fn op_audit_permissions_enabled() -> bool {
+  is_audit_enabled()
+}
+
+#[op]
+pub fn op_audit_permissions_set_caller(location: Option<String>) {
+  set_caller_location(location);
 }
@@ -0,0 +1,81 @@
+// Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
+
+use deno_core::error::AnyError;
+use deno_core::parking_lot::Mutex;
+use deno_core::serde::Serialize;
+use deno_core::serde_json;
+use once_cell::sync::OnceCell;
+use std::cell::RefCell;
+use std::fs::File;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+
+static AUDIT_LOG: OnceCell<Mutex<File>> = OnceCell::new();
+
+thread_local! {
+  static CALLER_LOCATION: RefCell<Option<String>> = RefCell::new(None);
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AuditEntry<'a> {
+  kind: &'a str,
+  descriptor: Option<&'a str>,
+  api_name: Option<&'a str>,
+  result: &'static str,
+  prompted: bool,
+  stack: Option<&'a str>,
+}
+
+/// Starts recording every permission check to the file at `path`, one JSON
+/// object per line. The file is appended to if it already exists.
+pub fn set_audit_log(path: &Path) -> Result<(), AnyError> {
+  let file = OpenOptions::new().create(true).append(true).open(path)?;
+  // only the first call takes effect, the log is shared by all workers
+  let _ = AUDIT_LOG.set(Mutex::new(file));
+  Ok(())
+}
+
+pub fn is_audit_enabled() -> bool {
+  AUDIT_LOG.get().is_some()
+}
+
+/// Sets the location of the JS code calling into an op on the current thread.
+/// It is captured on the JS side before each op call while auditing is
+/// enabled, and cleared again when the op returns.
+pub fn set_caller_location(location: Option<String>) {
+  CALLER_LOCATION.with(|caller| *caller.borrow_mut() = location);
+}
+
+/// Returns the location set by `set_caller_location`, if any.
+pub(crate) fn caller_location() -> Option<String> {
+  CALLER_LOCATION.with(|caller| caller.borrow().clone())
+}
+
+#[inline]
+pub(crate) fn record_check(
+  kind: &str,
+  descriptor: Option<&str>,
+  api_name: Option<&str>,
+  result: &Result<(), AnyError>,
+  prompted: bool,
+  stack: Option<&str>,
+) {
+  let Some(log) = AUDIT_LOG.get() else {
+    return;
+  };
+  let entry = AuditEntry {
+    kind,
+    descriptor,
+    api_name,
+    result: if result.is_ok() { "granted" } else { "denied" },
+    prompted,
+    stack,
+  };
+  let mut line = serde_json::to_string(&entry).unwrap();
+  line.push('\n');
+  if let Err(err) = log.lock().write_all(line.as_bytes()) {
+    log::debug!("Failed writing permission audit log: {:#}", err);
+  }
+}
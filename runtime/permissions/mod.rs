@@ -22,6 +22,9 @@ use std::borrow::Cow;
 use std::collections::HashSet;
 use std::fmt;
 use std::hash::Hash;
+use std::net::IpAddr;
+use std::net::Ipv4Addr;
+use std::net::Ipv6Addr;
 use std::path::Path;
 use std::path::PathBuf;
 use std::str::FromStr;
@@ -29,11 +32,15 @@ use std::string::ToString;
 use std::sync::Arc;
 use which::which;
 
+mod audit;
 mod prompter;
 use prompter::permission_prompt;
 use prompter::PromptResponse;
 use prompter::PERMISSION_EMOJI;
 
+pub use audit::is_audit_enabled;
+pub use audit::set_audit_log;
+pub use audit::set_caller_location;
 pub use prompter::set_prompt_callbacks;
 pub use prompter::PromptCallback;
 
@@ -218,6 +225,16 @@ impl UnitPermission {
   pub fn check(&mut self) -> Result<(), AnyError> {
     let (result, prompted, _is_allow_all) =
       self.state.check(self.name, None, None, self.prompt);
+    if audit::is_audit_enabled() {
+      audit::record_check(
+        self.name,
+        None,
+        None,
+        &result,
+        prompted,
+        audit::caller_location().as_deref(),
+      );
+    }
     if prompted {
       if result.is_ok() {
         self.state = PermissionState::Granted;
@@ -311,6 +328,16 @@ impl<T: Descriptor + Hash> UnaryPermission<T> {
         },
         self.prompt,
       );
+    if audit::is_audit_enabled() {
+      audit::record_check(
+        T::flag_name(),
+        desc.as_ref().map(|d| d.name()).as_deref(),
+        api_name,
+        &result,
+        prompted,
+        audit::caller_location().as_deref(),
+      );
+    }
     if prompted {
       if result.is_ok() {
         if is_allow_all {
@@ -533,12 +560,218 @@ impl Descriptor for WriteDescriptor {
   }
 }
 
+/// The host part of a `NetDescriptor`.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub enum NetHost {
+  /// An exact hostname or IP address.
+  Name(String),
+  /// A wildcard like `*.example.com`, stored without the `*.` prefix. It
+  /// matches any subdomain of the domain, but not the domain itself.
+  Wildcard(String),
+  /// An IP network in CIDR notation like `10.0.0.0/8`. The address is
+  /// normalized to the first address of the network.
+  Cidr(IpAddr, u8),
+}
+
+impl NetHost {
+  fn parse(host: &str) -> Result<Self, AnyError> {
+    if let Some(domain) = host.strip_prefix("*.") {
+      let url = Url::parse(&format!("http://{domain}"))?;
+      return match url.host() {
+        Some(url::Host::Domain(domain)) if !domain.contains('*') => {
+          Ok(NetHost::Wildcard(domain.to_string()))
+        }
+        _ => Err(type_error(format!("Invalid wildcard host: {host}"))),
+      };
+    }
+    if let Some((addr, prefix_len)) = host.split_once('/') {
+      let invalid = || type_error(format!("Invalid CIDR range: {host}"));
+      let addr = addr.parse::<IpAddr>().map_err(|_| invalid())?;
+      let prefix_len = prefix_len.parse::<u8>().map_err(|_| invalid())?;
+      let max_prefix_len = if addr.is_ipv4() { 32 } else { 128 };
+      if prefix_len > max_prefix_len {
+        return Err(invalid());
+      }
+      // a range of IPv4-mapped addresses is the same as the IPv4 range
+      let (addr, prefix_len) = match canonical_ip(addr) {
+        IpAddr::V4(v4) if addr.is_ipv6() && prefix_len >= 96 => {
+          (IpAddr::V4(v4), prefix_len - 96)
+        }
+        _ => (addr, prefix_len),
+      };
+      return Ok(NetHost::Cidr(mask_ip(addr, prefix_len), prefix_len));
+    }
+    if let Ok(addr) = host.parse::<Ipv6Addr>() {
+      return Ok(NetHost::Name(ip_host_name(IpAddr::V6(addr))));
+    }
+    let url = Url::parse(&format!("http://{host}"))?;
+    Ok(NetHost::Name(url.host_str().unwrap().to_string()))
+  }
+
+  /// Normalizes a host that is being checked the same way as the hosts in
+  /// the allow and deny lists, so that they can be compared. Unlike `parse`,
+  /// this never treats the host as a pattern. IPv4 addresses in the legacy
+  /// forms that the system resolver accepts (`10.1`, `0x0a000001` or
+  /// `167772161`) and IPv4-mapped IPv6 addresses are turned into the dotted
+  /// IPv4 address they connect to.
+  fn from_checked_host(host: &str) -> Self {
+    let unbracketed = host
+      .strip_prefix('[')
+      .and_then(|host| host.strip_suffix(']'))
+      .unwrap_or(host);
+    if let Ok(addr) = unbracketed.parse::<Ipv6Addr>() {
+      return NetHost::Name(ip_host_name(IpAddr::V6(addr)));
+    }
+    match Url::parse(&format!("http://{host}")) {
+      Ok(url) => match url.host() {
+        Some(url::Host::Ipv4(addr)) => NetHost::Name(addr.to_string()),
+        _ => NetHost::Name(url.host_str().unwrap().to_string()),
+      },
+      Err(_) => NetHost::Name(host.to_ascii_lowercase()),
+    }
+  }
+
+  fn covers(&self, other: &Self) -> bool {
+    match (self, other) {
+      (NetHost::Name(a), NetHost::Name(b)) => a.eq_ignore_ascii_case(b),
+      (NetHost::Wildcard(domain), NetHost::Name(name)) => {
+        is_subdomain(name, domain)
+      }
+      (NetHost::Wildcard(a), NetHost::Wildcard(b)) => {
+        a == b || is_subdomain(b, a)
+      }
+      (NetHost::Cidr(network, prefix_len), NetHost::Name(name)) => {
+        let name = name.trim_start_matches('[').trim_end_matches(']');
+        match name.parse::<IpAddr>() {
+          Ok(addr) => ip_in_network(addr, *network, *prefix_len),
+          Err(_) => false,
+        }
+      }
+      (NetHost::Cidr(a, a_len), NetHost::Cidr(b, b_len)) => {
+        a_len <= b_len && ip_in_network(*b, *a, *a_len)
+      }
+      _ => false,
+    }
+  }
+}
+
+impl fmt::Display for NetHost {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      NetHost::Name(name) => f.write_str(name),
+      NetHost::Wildcard(domain) => write!(f, "*.{domain}"),
+      NetHost::Cidr(addr, prefix_len) => write!(f, "{addr}/{prefix_len}"),
+    }
+  }
+}
+
+fn is_subdomain(name: &str, domain: &str) -> bool {
+  match name.to_ascii_lowercase().strip_suffix(domain) {
+    Some(rest) => rest.len() > 1 && rest.ends_with('.'),
+    None => false,
+  }
+}
+
+fn mask_ip(addr: IpAddr, prefix_len: u8) -> IpAddr {
+  match addr {
+    IpAddr::V4(addr) => {
+      let mask = u32::MAX.checked_shl(32 - prefix_len as u32).unwrap_or(0);
+      IpAddr::V4(Ipv4Addr::from(u32::from(addr) & mask))
+    }
+    IpAddr::V6(addr) => {
+      let mask = u128::MAX.checked_shl(128 - prefix_len as u32).unwrap_or(0);
+      IpAddr::V6(Ipv6Addr::from(u128::from(addr) & mask))
+    }
+  }
+}
+
+/// Turns IPv4-mapped (`::ffff:10.0.0.1`) and IPv4-compatible (`::10.0.0.1`)
+/// IPv6 addresses into the IPv4 address they stand for. The unspecified and
+/// loopback IPv6 addresses are left alone.
+fn canonical_ip(addr: IpAddr) -> IpAddr {
+  match addr {
+    IpAddr::V6(v6) => {
+      if let Some(v4) = v6.to_ipv4_mapped() {
+        return IpAddr::V4(v4);
+      }
+      match v6.to_ipv4() {
+        Some(v4) if u128::from(v6) > 1 => IpAddr::V4(v4),
+        _ => addr,
+      }
+    }
+    IpAddr::V4(_) => addr,
+  }
+}
+
+/// Formats an IP address the way hosts are stored, with IPv6 addresses in
+/// brackets.
+fn ip_host_name(addr: IpAddr) -> String {
+  match canonical_ip(addr) {
+    IpAddr::V4(addr) => addr.to_string(),
+    IpAddr::V6(addr) => format!("[{addr}]"),
+  }
+}
+
+fn ip_in_network(addr: IpAddr, network: IpAddr, prefix_len: u8) -> bool {
+  let addr = canonical_ip(addr);
+  addr.is_ipv4() == network.is_ipv4() && mask_ip(addr, prefix_len) == network
+}
+
+/// An inclusive range of ports. A single port is a range where `start` and
+/// `end` are equal.
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub struct PortRange {
+  pub start: u16,
+  pub end: u16,
+}
+
+impl PortRange {
+  pub fn single(port: u16) -> Self {
+    Self {
+      start: port,
+      end: port,
+    }
+  }
+
+  fn covers(&self, other: &Self) -> bool {
+    self.start <= other.start && other.end <= self.end
+  }
+}
+
+impl FromStr for PortRange {
+  type Err = AnyError;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let invalid = || type_error(format!("Invalid port or port range: {s}"));
+    let (start, end) = s.split_once('-').unwrap_or((s, s));
+    let start = start.parse::<u16>().map_err(|_| invalid())?;
+    let end = end.parse::<u16>().map_err(|_| invalid())?;
+    if start > end {
+      return Err(invalid());
+    }
+    Ok(Self { start, end })
+  }
+}
+
+impl fmt::Display for PortRange {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    if self.start == self.end {
+      write!(f, "{}", self.start)
+    } else {
+      write!(f, "{}-{}", self.start, self.end)
+    }
+  }
+}
+
 #[derive(Clone, Eq, PartialEq, Hash, Debug)]
-pub struct NetDescriptor(pub String, pub Option<u16>);
+pub struct NetDescriptor(pub NetHost, pub Option<PortRange>);
 
 impl NetDescriptor {
   fn new<T: AsRef<str>>(host: &&(T, Option<u16>)) -> Self {
-    NetDescriptor(host.0.as_ref().to_string(), host.1)
+    NetDescriptor(
+      NetHost::from_checked_host(host.0.as_ref()),
+      host.1.map(PortRange::single),
+    )
   }
 }
 
@@ -552,27 +785,60 @@ impl Descriptor for NetDescriptor {
   }
 
   fn stronger_than(&self, other: &Self) -> bool {
-    self.0 == other.0 && (self.1.is_none() || self.1 == other.1)
+    self.0.covers(&other.0)
+      && match (self.1, other.1) {
+        (None, _) => true,
+        (Some(ports), Some(other_ports)) => ports.covers(&other_ports),
+        (Some(_), None) => false,
+      }
   }
 }
 
 impl FromStr for NetDescriptor {
   type Err = AnyError;
 
+  /// Parses `host`, `host:port` or `host:start-end`, where the host can also
+  /// be a wildcard (`*.example.com`) or a CIDR range (`10.0.0.0/8`). IPv6
+  /// addresses and ranges need to be enclosed in brackets to specify ports.
   fn from_str(s: &str) -> Result<Self, Self::Err> {
-    let url = url::Url::parse(&format!("http://{s}"))?;
-    let hostname = url.host_str().unwrap().to_string();
-
-    Ok(NetDescriptor(hostname, url.port()))
+    let (host, ports) = if let Some(rest) = s.strip_prefix('[') {
+      let (host, rest) = rest
+        .split_once(']')
+        .ok_or_else(|| type_error(format!("Invalid host: {s}")))?;
+      let ports = match rest {
+        "" => None,
+        rest => Some(
+          rest
+            .strip_prefix(':')
+            .ok_or_else(|| type_error(format!("Invalid host: {s}")))?,
+        ),
+      };
+      (host, ports)
+    } else if s.matches(':').count() > 1 {
+      // an IPv6 address or range without ports
+      (s, None)
+    } else {
+      match s.rsplit_once(':') {
+        Some((host, ports)) => (host, Some(ports)),
+        None => (s, None),
+      }
+    };
+    Ok(NetDescriptor(
+      NetHost::parse(host)?,
+      ports.map(PortRange::from_str).transpose()?,
+    ))
   }
 }
 
 impl fmt::Display for NetDescriptor {
   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-    f.write_str(&match self.1 {
-      None => self.0.clone(),
-      Some(port) => format!("{}:{}", self.0, port),
-    })
+    match (&self.0, self.1) {
+      (host, None) => write!(f, "{host}"),
+      (NetHost::Cidr(IpAddr::V6(_), _), Some(ports)) => {
+        write!(f, "[{}]:{ports}", self.0)
+      }
+      (host, Some(ports)) => write!(f, "{host}:{ports}"),
+    }
   }
 }
 
@@ -890,6 +1156,30 @@ impl UnaryPermission<NetDescriptor> {
   pub fn check_all(&mut self) -> Result<(), AnyError> {
     self.check_desc(&None, false, None, || None)
   }
+
+  /// As `query()`, but the descriptor can also hold a wildcard host, a CIDR
+  /// range or a port range.
+  pub fn query_pattern(&self, desc: Option<&NetDescriptor>) -> PermissionState {
+    self.query_desc(&desc.cloned(), AllowPartial::TreatAsPartialGranted)
+  }
+
+  /// As `request()`, but the descriptor can also hold a wildcard host, a CIDR
+  /// range or a port range.
+  pub fn request_pattern(
+    &mut self,
+    desc: Option<&NetDescriptor>,
+  ) -> PermissionState {
+    self.request_desc(&desc.cloned(), || None)
+  }
+
+  /// As `revoke()`, but the descriptor can also hold a wildcard host, a CIDR
+  /// range or a port range.
+  pub fn revoke_pattern(
+    &mut self,
+    desc: Option<&NetDescriptor>,
+  ) -> PermissionState {
+    self.revoke_desc(&desc.cloned())
+  }
 }
 
 impl UnaryPermission<EnvDescriptor> {
@@ -2010,12 +2300,12 @@ pub fn create_child_permissions(
     ChildUnaryPermissionArg::NotGranted => {}
     ChildUnaryPermissionArg::GrantedList(granted_list) => {
       worker_perms.net.granted_list = parse_net_list(&Some(granted_list))?;
-      if !worker_perms
-        .net
-        .granted_list
-        .iter()
-        .all(|desc| main_perms.net.check(&(&desc.0, desc.1), None).is_ok())
-      {
+      if !worker_perms.net.granted_list.iter().all(|desc| {
+        main_perms
+          .net
+          .check_desc(&Some(desc.clone()), false, None, || None)
+          .is_ok()
+      }) {
         return Err(escalation_error());
       }
     }
@@ -2451,6 +2741,208 @@ mod tests {
     }
   }
 
+  #[test]
+  fn test_check_net_patterns() {
+    set_prompter(Box::new(TestPrompter));
+    let mut perms = Permissions::from_options(&PermissionsOptions {
+      allow_net: Some(svec![
+        "10.0.0.0/8",
+        "*.internal.example.com",
+        "localhost:8000-8100",
+        "[fd00::/8]:443"
+      ]),
+      deny_net: Some(svec!["10.1.0.0/16", "secret.internal.example.com"]),
+      ..Default::default()
+    })
+    .unwrap();
+
+    let domain_tests = vec![
+      ("10.0.0.1", 80, true),
+      ("10.255.255.255", 5000, true),
+      ("10.1.2.3", 80, false),
+      ("11.0.0.1", 80, false),
+      ("a.internal.example.com", 443, true),
+      ("a.b.internal.example.com", 80, true),
+      ("A.Internal.Example.com", 80, true),
+      ("internal.example.com", 443, false),
+      ("badinternal.example.com", 443, false),
+      ("secret.internal.example.com", 443, false),
+      ("localhost", 8000, true),
+      ("localhost", 8050, true),
+      ("localhost", 8100, true),
+      ("localhost", 7999, false),
+      ("localhost", 8101, false),
+      ("[fd00::1]", 443, true),
+      ("fd12:3456::1", 443, true),
+      ("[fd00::1]", 80, false),
+      ("[fe80::1]", 443, false),
+    ];
+
+    for (host, port, is_ok) in domain_tests {
+      assert_eq!(
+        is_ok,
+        perms.net.check(&(host, Some(port)), None).is_ok(),
+        "{}:{}",
+        host,
+        port,
+      );
+    }
+
+    let url_tests = vec![
+      ("https://10.2.3.4/", true),
+      ("wss://api.internal.example.com/socket", true),
+      ("http://localhost:8080/", true),
+      ("http://localhost/", false),
+      ("https://[fd00::2]/", true),
+    ];
+
+    for (url_str, is_ok) in url_tests {
+      let u = url::Url::parse(url_str).unwrap();
+      assert_eq!(is_ok, perms.net.check_url(&u, None).is_ok(), "{}", u);
+    }
+  }
+
+  #[test]
+  fn test_net_descriptor_parse() {
+    let cases = vec![
+      ("deno.land", "deno.land"),
+      ("deno.land:80", "deno.land:80"),
+      ("deno.land:8000-8100", "deno.land:8000-8100"),
+      ("*.Example.com:443", "*.example.com:443"),
+      ("10.1.2.3/8", "10.0.0.0/8"),
+      ("[::1]:8000", "[::1]:8000"),
+      ("::1", "[::1]"),
+      ("fd00::1/8", "fd00::/8"),
+      ("[fd00::/8]:443", "[fd00::/8]:443"),
+      ("[::ffff:10.0.0.1]:80", "10.0.0.1:80"),
+      ("::ffff:10.0.0.0/104", "10.0.0.0/8"),
+    ];
+    for (input, expected) in cases {
+      assert_eq!(
+        NetDescriptor::from_str(input).unwrap().to_string(),
+        expected,
+        "{}",
+        input
+      );
+    }
+
+    for input in [
+      "",
+      "deno.land:8100-8000",
+      "deno.land:65536",
+      "*.10.0.0.1",
+      "*.*.example.com",
+      "10.0.0.0/33",
+      "fd00::/129",
+      "deno.land/8",
+      "[::1",
+    ] {
+      assert!(NetDescriptor::from_str(input).is_err(), "{}", input);
+    }
+  }
+
+  #[test]
+  fn test_net_pattern_query_request_revoke() {
+    set_prompter(Box::new(TestPrompter));
+    let mut perms = Permissions::from_options(&PermissionsOptions {
+      allow_net: Some(svec!["10.0.0.0/8"]),
+      ..Default::default()
+    })
+    .unwrap();
+    let prompt_value = PERMISSION_PROMPT_STUB_VALUE_SETTER.lock();
+    let desc = |s: &str| NetDescriptor::from_str(s).unwrap();
+    assert_eq!(
+      perms.net.query_pattern(Some(&desc("10.1.0.0/16"))),
+      PermissionState::Granted
+    );
+    assert_eq!(
+      perms.net.query_pattern(Some(&desc("*.example.com"))),
+      PermissionState::Prompt
+    );
+    prompt_value.set(true);
+    assert_eq!(
+      perms.net.request_pattern(Some(&desc("*.example.com"))),
+      PermissionState::Granted
+    );
+    assert!(perms.net.check(&("api.example.com", None), None).is_ok());
+    assert_eq!(
+      perms.net.revoke_pattern(Some(&desc("*.example.com"))),
+      PermissionState::Prompt
+    );
+    prompt_value.set(false);
+    assert!(perms.net.check(&("api.example.com", None), None).is_err());
+  }
+
+  #[test]
+  fn test_net_deny_normalizes_checked_host() {
+    set_prompter(Box::new(TestPrompter));
+    let mut perms = Permissions::from_options(&PermissionsOptions {
+      allow_net: Some(vec![]),
+      deny_net: Some(svec!["evil.com", "*.example.com", "[::1]", "fd00::/8"]),
+      ..Default::default()
+    })
+    .unwrap();
+    let denied = [
+      ("evil.com", None),
+      ("EVIL.com", Some(443)),
+      ("api.Example.COM", None),
+      ("::1", None),
+      ("[::1]", Some(8000)),
+      ("0:0:0:0:0:0:0:1", None),
+      ("FD00::1", None),
+    ];
+    for host in denied {
+      assert!(perms.net.check(&host, None).is_err(), "{:?}", host);
+    }
+    for host in [("example.com", None), ("::2", None)] {
+      assert!(perms.net.check(&host, None).is_ok(), "{:?}", host);
+    }
+  }
+
+  #[test]
+  fn test_net_deny_ipv4_in_other_forms() {
+    set_prompter(Box::new(TestPrompter));
+    let mut perms = Permissions::from_options(&PermissionsOptions {
+      allow_net: Some(vec![]),
+      deny_net: Some(svec!["10.0.0.1", "192.168.0.0/16"]),
+      ..Default::default()
+    })
+    .unwrap();
+    let denied = [
+      // legacy forms accepted by `inet_aton`
+      "10.1",
+      "10.0.1",
+      "0x0a000001",
+      "167772161",
+      "012.0.0.01",
+      "0xc0a80101",
+      "192.168.257",
+      // IPv4-mapped and IPv4-compatible IPv6 addresses
+      "::ffff:10.0.0.1",
+      "[::ffff:a00:1]",
+      "::10.0.0.1",
+      "[::ffff:192.168.1.1]",
+      "::c0a8:101",
+    ];
+    for host in denied {
+      assert!(perms.net.check(&(host, None), None).is_err(), "{}", host);
+    }
+    for host in ["10.0.0.2", "::ffff:10.0.0.2", "0x0a000002", "::1"] {
+      assert!(perms.net.check(&(host, None), None).is_ok(), "{}", host);
+    }
+
+    let mut perms = Permissions::from_options(&PermissionsOptions {
+      allow_net: Some(vec![]),
+      deny_net: Some(svec!["[::ffff:10.0.0.1]", "::ffff:172.16.0.0/108"]),
+      ..Default::default()
+    })
+    .unwrap();
+    for host in ["10.0.0.1", "167772161", "172.16.5.4", "::ffff:172.31.0.1"] {
+      assert!(perms.net.check(&(host, None), None).is_err(), "{}", host);
+    }
+    assert!(perms.net.check(&("172.32.0.1", None), None).is_ok());
+  }
+
   #[test]
   fn check_specifiers() {
     set_prompter(Box::new(TestPrompter));
@@ -8,6 +8,8 @@ use crate::tokio_util::create_and_run_current_thread;
 use crate::worker::FormatJsErrorFn;
 use crate::BootstrapOptions;
 use deno_broadcast_channel::InMemoryBroadcastChannel;
+use deno_cache::CacheImpl;
+use deno_cache::CacheLimits;
 use deno_cache::CreateCache;
 use deno_cache::SqliteBackedCache;
 use deno_core::ascii_str;
@@ -348,6 +350,11 @@ pub struct WebWorkerOptions {
   pub shared_array_buffer_store: Option<SharedArrayBufferStore>,
   pub compiled_wasm_module_store: Option<CompiledWasmModuleStore>,
   pub cache_storage_dir: Option<std::path::PathBuf>,
+  /// Size limits of the Cache API storage in `cache_storage_dir`.
+  pub cache_storage_limits: CacheLimits,
+  /// Creates the backend of the Cache API, takes precedence over
+  /// `cache_storage_dir`.
+  pub create_cache: Option<CreateCache<CacheImpl>>,
   pub stdio: Stdio,
 }
 
@@ -389,9 +396,17 @@ impl WebWorker {
     // Permissions: many ops depend on this
     let unstable = options.bootstrap.unstable;
     let enable_testing_features = options.bootstrap.enable_testing_features;
-    let create_cache = options.cache_storage_dir.map(|storage_dir| {
-      let create_cache_fn = move || SqliteBackedCache::new(storage_dir.clone());
-      CreateCache(Arc::new(create_cache_fn))
+    let cache_storage_limits = options.cache_storage_limits;
+    let create_cache = options.create_cache.take().or_else(|| {
+      options.cache_storage_dir.take().map(|storage_dir| {
+        let create_cache_fn = move || {
+          CacheImpl::Sqlite(SqliteBackedCache::with_limits(
+            storage_dir.clone(),
+            cache_storage_limits,
+          ))
+        };
+        CreateCache(Arc::new(create_cache_fn))
+      })
     });
 
     // NOTE(bartlomieju): ordering is important here, keep it in sync with
@@ -417,15 +432,13 @@ impl WebWorker {
           ..Default::default()
         },
       ),
-      deno_cache::deno_cache::init_ops_and_esm::<SqliteBackedCache>(
-        create_cache,
-      ),
+      deno_cache::deno_cache::init_ops_and_esm::<CacheImpl>(create_cache),
       deno_websocket::deno_websocket::init_ops_and_esm::<PermissionsContainer>(
         options.bootstrap.user_agent.clone(),
         options.root_cert_store_provider.clone(),
         options.unsafely_ignore_certificate_errors.clone(),
       ),
-      deno_webstorage::deno_webstorage::init_ops_and_esm(None).disable(),
+      deno_webstorage::deno_webstorage::init_ops_and_esm(None, None).disable(),
       deno_crypto::deno_crypto::init_ops_and_esm(options.seed),
       deno_broadcast_channel::deno_broadcast_channel::init_ops_and_esm(
         options.broadcast_channel.clone(),
@@ -9,6 +9,8 @@ use std::task::Context;
 use std::task::Poll;
 
 use deno_broadcast_channel::InMemoryBroadcastChannel;
+use deno_cache::CacheImpl;
+use deno_cache::CacheLimits;
 use deno_cache::CreateCache;
 use deno_cache::SqliteBackedCache;
 use deno_core::ascii_str;
@@ -122,7 +124,15 @@ pub struct WorkerOptions {
   /// error in JavaScript.
   pub get_error_class_fn: Option<GetErrorClassFn>,
   pub cache_storage_dir: Option<std::path::PathBuf>,
+  /// Size limits of the Cache API storage in `cache_storage_dir`.
+  pub cache_storage_limits: CacheLimits,
+  /// Creates the backend of the Cache API, takes precedence over
+  /// `cache_storage_dir`.
+  pub create_cache: Option<CreateCache<CacheImpl>>,
   pub origin_storage_dir: Option<std::path::PathBuf>,
+  /// Maximum size in bytes of `localStorage` and `sessionStorage`. Defaults to
+  /// [deno_webstorage::DEFAULT_MAX_STORAGE_BYTES].
+  pub local_storage_quota: Option<usize>,
   pub blob_store: Arc<BlobStore>,
   pub broadcast_channel: InMemoryBroadcastChannel,
 
@@ -161,7 +171,10 @@ impl Default for WorkerOptions {
       format_js_error_fn: Default::default(),
       get_error_class_fn: Default::default(),
       origin_storage_dir: Default::default(),
+      local_storage_quota: Default::default(),
       cache_storage_dir: Default::default(),
+      cache_storage_limits: Default::default(),
+      create_cache: Default::default(),
       broadcast_channel: Default::default(),
       source_map_getter: Default::default(),
       root_cert_store_provider: Default::default(),
@@ -210,9 +223,17 @@ impl MainWorker {
     let unstable = options.bootstrap.unstable;
     let enable_testing_features = options.bootstrap.enable_testing_features;
     let exit_code = ExitCode(Arc::new(AtomicI32::new(0)));
-    let create_cache = options.cache_storage_dir.map(|storage_dir| {
-      let create_cache_fn = move || SqliteBackedCache::new(storage_dir.clone());
-      CreateCache(Arc::new(create_cache_fn))
+    let cache_storage_limits = options.cache_storage_limits;
+    let create_cache = options.create_cache.take().or_else(|| {
+      options.cache_storage_dir.take().map(|storage_dir| {
+        let create_cache_fn = move || {
+          CacheImpl::Sqlite(SqliteBackedCache::with_limits(
+            storage_dir.clone(),
+            cache_storage_limits,
+          ))
+        };
+        CreateCache(Arc::new(create_cache_fn))
+      })
     });
 
     // NOTE(bartlomieju): ordering is important here, keep it in sync with
@@ -237,9 +258,7 @@ impl MainWorker {
           ..Default::default()
         },
       ),
-      deno_cache::deno_cache::init_ops_and_esm::<SqliteBackedCache>(
-        create_cache,
-      ),
+      deno_cache::deno_cache::init_ops_and_esm::<CacheImpl>(create_cache),
       deno_websocket::deno_websocket::init_ops_and_esm::<PermissionsContainer>(
         options.bootstrap.user_agent.clone(),
         options.root_cert_store_provider.clone(),
@@ -247,6 +266,7 @@ impl MainWorker {
       ),
       deno_webstorage::deno_webstorage::init_ops_and_esm(
         options.origin_storage_dir.clone(),
+        options.local_storage_quota,
       ),
       deno_crypto::deno_crypto::init_ops_and_esm(options.seed),
       deno_broadcast_channel::deno_broadcast_channel::init_ops_and_esm(
@@ -1,7 +1,90 @@
 // Copyright 2018-2023 the Deno authors. All rights reserved. MIT license.
 
+use std::collections::HashMap;
+use std::time::SystemTime;
+
+use datapath::Enqueue;
+use datapath::QueueMessage;
+
 // Generated code, disable lints
 #[allow(clippy::all, non_snake_case)]
 pub mod datapath {
   include!(concat!(env!("OUT_DIR"), "/datapath.rs"));
 }
+
+const DEFAULT_BACKOFF_SCHEDULE: [u32; 5] = [100, 1000, 5000, 30000, 60000];
+
+struct QueuedMessage {
+  deadline_ms: i64,
+  payload: Vec<u8>,
+  backoff_schedule: Vec<u32>,
+}
+
+/// An in-memory stand-in for the queue of a KV Connect data plane.
+///
+/// Messages whose delivery failed are redelivered according to their backoff
+/// schedule. Undelivered messages are dropped, because the mock data plane
+/// does not store any keys.
+#[derive(Default)]
+pub struct InMemoryQueue {
+  next_id: u64,
+  ready: Vec<(String, QueuedMessage)>,
+  running: HashMap<String, QueuedMessage>,
+}
+
+impl InMemoryQueue {
+  pub fn enqueue(&mut self, enqueue: Enqueue) {
+    let id = format!("{:016x}", self.next_id);
+    self.next_id += 1;
+    let backoff_schedule = if enqueue.backoff_schedule.is_empty() {
+      DEFAULT_BACKOFF_SCHEDULE.to_vec()
+    } else {
+      enqueue.backoff_schedule
+    };
+    self.ready.push((
+      id,
+      QueuedMessage {
+        deadline_ms: enqueue.deadline_ms,
+        payload: enqueue.payload,
+        backoff_schedule,
+      },
+    ));
+  }
+
+  pub fn dequeue(&mut self) -> Option<QueueMessage> {
+    let now = now_ms();
+    let (index, _) = self
+      .ready
+      .iter()
+      .enumerate()
+      .filter(|(_, (_, msg))| msg.deadline_ms <= now)
+      .min_by_key(|(_, (_, msg))| msg.deadline_ms)?;
+    let (id, msg) = self.ready.remove(index);
+    let out = QueueMessage {
+      id: id.clone(),
+      payload: msg.payload.clone(),
+    };
+    self.running.insert(id, msg);
+    Some(out)
+  }
+
+  /// Returns `false` if no message with the given id is being delivered.
+  pub fn finish(&mut self, id: &str, success: bool) -> bool {
+    let Some(mut msg) = self.running.remove(id) else {
+      return false;
+    };
+    if !success && !msg.backoff_schedule.is_empty() {
+      let backoff = msg.backoff_schedule.remove(0);
+      msg.deadline_ms = now_ms() + backoff as i64;
+      self.ready.push((id.to_string(), msg));
+    }
+    true
+  }
+}
+
+fn now_ms() -> i64 {
+  SystemTime::now()
+    .duration_since(SystemTime::UNIX_EPOCH)
+    .unwrap()
+    .as_millis() as i64
+}
@@ -18,9 +18,14 @@ use hyper::StatusCode;
 use kv_remote::datapath::AtomicWrite;
 use kv_remote::datapath::AtomicWriteOutput;
 use kv_remote::datapath::AtomicWriteStatus;
+use kv_remote::datapath::QueueDequeue;
+use kv_remote::datapath::QueueDequeueOutput;
+use kv_remote::datapath::QueueFinish;
+use kv_remote::datapath::QueueFinishOutput;
 use kv_remote::datapath::ReadRangeOutput;
 use kv_remote::datapath::SnapshotRead;
 use kv_remote::datapath::SnapshotReadOutput;
+use kv_remote::InMemoryQueue;
 use npm::CUSTOM_NPM_PACKAGE_CACHE;
 use once_cell::sync::Lazy;
 use pretty_assertions::assert_eq;
@@ -110,6 +115,11 @@ pub const PERMISSION_DENIED_PATTERN: &str = "PermissionDenied";
 static GUARD: Lazy<Mutex<HttpServerCount>> =
   Lazy::new(|| Mutex::new(HttpServerCount::default()));
 
+/// Queues of the mock KV Connect data plane, keyed by database id so that
+/// concurrently running tests don't receive each other's messages.
+static KV_QUEUES: Lazy<Mutex<HashMap<String, InMemoryQueue>>> =
+  Lazy::new(|| Mutex::new(HashMap::new()));
+
 pub fn env_vars_for_npm_tests_no_sync_download() -> Vec<(String, String)> {
   vec![
     ("NPM_CONFIG_REGISTRY".to_string(), npm_registry_url()),
@@ -741,6 +751,15 @@ async fn absolute_redirect(
   Ok(file_resp)
 }
 
+/// Returns the id of the database a KV Connect data plane request is for.
+fn kv_database_id(headers: &hyper::HeaderMap) -> String {
+  headers
+    .get("x-transaction-domain-id")
+    .and_then(|x| x.to_str().ok())
+    .unwrap_or(KV_DATABASE_ID)
+    .to_string()
+}
+
 async fn main_server(
   req: Request<Body>,
 ) -> Result<Response<Body>, hyper::http::Error> {
@@ -1126,13 +1145,24 @@ async fn main_server(
         );
       }
 
+      // Tests that need a database of their own pass its id in the query.
+      let database_id = req
+        .uri()
+        .query()
+        .and_then(|query| {
+          url::form_urlencoded::parse(query.as_bytes())
+            .find(|(key, _)| key == "databaseId")
+            .map(|(_, value)| value.into_owned())
+        })
+        .unwrap_or_else(|| KV_DATABASE_ID.to_string());
+
       Ok(
         Response::builder()
           .header("content-type", "application/json")
           .body(Body::from(
             serde_json::json!({
               "version": 1,
-              "databaseId": KV_DATABASE_ID,
+              "databaseId": database_id,
               "endpoints": [
                 {
                   "url": format!("http://localhost:{}/kv_blackhole", PORT),
@@ -1284,10 +1314,11 @@ async fn main_server(
         );
       }
 
+      let database_id = kv_database_id(req.headers());
       let body = hyper::body::to_bytes(req.into_body())
         .await
         .unwrap_or_default();
-      let Ok(_body): Result<AtomicWrite, _> = prost::Message::decode(&body[..])
+      let Ok(body): Result<AtomicWrite, _> = prost::Message::decode(&body[..])
       else {
         return Ok(
           Response::builder()
@@ -1296,6 +1327,13 @@ async fn main_server(
             .unwrap(),
         );
       };
+      if !body.enqueues.is_empty() {
+        let mut queues = KV_QUEUES.lock().unwrap();
+        let queue = queues.entry(database_id).or_default();
+        for enqueue in body.enqueues {
+          queue.enqueue(enqueue);
+        }
+      }
       Ok(
         Response::builder()
           .body(Body::from(
@@ -1309,6 +1347,96 @@ async fn main_server(
           .unwrap(),
       )
     }
+    (&hyper::Method::POST, "/kv_blackhole/queue_dequeue") => {
+      if req
+        .headers()
+        .get("authorization")
+        .and_then(|x| x.to_str().ok())
+        .unwrap_or_default()
+        != format!("Bearer {}", KV_DATABASE_TOKEN)
+      {
+        return Ok(
+          Response::builder()
+            .status(StatusCode::UNAUTHORIZED)
+            .body(Body::empty())
+            .unwrap(),
+        );
+      }
+
+      let database_id = kv_database_id(req.headers());
+      let body = hyper::body::to_bytes(req.into_body())
+        .await
+        .unwrap_or_default();
+      let Ok(_body): Result<QueueDequeue, _> =
+        prost::Message::decode(&body[..])
+      else {
+        return Ok(
+          Response::builder()
+            .status(StatusCode::BAD_REQUEST)
+            .body(Body::empty())
+            .unwrap(),
+        );
+      };
+      let message = KV_QUEUES
+        .lock()
+        .unwrap()
+        .get_mut(&database_id)
+        .and_then(|queue| queue.dequeue());
+      Ok(
+        Response::builder()
+          .body(Body::from(QueueDequeueOutput { message }.encode_to_vec()))
+          .unwrap(),
+      )
+    }
+    (&hyper::Method::POST, "/kv_blackhole/queue_finish") => {
+      if req
+        .headers()
+        .get("authorization")
+        .and_then(|x| x.to_str().ok())
+        .unwrap_or_default()
+        != format!("Bearer {}", KV_DATABASE_TOKEN)
+      {
+        return Ok(
+          Response::builder()
+            .status(StatusCode::UNAUTHORIZED)
+            .body(Body::empty())
+            .unwrap(),
+        );
+      }
+
+      let database_id = kv_database_id(req.headers());
+      let body = hyper::body::to_bytes(req.into_body())
+        .await
+        .unwrap_or_default();
+      let Ok(body): Result<QueueFinish, _> = prost::Message::decode(&body[..])
+      else {
+        return Ok(
+          Response::builder()
+            .status(StatusCode::BAD_REQUEST)
+            .body(Body::empty())
+            .unwrap(),
+        );
+      };
+      let finished = KV_QUEUES
+        .lock()
+        .unwrap()
+        .get_mut(&database_id)
+        .map(|queue| queue.finish(&body.id, body.success))
+        .unwrap_or(false);
+      if !finished {
+        return Ok(
+          Response::builder()
+            .status(StatusCode::NOT_FOUND)
+            .body(Body::empty())
+            .unwrap(),
+        );
+      }
+      Ok(
+        Response::builder()
+          .body(Body::from(QueueFinishOutput {}.encode_to_vec()))
+          .unwrap(),
+      )
+    }
     _ => {
       let mut file_path = testdata_path().to_path_buf();
       file_path.push(&req.uri().path()[1..]);